@@ -0,0 +1,61 @@
+use std::time::SystemTime;
+
+/// Width of an age column, wide enough for every age below a hundred years.
+pub const AGE_COLUMN_WIDTH: usize = "364d".len();
+
+/// Which ages of the items to display next to their sizes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AgeFormat {
+    /// The time the ages are relative to.
+    pub now: SystemTime,
+    /// Display the ages of the newest and the oldest modification.
+    pub modified: bool,
+    /// Display the age of the latest access.
+    pub accessed: bool,
+}
+
+impl AgeFormat {
+    /// Render the enabled age columns, separated and preceded by spaces.
+    pub fn columns(
+        self,
+        newest_modified: Option<SystemTime>,
+        oldest_modified: Option<SystemTime>,
+        accessed: Option<SystemTime>,
+    ) -> String {
+        let mut columns = String::new();
+        let mut push = |time| {
+            let age = relative_age(self.now, time);
+            columns += &format!(" {:>width$}", age, width = AGE_COLUMN_WIDTH);
+        };
+        if self.modified {
+            push(newest_modified);
+            push(oldest_modified);
+        }
+        if self.accessed {
+            push(accessed);
+        }
+        columns
+    }
+}
+
+/// Describe how long ago `time` was in the largest fitting unit, e.g. `"3y"` or `"12d"`.
+///
+/// Unknown times are displayed as `"-"`, and times in the future as `"0s"`.
+pub fn relative_age(now: SystemTime, time: Option<SystemTime>) -> String {
+    let time = match time {
+        None => return "-".to_string(),
+        Some(time) => time,
+    };
+    let seconds = now.duration_since(time).map_or(0, |age| age.as_secs());
+    const MINUTE: u64 = 60;
+    const HOUR: u64 = 60 * MINUTE;
+    const DAY: u64 = 24 * HOUR;
+    const YEAR: u64 = 365 * DAY;
+    match seconds {
+        _ if seconds >= YEAR => format!("{}y", seconds / YEAR),
+        _ if seconds >= DAY => format!("{}d", seconds / DAY),
+        _ if seconds >= HOUR => format!("{}h", seconds / HOUR),
+        _ if seconds >= MINUTE => format!("{}m", seconds / MINUTE),
+        _ => format!("{}s", seconds),
+    }
+}
@@ -1,23 +1,55 @@
+mod interrupt;
 pub mod sub;
 
 pub use sub::Sub;
 
+use sub::{emit_output, prune};
+
 use crate::{
+    age_format::AgeFormat,
+    archive::{read_archive, ArchiveError},
     args::{Args, Quantity},
+    capacity::{Capacity, CapacitySettings},
     data_tree::DataTree,
-    reporter::{ErrorOnlyReporter, ErrorReport, ProgressAndErrorReporter, ProgressReport},
+    du::{parse_du_output, DuHierarchy},
+    history::{HistorySettings, HistoryStore, Trend},
+    input::read_input,
+    listed_tree_builder::read_file_list,
+    ncdu::{self, NcduData, NcduEntry, NcduSize},
+    os_string_display::OsStringDisplay,
+    output_format::{OutputFormat, RenderContext},
+    overlapping_roots::canonical_root,
+    reporter::{
+        ErrorOnlyReporter, ErrorReport, ParallelReporter, ProgressAndErrorReporter, ProgressReport,
+    },
     runtime_error::RuntimeError,
-    size::{Bytes, Size},
-    size_getters::GET_APPARENT_SIZE,
-    visualizer::Direction,
+    scan_cache::CacheSettings,
+    size::{Blocks, ByteDays, Bytes, Size, Timed, TimedFormat},
+    size_getters::{
+        get_cold_bytes, get_timed_cold_bytes, untouched_since, SizeGetter, GET_APPARENT_SIZE,
+        GET_TIMED_APPARENT_SIZE,
+    },
+    sort_key::SortKey,
+    visualizer::{ColumnWidthDistribution, Direction, Visualizer},
+    weighted_tree::{parse_weighted_text, WeightedTreeBuilder},
+};
+use pipe_trait::Pipe;
+use std::{
+    fs::File,
+    io::{stdout, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
-use std::time::Duration;
 use structopt_utilities::StructOptUtils;
 
 #[cfg(unix)]
 use crate::{
-    size::Blocks,
-    size_getters::{GET_BLOCK_COUNT, GET_BLOCK_SIZE},
+    ownership::{group_by_owner, owner_ids, AccountNames, OwnerKind},
+    size::Owned,
+    size_getters::{
+        get_owned_cold_bytes, GET_BLOCK_COUNT, GET_BLOCK_SIZE, GET_OWNED_APPARENT_SIZE,
+        GET_OWNED_BLOCK_COUNT, GET_OWNED_BLOCK_SIZE, GET_TIMED_BLOCK_COUNT, GET_TIMED_BLOCK_SIZE,
+    },
 };
 
 /// The main application.
@@ -35,7 +67,7 @@ impl App {
     }
 
     /// Run the application.
-    pub fn run(self) -> Result<(), RuntimeError> {
+    pub fn run(mut self) -> Result<(), RuntimeError> {
         // DYNAMIC DISPATCH POLICY:
         //
         // Errors rarely occur, therefore, using dynamic dispatch to report errors have an acceptable
@@ -43,10 +75,23 @@ impl App {
         //
         // The other operations which are invoked frequently should not utilize dynamic dispatch.
 
-        let column_width_distribution = self
-            .args
-            .column_width_distribution()
-            .expect("get column width distribution");
+        if self.args.watch.is_some() && self.args.output_format != OutputFormat::Text {
+            return Err(RuntimeError::WatchUnsupported);
+        }
+
+        if self.args.history_trend.is_some() && self.args.output_format != OutputFormat::Text {
+            return Err(RuntimeError::TrendUnsupported);
+        }
+
+        if self.args.reproducible || self.args.watch.is_some() {
+            self.args.progress = false;
+        }
+
+        // Only the progress reporter counts the items and the errors that the metrics include.
+        let print_progress = self.args.progress;
+        if self.args.output_format == OutputFormat::Prometheus && self.args.watch.is_none() {
+            self.args.progress = true;
+        }
 
         let report_error = if self.args.silent_errors {
             ErrorReport::SILENT
@@ -54,63 +99,568 @@ impl App {
             ErrorReport::TEXT
         };
 
+        // The dump may be written to the standard output, which is not a terminal.
+        if let Some(destination) = &self.args.ncdu_export {
+            let root = match self.args.files.as_slice() {
+                [] => Path::new("."),
+                [root] => root.as_path(),
+                _ => return Err(RuntimeError::NcduMultipleRoots),
+            };
+            let name = canonical_root(root).unwrap_or_else(|| root.to_path_buf());
+            let entry = match self.args.quantity {
+                Quantity::ApparentSize => {
+                    scan_for_ncdu(root, name, GET_APPARENT_SIZE, report_error, print_progress)
+                }
+                #[cfg(unix)]
+                Quantity::BlockCount => {
+                    scan_for_ncdu(root, name, GET_BLOCK_COUNT, report_error, print_progress)
+                }
+                _ => return Err(RuntimeError::NcduQuantityUnsupported),
+            };
+            let timestamp = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |duration| duration.as_secs());
+            let result = if destination.as_os_str() == "-" {
+                let stdout = stdout();
+                let mut output = BufWriter::new(stdout.lock());
+                entry
+                    .write_dump(&mut output, timestamp)
+                    .and_then(|()| output.flush())
+            } else {
+                File::create(destination).and_then(|file| {
+                    let mut output = BufWriter::new(file);
+                    entry
+                        .write_dump(&mut output, timestamp)
+                        .and_then(|()| output.flush())
+                })
+            };
+            return result.map_err(|error| {
+                eprintln!("[error] {:?}: {}", destination, error);
+                RuntimeError::NcduFailure
+            });
+        }
+
+        // Only the text format fits its lines to the terminal, so the other formats must not
+        // require one (`--watch` is only available with the text format), nor must the trend.
+        let fit_to_terminal =
+            self.args.output_format == OutputFormat::Text && self.args.history_trend.is_none();
+        let column_width_distribution = if fit_to_terminal {
+            self.args
+                .column_width_distribution()
+                .expect("get column width distribution")
+        } else {
+            ColumnWidthDistribution::total(0) // unused by the other formats and the trend.
+        };
+
         fn error_only_reporter<Data: Size>(
             report_error: fn(ErrorReport),
+            _print_progress: bool,
         ) -> ErrorOnlyReporter<fn(ErrorReport)> {
             ErrorOnlyReporter::new(report_error)
         }
 
         fn progress_and_error_reporter<Data>(
             report_error: fn(ErrorReport),
+            print_progress: bool,
         ) -> ProgressAndErrorReporter<Data, fn(ErrorReport)>
         where
             Data: Size + Into<u64> + Send + Sync,
             ProgressReport<Data>: Default + 'static,
         {
             ProgressAndErrorReporter::new(
-                ProgressReport::TEXT,
+                if print_progress {
+                    ProgressReport::TEXT
+                } else {
+                    ProgressReport::SILENT
+                },
                 Duration::from_millis(100),
                 report_error,
             )
         }
 
-        fn sort<Name, Data: Size>(children: &mut Vec<DataTree<Name, Data>>) {
-            children.sort_by(|left, right| left.data().cmp(&right.data()).reverse());
+        fn sort_children<Data: Size>(
+            sort: Option<SortKey>,
+        ) -> impl Fn(&mut Vec<DataTree<OsStringDisplay, Data>>) + Copy + Send + Sync {
+            move |children| {
+                if let Some(sort) = sort.filter(|sort| !sort.requires_whole_tree()) {
+                    sort.sort(children);
+                }
+            }
+        }
+
+        fn sort_timed_children<Data>(
+            sort: Option<SortKey>,
+        ) -> impl Fn(&mut Vec<DataTree<OsStringDisplay, Timed<Data>>>) + Copy + Send + Sync
+        where
+            Timed<Data>: Size,
+        {
+            move |children| {
+                if let Some(sort) = sort.filter(|sort| !sort.requires_whole_tree()) {
+                    sort.sort_timed(children);
+                }
+            }
+        }
+
+        #[cfg(unix)]
+        fn group_by_owner_tree<Data>(
+            kind: OwnerKind,
+            sort: Option<SortKey>,
+        ) -> impl Fn(DataTree<OsStringDisplay, Owned<Data>>) -> DataTree<OsStringDisplay, Owned<Data>>
+        where
+            Data: Default + Copy + Send + Sync,
+            Owned<Data>: Size + Send + Sync,
+        {
+            move |data_tree| {
+                let names = AccountNames::lookup(kind, owner_ids(&data_tree, kind));
+                group_by_owner(&data_tree, kind, &names, sort_children::<Owned<Data>>(sort))
+            }
+        }
+
+        fn scan_for_ncdu<Data>(
+            root: &Path,
+            name: PathBuf,
+            get_data: SizeGetter<Data>,
+            report_error: fn(ErrorReport),
+            print_progress: bool,
+        ) -> NcduEntry
+        where
+            Data: Size + Send + Sync,
+            NcduData<Data>: Size + Into<u64> + Send + Sync,
+            ProgressReport<NcduData<Data>>: Default + 'static,
+        {
+            let reporter =
+                progress_and_error_reporter::<NcduData<Data>>(report_error, print_progress);
+            let entry = ncdu::scan(root, name, get_data, &reporter);
+            if reporter.destroy().is_err() {
+                eprintln!("[warning] Failed to destroy the thread that reports progress");
+            }
+            eprint!("\r"); // erase progress report.
+            entry
+        }
+
+        fn show_imported<Data>(
+            args: &Args,
+            mut data_tree: DataTree<OsStringDisplay, Data>,
+            sort: Option<SortKey>,
+            bytes_format: Data::DisplayFormat,
+            column_width_distribution: ColumnWidthDistribution,
+        ) -> Result<(), RuntimeError>
+        where
+            Data: Size + Into<u64> + Send,
+        {
+            if let Some(sort) = sort {
+                sort.sort_tree(&mut data_tree);
+            }
+            prune(
+                &mut data_tree,
+                args.minimal_ratio.into(),
+                args.collapse_chains,
+            );
+            let visualizer = Visualizer {
+                data_tree: &data_tree,
+                bytes_format,
+                direction: Direction::from_top_down(args.top_down),
+                column_width_distribution,
+                max_depth: args.max_depth,
+                proportion_total: None,
+            };
+            let context = RenderContext {
+                color_scheme: args.svg_colors,
+                quantity: args.quantity.as_ref(),
+                unit: args.quantity.unit(),
+                progress: None,
+                scan_duration: None,
+                markdown_bar: args.markdown_bar,
+            };
+            let output = args.output_format.render(visualizer, context);
+            emit_output(&output, args.output_file.as_deref()) // output already ends with "\n", println! isn't needed here.
+        }
+
+        fn show_trend<Data>(
+            store: &HistoryStore,
+            label: &str,
+            roots: &[PathBuf],
+            count: usize,
+            capacity: Option<Capacity>,
+            bytes_format: Data::DisplayFormat,
+            output_file: Option<&Path>,
+        ) -> Result<(), RuntimeError>
+        where
+            Data: Size + From<u64> + Into<u64>,
+        {
+            let snapshots = store.load::<Data>(label, roots, count).map_err(|error| {
+                eprintln!("[error] {}", error);
+                RuntimeError::HistoryFailure
+            })?;
+            if snapshots.len() < 2 {
+                return Err(RuntimeError::InsufficientHistory);
+            }
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |duration| duration.as_secs());
+            let trend = Trend {
+                snapshots: &snapshots,
+                capacity,
+                now,
+                bytes_format,
+            };
+            emit_output(&trend.to_string(), output_file)
+        }
+
+        if let Some(count) = self.args.history_trend {
+            let store = HistoryStore {
+                directory: self.args.history.clone().expect("required by clap"),
+            };
+            let label = self.args.quantity.as_ref();
+            let roots = canonical_roots(&self.args.files);
+            let capacity = Capacity::of(&roots[0]).ok(); // only comparable to quantities of bytes.
+            let bytes_format = self.args.bytes_format;
+            let output_file = self.args.output_file.as_deref();
+            return match self.args.quantity {
+                Quantity::ApparentSize => show_trend::<Bytes>(
+                    &store,
+                    label,
+                    &roots,
+                    count,
+                    capacity,
+                    bytes_format,
+                    output_file,
+                ),
+                #[cfg(unix)]
+                Quantity::BlockSize => show_trend::<Bytes>(
+                    &store,
+                    label,
+                    &roots,
+                    count,
+                    capacity,
+                    bytes_format,
+                    output_file,
+                ),
+                #[cfg(unix)]
+                Quantity::BlockCount => {
+                    show_trend::<Blocks>(&store, label, &roots, count, None, (), output_file)
+                }
+                Quantity::ColdBytes => show_trend::<ByteDays>(
+                    &store,
+                    label,
+                    &roots,
+                    count,
+                    None,
+                    bytes_format,
+                    output_file,
+                ),
+            };
+        }
+
+        let sort = match (self.args.no_sort, self.args.reproducible) {
+            (false, _) => Some(self.args.sort),
+            (true, false) => None,
+            (true, true) => Some(SortKey::Name), // order of entries in the filesystem varies
+        };
+
+        // Only the scans that are not grouped by owner carry modification times.
+        let is_imported = self.args.weighted_input.is_some()
+            || self.args.archive.is_some()
+            || self.args.ncdu_import.is_some()
+            || self.args.du_import.is_some();
+        if sort.map_or(false, SortKey::requires_modification_time)
+            && (is_imported || self.args.group_by_owner.is_some())
+        {
+            return Err(RuntimeError::ModificationTimeUnavailable);
+        }
+
+        // Hierarchies that are not scanned from the filesystem.
+        let imported_tree = if let Some(source) = &self.args.weighted_input {
+            let text = read_input(source).map_err(|error| {
+                eprintln!("[error] {:?}: {}", source, error);
+                RuntimeError::WeightedInputFailure
+            })?;
+            let entries =
+                parse_weighted_text(&String::from_utf8_lossy(&text), &self.args.key_separator)
+                    .map_err(|error| {
+                        eprintln!("[error] {:?}: {}", source, error);
+                        RuntimeError::WeightedInputFailure
+                    })?;
+            WeightedTreeBuilder {
+                name: OsStringDisplay::os_string_from(&self.args.root_label),
+                entries,
+                post_process_children: sort_children(sort),
+            }
+            .pipe(DataTree::<_, Bytes>::from)
+            .pipe(Some)
+        } else if let Some(archive) = &self.args.archive {
+            let compressed = self.args.compressed_size;
+            let entries = File::open(archive)
+                .map_err(ArchiveError::from)
+                .map(BufReader::new)
+                .and_then(read_archive)
+                .and_then(|entries| {
+                    entries
+                        .into_iter()
+                        .map(|entry| entry.into_weighted(compressed))
+                        .collect::<Result<Vec<_>, _>>()
+                })
+                .map_err(|error| {
+                    eprintln!("[error] {:?}: {}", archive, error);
+                    RuntimeError::ArchiveFailure
+                })?;
+            let name = archive
+                .file_name()
+                .unwrap_or_else(|| archive.as_os_str())
+                .pipe(OsStringDisplay::os_string_from);
+            WeightedTreeBuilder {
+                name,
+                entries,
+                post_process_children: sort_children(sort),
+            }
+            .pipe(DataTree::<_, Bytes>::from)
+            .pipe(Some)
+        } else if let Some(source) = &self.args.ncdu_import {
+            let size = match self.args.quantity {
+                Quantity::ApparentSize => NcduSize::Apparent,
+                #[cfg(unix)]
+                Quantity::BlockCount => NcduSize::Blocks,
+                _ => return Err(RuntimeError::NcduQuantityUnsupported),
+            };
+            let text = read_input(source).map_err(|error| {
+                eprintln!("[error] {:?}: {}", source, error);
+                RuntimeError::NcduFailure
+            })?;
+            let entry = NcduEntry::parse_dump(&text).map_err(|error| {
+                eprintln!("[error] {:?}: {}", source, error);
+                RuntimeError::NcduFailure
+            })?;
+            match size {
+                NcduSize::Apparent => entry.into_data_tree(size, sort_children(sort)).pipe(Some),
+                NcduSize::Blocks => {
+                    let data_tree = entry.into_data_tree::<Blocks, _>(size, sort_children(sort));
+                    return show_imported(
+                        &self.args,
+                        data_tree,
+                        sort,
+                        (),
+                        column_width_distribution,
+                    );
+                }
+            }
+        } else if let Some(source) = &self.args.du_import {
+            let text = read_input(source).map_err(|error| {
+                eprintln!("[error] {:?}: {}", source, error);
+                RuntimeError::DuImportFailure
+            })?;
+            let entries = parse_du_output(
+                &String::from_utf8_lossy(&text),
+                self.args.du_block_size.get(),
+            )
+            .map_err(|error| {
+                eprintln!("[error] {:?}: {}", source, error);
+                RuntimeError::DuImportFailure
+            })?;
+            let DuHierarchy { root, entries } = DuHierarchy::new(entries);
+            WeightedTreeBuilder {
+                name: root
+                    .as_ref()
+                    .unwrap_or(&self.args.root_label)
+                    .pipe(OsStringDisplay::os_string_from),
+                entries,
+                post_process_children: sort_children(sort),
+            }
+            .pipe(DataTree::<_, Bytes>::from)
+            .pipe(Some)
+        } else {
+            None
+        };
+
+        if let Some(data_tree) = imported_tree {
+            let bytes_format = self.args.bytes_format;
+            return show_imported(
+                &self.args,
+                data_tree,
+                sort,
+                bytes_format,
+                column_width_distribution,
+            );
+        }
+
+        // Times and owners are only collected when they are needed.
+        #[derive(Clone, Copy, PartialEq, Eq)]
+        enum DataKind {
+            Plain,
+            Timed,
+            Owned,
         }
+        let data_kind = match (self.args.group_by_owner, sort) {
+            (Some(_), _) => DataKind::Owned,
+            (None, _) if self.args.show_age || self.args.show_access_age => DataKind::Timed,
+            (None, Some(sort)) if sort.requires_modification_time() => DataKind::Timed,
+            (None, _) => DataKind::Plain,
+        };
 
-        fn no_sort<Name, Data: Size>(_: &mut Vec<DataTree<Name, Data>>) {}
+        #[cfg(not(unix))]
+        if data_kind == DataKind::Owned {
+            return Err(RuntimeError::OwnershipUnsupported);
+        }
+
+        // Cached data must be reproducible from the metadata of the files alone.
+        let is_cacheable =
+            data_kind == DataKind::Plain && self.args.quantity != Quantity::ColdBytes;
+        if self.args.cache.is_some() && !is_cacheable {
+            return Err(RuntimeError::CacheUnsupported);
+        }
+
+        #[cfg(unix)]
+        let owner_kind = self.args.group_by_owner.unwrap_or(OwnerKind::User); // only used by `DataKind::Owned`
+
+        let now = SystemTime::now();
+        let age_format = AgeFormat {
+            now,
+            modified: self.args.show_age,
+            accessed: self.args.show_access_age,
+        };
+        let untouched_cutoff = self
+            .args
+            .untouched_for
+            .map(|days| Duration::from_secs(days.saturating_mul(24 * 60 * 60)))
+            .map(|age| now.checked_sub(age).unwrap_or(UNIX_EPOCH));
+
+        #[cfg(not(target_os = "linux"))]
+        if self.args.show_capacity || self.args.relative_to_capacity {
+            return Err(RuntimeError::CapacityUnsupported);
+        }
+
+        let is_measured_in_bytes = match self.args.quantity {
+            #[cfg(unix)]
+            Quantity::BlockCount => false,
+            Quantity::ColdBytes => false,
+            _ => true,
+        };
+        if self.args.relative_to_capacity && !is_measured_in_bytes {
+            return Err(RuntimeError::CapacityIncomparable);
+        }
+
+        let mut capacity_header = String::new();
+        let mut capacity_total = 0;
+        let mut capacity_settings = None;
+        if self.args.show_capacity || self.args.relative_to_capacity {
+            let settings = CapacitySettings {
+                roots: if self.args.files.is_empty() {
+                    vec![".".into()]
+                } else {
+                    self.args.files.clone()
+                },
+                bytes_format: self.args.bytes_format,
+            };
+            let header = settings.header();
+            for (root, error) in &header.errors {
+                eprintln!(
+                    "[warning] Failed to query the capacity of {:?}: {}",
+                    root, error,
+                );
+            }
+            capacity_total = header.total;
+            if self.args.show_capacity {
+                capacity_header = header.text;
+                capacity_settings = Some(settings);
+            }
+        }
+        let proportion_total = if self.args.relative_to_capacity {
+            Some(capacity_total)
+        } else {
+            None
+        };
+
+        let listed_files = match &self.args.files_from {
+            None => None,
+            Some(source) => match read_file_list(source) {
+                Ok(paths) => Some(paths),
+                Err(error) => {
+                    eprintln!("[error] {:?}: {}", source, error);
+                    return Err(RuntimeError::FileListFailure);
+                }
+            },
+        };
+
+        let history_roots = canonical_roots(&self.args.files);
 
         macro_rules! sub {
             (
                 $data:ty => $format:expr;
-                $quantity:ident => $get_data:ident;
+                $quantity:ident => $get_data:expr;
                 $progress:literal => $create_reporter:ident;
-                $no_sort:literal => $post_process_children:ident;
+                $kind:ident => $post_process_children:expr, $post_process_tree:expr;
             ) => {
-                if let Args {
-                    quantity: Quantity::$quantity,
-                    progress: $progress,
-                    no_sort: $no_sort,
-                    files,
-                    bytes_format,
-                    top_down,
-                    max_depth,
-                    minimal_ratio,
-                    ..
-                } = self.args
-                {
-                    return Sub {
-                        direction: Direction::from_top_down(top_down),
-                        get_data: $get_data,
-                        post_process_children: $post_process_children,
-                        reporter: $create_reporter::<$data>(report_error),
-                        bytes_format: $format(bytes_format),
+                if data_kind == DataKind::$kind {
+                    if let Args {
+                        quantity: Quantity::$quantity,
+                        progress: $progress,
                         files,
-                        column_width_distribution,
+                        bytes_format,
+                        top_down,
                         max_depth,
                         minimal_ratio,
+                        collapse_chains,
+                        group_by_extension,
+                        multi_part_extensions,
+                        watch,
+                        live,
+                        cache,
+                        cache_sample_ratio,
+                        history,
+                        history_depth,
+                        root_label,
+                        shared_prefix,
+                        output_format,
+                        svg_colors,
+                        markdown_bar,
+                        output_file,
+                        ..
+                    } = self.args
+                    {
+                        return Sub {
+                            direction: Direction::from_top_down(top_down),
+                            get_data: untouched_since($get_data, untouched_cutoff),
+                            post_process_children: $post_process_children,
+                            post_process_tree: $post_process_tree,
+                            sort,
+                            reporter: $create_reporter::<$data>(report_error, print_progress),
+                            bytes_format: $format(bytes_format),
+                            files,
+                            column_width_distribution,
+                            max_depth,
+                            minimal_ratio,
+                            collapse_chains,
+                            group_by_extension: if group_by_extension {
+                                Some(multi_part_extensions)
+                            } else {
+                                None
+                            },
+                            watch: watch.map(Duration::from),
+                            live,
+                            cache: cache.map(|file| CacheSettings {
+                                file,
+                                label: Quantity::$quantity.as_ref().to_string(),
+                                sample_ratio: cache_sample_ratio,
+                            }),
+                            header: capacity_header,
+                            capacity: capacity_settings,
+                            proportion_total,
+                            history: history.map(|directory| HistorySettings {
+                                store: HistoryStore { directory },
+                                label: Quantity::$quantity.as_ref().to_string(),
+                                roots: history_roots.clone(),
+                                max_depth: history_depth.get(),
+                            }),
+                            root_label,
+                            shared_prefix,
+                            listed_files,
+                            output_format,
+                            color_scheme: svg_colors,
+                            markdown_bar,
+                            quantity: Quantity::$quantity,
+                            output_file,
+                        }
+                        .run();
                     }
-                    .run();
                 }
             };
         }
@@ -119,28 +669,46 @@ impl App {
             Bytes => |x| x;
             ApparentSize => GET_APPARENT_SIZE;
             false => error_only_reporter;
-            false => sort;
+            Plain => sort_children::<Bytes>(sort), |data_tree| data_tree;
         }
 
         sub! {
-            Bytes => |x| x;
-            ApparentSize => GET_APPARENT_SIZE;
+            Timed<Bytes> => |size| TimedFormat { size, age: age_format };
+            ApparentSize => GET_TIMED_APPARENT_SIZE;
+            false => error_only_reporter;
+            Timed => sort_timed_children::<Bytes>(sort), |data_tree| data_tree;
+        }
+
+        #[cfg(unix)]
+        sub! {
+            Owned<Bytes> => |x| x;
+            ApparentSize => GET_OWNED_APPARENT_SIZE;
             false => error_only_reporter;
-            true => no_sort;
+            Owned => sort_children::<Owned<Bytes>>(sort),
+                group_by_owner_tree::<Bytes>(owner_kind, sort);
         }
 
         sub! {
             Bytes => |x| x;
             ApparentSize => GET_APPARENT_SIZE;
             true => progress_and_error_reporter;
-            false => sort;
+            Plain => sort_children::<Bytes>(sort), |data_tree| data_tree;
         }
 
         sub! {
-            Bytes => |x| x;
-            ApparentSize => GET_APPARENT_SIZE;
+            Timed<Bytes> => |size| TimedFormat { size, age: age_format };
+            ApparentSize => GET_TIMED_APPARENT_SIZE;
             true => progress_and_error_reporter;
-            true => no_sort;
+            Timed => sort_timed_children::<Bytes>(sort), |data_tree| data_tree;
+        }
+
+        #[cfg(unix)]
+        sub! {
+            Owned<Bytes> => |x| x;
+            ApparentSize => GET_OWNED_APPARENT_SIZE;
+            true => progress_and_error_reporter;
+            Owned => sort_children::<Owned<Bytes>>(sort),
+                group_by_owner_tree::<Bytes>(owner_kind, sort);
         }
 
         #[cfg(unix)]
@@ -148,15 +716,24 @@ impl App {
             Bytes => |x| x;
             BlockSize => GET_BLOCK_SIZE;
             false => error_only_reporter;
-            false => sort;
+            Plain => sort_children::<Bytes>(sort), |data_tree| data_tree;
         }
 
         #[cfg(unix)]
         sub! {
-            Bytes => |x| x;
-            BlockSize => GET_BLOCK_SIZE;
+            Timed<Bytes> => |size| TimedFormat { size, age: age_format };
+            BlockSize => GET_TIMED_BLOCK_SIZE;
             false => error_only_reporter;
-            true => no_sort;
+            Timed => sort_timed_children::<Bytes>(sort), |data_tree| data_tree;
+        }
+
+        #[cfg(unix)]
+        sub! {
+            Owned<Bytes> => |x| x;
+            BlockSize => GET_OWNED_BLOCK_SIZE;
+            false => error_only_reporter;
+            Owned => sort_children::<Owned<Bytes>>(sort),
+                group_by_owner_tree::<Bytes>(owner_kind, sort);
         }
 
         #[cfg(unix)]
@@ -164,15 +741,24 @@ impl App {
             Bytes => |x| x;
             BlockSize => GET_BLOCK_SIZE;
             true => progress_and_error_reporter;
-            false => sort;
+            Plain => sort_children::<Bytes>(sort), |data_tree| data_tree;
         }
 
         #[cfg(unix)]
         sub! {
-            Bytes => |x| x;
-            BlockSize => GET_BLOCK_SIZE;
+            Timed<Bytes> => |size| TimedFormat { size, age: age_format };
+            BlockSize => GET_TIMED_BLOCK_SIZE;
+            true => progress_and_error_reporter;
+            Timed => sort_timed_children::<Bytes>(sort), |data_tree| data_tree;
+        }
+
+        #[cfg(unix)]
+        sub! {
+            Owned<Bytes> => |x| x;
+            BlockSize => GET_OWNED_BLOCK_SIZE;
             true => progress_and_error_reporter;
-            true => no_sort;
+            Owned => sort_children::<Owned<Bytes>>(sort),
+                group_by_owner_tree::<Bytes>(owner_kind, sort);
         }
 
         #[cfg(unix)]
@@ -180,15 +766,24 @@ impl App {
             Blocks => |_| ();
             BlockCount => GET_BLOCK_COUNT;
             false => error_only_reporter;
-            false => sort;
+            Plain => sort_children::<Blocks>(sort), |data_tree| data_tree;
         }
 
         #[cfg(unix)]
         sub! {
-            Blocks => |_| ();
-            BlockCount => GET_BLOCK_COUNT;
+            Timed<Blocks> => |_| TimedFormat { size: (), age: age_format };
+            BlockCount => GET_TIMED_BLOCK_COUNT;
             false => error_only_reporter;
-            true => no_sort;
+            Timed => sort_timed_children::<Blocks>(sort), |data_tree| data_tree;
+        }
+
+        #[cfg(unix)]
+        sub! {
+            Owned<Blocks> => |_| ();
+            BlockCount => GET_OWNED_BLOCK_COUNT;
+            false => error_only_reporter;
+            Owned => sort_children::<Owned<Blocks>>(sort),
+                group_by_owner_tree::<Blocks>(owner_kind, sort);
         }
 
         #[cfg(unix)]
@@ -196,18 +791,88 @@ impl App {
             Blocks => |_| ();
             BlockCount => GET_BLOCK_COUNT;
             true => progress_and_error_reporter;
-            false => sort;
+            Plain => sort_children::<Blocks>(sort), |data_tree| data_tree;
         }
 
         #[cfg(unix)]
         sub! {
-            Blocks => |_| ();
-            BlockCount => GET_BLOCK_COUNT;
+            Timed<Blocks> => |_| TimedFormat { size: (), age: age_format };
+            BlockCount => GET_TIMED_BLOCK_COUNT;
             true => progress_and_error_reporter;
-            true => no_sort;
+            Timed => sort_timed_children::<Blocks>(sort), |data_tree| data_tree;
+        }
+
+        #[cfg(unix)]
+        sub! {
+            Owned<Blocks> => |_| ();
+            BlockCount => GET_OWNED_BLOCK_COUNT;
+            true => progress_and_error_reporter;
+            Owned => sort_children::<Owned<Blocks>>(sort),
+                group_by_owner_tree::<Blocks>(owner_kind, sort);
+        }
+
+        sub! {
+            ByteDays => |x| x;
+            ColdBytes => get_cold_bytes(now);
+            false => error_only_reporter;
+            Plain => sort_children::<ByteDays>(sort), |data_tree| data_tree;
+        }
+
+        sub! {
+            Timed<ByteDays> => |size| TimedFormat { size, age: age_format };
+            ColdBytes => get_timed_cold_bytes(now);
+            false => error_only_reporter;
+            Timed => sort_timed_children::<ByteDays>(sort), |data_tree| data_tree;
+        }
+
+        #[cfg(unix)]
+        sub! {
+            Owned<ByteDays> => |x| x;
+            ColdBytes => get_owned_cold_bytes(now);
+            false => error_only_reporter;
+            Owned => sort_children::<Owned<ByteDays>>(sort),
+                group_by_owner_tree::<ByteDays>(owner_kind, sort);
+        }
+
+        sub! {
+            ByteDays => |x| x;
+            ColdBytes => get_cold_bytes(now);
+            true => progress_and_error_reporter;
+            Plain => sort_children::<ByteDays>(sort), |data_tree| data_tree;
+        }
+
+        sub! {
+            Timed<ByteDays> => |size| TimedFormat { size, age: age_format };
+            ColdBytes => get_timed_cold_bytes(now);
+            true => progress_and_error_reporter;
+            Timed => sort_timed_children::<ByteDays>(sort), |data_tree| data_tree;
+        }
+
+        #[cfg(unix)]
+        sub! {
+            Owned<ByteDays> => |x| x;
+            ColdBytes => get_owned_cold_bytes(now);
+            true => progress_and_error_reporter;
+            Owned => sort_children::<Owned<ByteDays>>(sort),
+                group_by_owner_tree::<ByteDays>(owner_kind, sort);
         }
 
         dbg!(self.args);
         panic!("Invalid combination of arguments")
     }
 }
+
+/// Canonical paths of the roots to scan, which identify the snapshots of `--history`.
+fn canonical_roots(files: &[PathBuf]) -> Vec<PathBuf> {
+    let mut roots: Vec<_> = if files.is_empty() {
+        vec![PathBuf::from(".")]
+    } else {
+        files.to_vec()
+    }
+    .into_iter()
+    .map(|root| canonical_root(&root).unwrap_or(root))
+    .collect();
+    roots.sort();
+    roots.dedup();
+    roots
+}
@@ -0,0 +1,33 @@
+/// Escape sequence that hides the cursor while the frames of `--watch` are redrawn.
+pub const HIDE_CURSOR: &str = "\x1B[?25l";
+
+/// Make an interrupt (Ctrl-C) show the cursor again and end the line before exiting,
+/// return whether the handler is installed.
+#[cfg(unix)]
+pub fn restore_terminal_on_interrupt() -> bool {
+    use libc::{c_int, c_void, sighandler_t, signal, write, SIGINT, SIG_ERR, STDOUT_FILENO};
+
+    extern "C" fn restore_and_exit(_: c_int) {
+        // Show the cursor, then end the line that the interrupt (`^C`) was echoed on.
+        let restore = b"\x1B[?25h\n";
+        // SAFETY: `write` and `_exit` are async-signal-safe.
+        unsafe {
+            write(
+                STDOUT_FILENO,
+                restore.as_ptr() as *const c_void,
+                restore.len(),
+            );
+            libc::_exit(130);
+        }
+    }
+
+    let handler = restore_and_exit as extern "C" fn(c_int) as sighandler_t;
+    // SAFETY: the handler only calls async-signal-safe functions.
+    unsafe { signal(SIGINT, handler) != SIG_ERR }
+}
+
+/// Interrupts cannot be handled on this platform, the cursor is then never hidden.
+#[cfg(not(unix))]
+pub fn restore_terminal_on_interrupt() -> bool {
+    false
+}
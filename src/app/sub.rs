@@ -1,22 +1,45 @@
+use super::interrupt::{restore_terminal_on_interrupt, HIDE_CURSOR};
 use crate::{
-    args::Fraction,
+    args::{Fraction, Quantity},
+    capacity::CapacitySettings,
     data_tree::DataTree,
+    extension_breakdown::ExtensionBreakdown,
     fs_tree_builder::FsTreeBuilder,
+    history::HistorySettings,
+    listed_tree_builder::ListedTreeBuilder,
     os_string_display::OsStringDisplay,
+    output_format::{write_output_file, OutputFormat, RenderContext},
+    overlapping_roots::{canonical_root, remove_overlapping_roots},
     reporter::ParallelReporter,
     runtime_error::RuntimeError,
+    scan_cache::{CacheSettings, CachedFsTreeBuilder, ScanCache},
+    shared_prefix::nest_by_shared_prefix,
     size::Size,
+    sort_key::SortKey,
+    svg_chart::ColorScheme,
     visualizer::{ColumnWidthDistribution, Direction, Visualizer},
 };
-use std::{fs::Metadata, iter::once, num::NonZeroUsize, path::PathBuf};
+use pipe_trait::Pipe;
+use std::{
+    fs::Metadata,
+    io::{stdout, Write},
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    thread::sleep,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+#[cfg(target_os = "linux")]
+use crate::live::{LiveTree, UpdateSummary};
 
 /// The sub program of the main application.
-pub struct Sub<Data, GetData, Report, PostProcessChildren>
+pub struct Sub<Data, GetData, Report, PostProcessChildren, PostProcessTree>
 where
     Data: Size + Into<u64> + Send + Sync,
     Report: ParallelReporter<Data> + Sync,
     GetData: Fn(&Metadata) -> Data + Copy + Sync,
     PostProcessChildren: Fn(&mut Vec<DataTree<OsStringDisplay, Data>>) + Copy + Send + Sync,
+    PostProcessTree: Fn(DataTree<OsStringDisplay, Data>) -> DataTree<OsStringDisplay, Data>,
 {
     /// List of files and/or directories.
     pub files: Vec<PathBuf>,
@@ -34,16 +57,57 @@ where
     pub reporter: Report,
     /// Processes lists of children after forming.
     pub post_process_children: PostProcessChildren,
+    /// Processes the whole tree after forming, e.g. to regroup its items.
+    pub post_process_tree: PostProcessTree,
+    /// Order of the entries, which is applied to the whole tree once it is formed if the key
+    /// [requires it](SortKey::requires_whole_tree).
+    pub sort: Option<SortKey>,
     /// Minimal size proportion required to appear.
     pub minimal_ratio: Fraction,
+    /// Merge chains of single-child directories into one node.
+    pub collapse_chains: bool,
+    /// Group the files by their extensions, recognizing the listed multi-part extensions.
+    pub group_by_extension: Option<Vec<String>>,
+    /// Rescan and redraw after every interval, showing the changes since the previous scan.
+    pub watch: Option<Duration>,
+    /// Update the tree from filesystem events instead of rescanning it when watching (Linux only).
+    pub live: bool,
+    /// Reuse and update the scans of a cache file.
+    pub cache: Option<CacheSettings>,
+    /// Record the top levels of the tree into a history of snapshots.
+    pub history: Option<HistorySettings>,
+    /// Lines to print above the visualization, e.g. the capacities of the filesystems.
+    pub header: String,
+    /// Query the capacities of the filesystems anew for the header of every redraw.
+    pub capacity: Option<CapacitySettings>,
+    /// Quantity that the bars and the percentages are relative to instead of the total.
+    pub proportion_total: Option<u64>,
+    /// Name of the node that holds multiple roots.
+    pub root_label: String,
+    /// Nest multiple roots under the directories that follow their longest common path prefix.
+    pub shared_prefix: bool,
+    /// Measure only these paths instead of the files.
+    pub listed_files: Option<Vec<PathBuf>>,
+    /// Format of the output.
+    pub output_format: OutputFormat,
+    /// How to color the charts of the output.
+    pub color_scheme: ColorScheme,
+    /// Width of the bars of the Markdown outputs.
+    pub markdown_bar: Option<NonZeroUsize>,
+    /// The measured quantity.
+    pub quantity: Quantity,
+    /// Write the output into this file instead of the standard output.
+    pub output_file: Option<PathBuf>,
 }
 
-impl<Data, GetData, Report, PostProcessChildren> Sub<Data, GetData, Report, PostProcessChildren>
+impl<Data, GetData, Report, PostProcessChildren, PostProcessTree>
+    Sub<Data, GetData, Report, PostProcessChildren, PostProcessTree>
 where
-    Data: Size + Into<u64> + Send + Sync,
+    Data: Size<Inner = u64> + From<u64> + Into<u64> + Send + Sync,
     Report: ParallelReporter<Data> + Sync,
     GetData: Fn(&Metadata) -> Data + Copy + Sync,
     PostProcessChildren: Fn(&mut Vec<DataTree<OsStringDisplay, Data>>) + Copy + Send + Sync,
+    PostProcessTree: Fn(DataTree<OsStringDisplay, Data>) -> DataTree<OsStringDisplay, Data>,
 {
     /// Run the sub program.
     pub fn run(self) -> Result<(), RuntimeError> {
@@ -56,67 +120,316 @@ where
             get_data,
             reporter,
             post_process_children,
+            post_process_tree,
+            sort,
             minimal_ratio,
+            collapse_chains,
+            group_by_extension,
+            watch,
+            live,
+            cache,
+            history,
+            header,
+            capacity,
+            proportion_total,
+            root_label,
+            shared_prefix,
+            listed_files,
+            output_format,
+            color_scheme,
+            markdown_bar,
+            quantity,
+            output_file,
         } = self;
 
-        let mut iter = files
-            .into_iter()
-            .map(|root| -> DataTree<OsStringDisplay, Data> {
-                FsTreeBuilder {
-                    reporter: &reporter,
-                    root,
-                    get_data,
-                    post_process_children,
-                }
-                .into()
-            });
-
-        let data_tree = if let Some(data_tree) = iter.next() {
-            data_tree
-        } else {
+        if files.is_empty() && listed_files.is_none() {
             return Sub {
                 files: vec![".".into()],
                 reporter,
+                post_process_tree,
+                group_by_extension,
+                cache,
+                history,
+                header,
+                capacity,
+                root_label,
+                listed_files,
+                output_file,
                 ..self
             }
             .run();
+        }
+
+        let (files, overlapping_roots) = remove_overlapping_roots(&files);
+        for overlapping_root in overlapping_roots {
+            eprintln!("[warning] {}", overlapping_root);
+        }
+
+        let build = |root: &PathBuf| -> DataTree<OsStringDisplay, Data> {
+            FsTreeBuilder {
+                reporter: &reporter,
+                root: root.clone(),
+                get_data,
+                post_process_children,
+            }
+            .into()
         };
 
-        // ExactSizeIterator::is_empty is unstable
-        let data_tree = if iter.len() == 0 {
-            data_tree
+        let resolved_files: Vec<PathBuf> = if shared_prefix {
+            files
+                .iter()
+                .map(|root| canonical_root(root).unwrap_or_else(|| root.clone()))
+                .collect()
         } else {
-            let children: Vec<_> = once(data_tree).chain(iter).collect();
-            DataTree::dir(
-                OsStringDisplay::os_string_from("(total)"),
-                Data::default(),
-                children,
-            )
+            Vec::new()
         };
 
-        if reporter.destroy().is_err() {
-            eprintln!("[warning] Failed to destroy the thread that reports progress");
-        }
+        // The trees are in the same order as the files.
+        let combine = |mut trees: Vec<DataTree<OsStringDisplay, Data>>| {
+            let root_label = OsStringDisplay::os_string_from(&root_label);
+            let data_tree = if trees.len() == 1 {
+                trees.pop().expect("there is one tree")
+            } else if shared_prefix {
+                let roots = resolved_files.iter().cloned().zip(trees).collect();
+                nest_by_shared_prefix(roots, root_label, post_process_children)
+            } else {
+                DataTree::dir(root_label, Data::default(), trees)
+            };
 
-        let minimal_ratio: f32 = minimal_ratio.into();
-        let data_tree = {
-            let mut data_tree = data_tree;
-            if minimal_ratio > 0.0 {
-                data_tree.par_cull_insignificant_data(minimal_ratio);
+            let data_tree = post_process_tree(data_tree);
+
+            let mut data_tree = if let Some(multi_part_extensions) = &group_by_extension {
+                ExtensionBreakdown::from_tree(&data_tree, multi_part_extensions)
+                    .into_data_tree(data_tree.name().clone(), post_process_children)
+            } else {
+                data_tree
+            };
+
+            if let Some(sort) = sort {
+                sort.sort_tree(&mut data_tree);
             }
+
             data_tree
         };
 
-        let visualizer = Visualizer {
-            data_tree: &data_tree,
-            bytes_format,
-            direction,
-            column_width_distribution,
-            max_depth,
+        let scan = || match &listed_files {
+            Some(paths) => combine(vec![ListedTreeBuilder {
+                paths: paths.clone(),
+                get_data,
+                reporter: &reporter,
+                post_process_children,
+            }
+            .into()]),
+            None => combine(files.iter().map(&build).collect()),
+        };
+
+        let minimal_ratio: f32 = minimal_ratio.into();
+        macro_rules! visualize {
+            ($data_tree:expr, $progress:expr, $scan_duration:expr) => {{
+                let mut data_tree = $data_tree;
+                prune(&mut data_tree, minimal_ratio, collapse_chains);
+                let visualizer = Visualizer {
+                    data_tree: &data_tree,
+                    bytes_format,
+                    direction,
+                    column_width_distribution,
+                    max_depth,
+                    proportion_total,
+                };
+                let context = RenderContext {
+                    color_scheme,
+                    quantity: quantity.as_ref(),
+                    unit: quantity.unit(),
+                    progress: $progress,
+                    scan_duration: Some($scan_duration),
+                    markdown_bar,
+                };
+                output_format.render(visualizer, context)
+            }};
+        }
+
+        let interval = if let Some(interval) = watch {
+            interval
+        } else {
+            let start = Instant::now();
+            let (data_tree, scan_cache) = if let Some(settings) = &cache {
+                let mut scan_cache = ScanCache::load(&settings.file, settings.label.clone());
+                let results: Vec<_> = files
+                    .iter()
+                    .map(|root| {
+                        CachedFsTreeBuilder {
+                            root: root.clone(),
+                            get_data,
+                            reporter: &reporter,
+                            post_process_children,
+                            cache: &scan_cache,
+                            sample_ratio: settings.sample_ratio,
+                        }
+                        .build()
+                    })
+                    .collect();
+                let mut trees = Vec::with_capacity(results.len());
+                for (root, (tree, scanned)) in files.iter().zip(results) {
+                    scan_cache.merge(root, scanned);
+                    trees.push(tree);
+                }
+                (combine(trees), Some(scan_cache))
+            } else {
+                (scan(), None)
+            };
+            let scan_duration = start.elapsed();
+
+            let progress = reporter.progress();
+            if reporter.destroy().is_err() {
+                eprintln!("[warning] Failed to destroy the thread that reports progress");
+            }
+
+            if let (Some(settings), Some(scan_cache)) = (&cache, scan_cache) {
+                if let Err(error) = scan_cache.save(&settings.file) {
+                    eprintln!(
+                        "[warning] Failed to save the cache {:?}: {}",
+                        settings.file, error,
+                    );
+                }
+            }
+
+            if let Some(HistorySettings {
+                store,
+                label,
+                roots,
+                max_depth: depth,
+            }) = &history
+            {
+                let time = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_or(0, |duration| duration.as_secs());
+                if let Err(error) = store.record(time, label, roots, &data_tree, *depth) {
+                    eprintln!(
+                        "[warning] Failed to record a snapshot into {:?}: {}",
+                        store.directory, error,
+                    );
+                }
+            }
+
+            eprint!("\r"); // erase progress report.
+            let output = header + &visualize!(data_tree, progress, scan_duration);
+            return emit_output(&output, output_file.as_deref());
+        };
+
+        #[cfg(target_os = "linux")]
+        let mut live_tree = if live {
+            match LiveTree::new(files.clone(), get_data, &reporter, post_process_children) {
+                Ok(live_tree) => Some(live_tree),
+                Err(error) => {
+                    eprintln!("[error] {}", error);
+                    return Err(RuntimeError::FilesystemWatchFailure);
+                }
+            }
+        } else {
+            None
         };
 
-        eprint!("\r"); // erase progress report.
-        print!("{}", visualizer); // visualizer already ends with "\n", println! isn't needed here.
-        Ok(())
+        #[cfg(not(target_os = "linux"))]
+        if live {
+            return Err(RuntimeError::LiveUpdatesUnsupported);
+        }
+
+        // The cursor is hidden during the redraws, so it must be shown again when the loop is
+        // stopped by an interrupt (Ctrl-C).
+        if restore_terminal_on_interrupt() {
+            print!("{}", HIDE_CURSOR);
+        }
+        let mut previous: Option<DataTree<OsStringDisplay, Data>> = None;
+        let mut previous_height = 0;
+        loop {
+            let start = Instant::now();
+
+            #[cfg(target_os = "linux")]
+            let (data_tree, action) = if let Some(live_tree) = &mut live_tree {
+                let action = match live_tree.update() {
+                    Ok(UpdateSummary {
+                        events,
+                        rescanned,
+                        unwatched,
+                    }) => format!(
+                        "Applied {} events by rescanning {} items ({} directories are not watched)",
+                        events, rescanned, unwatched,
+                    ),
+                    Err(error) => format!("Failed to read filesystem events: {}", error),
+                };
+                (combine(live_tree.trees().to_vec()), action)
+            } else {
+                (scan(), "Scanned".to_string())
+            };
+
+            #[cfg(not(target_os = "linux"))]
+            let (data_tree, action) = (scan(), "Scanned");
+
+            let scan_duration = start.elapsed();
+
+            // Render the whole frame before writing it so that the redraw does not flicker.
+            let mut frame = match &capacity {
+                Some(settings) => settings.header().text,
+                None => header.clone(),
+            };
+            frame += &visualize!(
+                data_tree.par_compare(previous.as_ref()),
+                None,
+                scan_duration
+            );
+            frame += &format!(
+                "{} in {:.3}s, refreshing every {:.3}s (press Ctrl-C to stop)\n",
+                action,
+                scan_duration.as_secs_f64(),
+                interval.as_secs_f64(),
+            );
+            if previous_height > 0 {
+                // Move the cursor up to the first line of the previous frame and erase it.
+                print!("\x1B[{}A\x1B[J", previous_height);
+            }
+            print!("{}", frame);
+            stdout().flush().ok();
+            previous_height = frame.lines().count();
+            previous = Some(data_tree);
+
+            if let Some(remaining) = interval.checked_sub(start.elapsed()) {
+                sleep(remaining);
+            }
+        }
+    }
+}
+
+/// Print the output, or write it into the output file if there is one.
+pub(crate) fn emit_output(output: &str, output_file: Option<&Path>) -> Result<(), RuntimeError> {
+    match output_file {
+        None => {
+            print!("{}", output);
+            Ok(())
+        }
+        Some(output_file) => write_output_file(output_file, output).map_err(|error| {
+            eprintln!("[error] {:?}: {}", output_file, error);
+            RuntimeError::OutputFileFailure
+        }),
+    }
+}
+
+/// Cull the insignificant descendants of a tree and merge its chains of single-child directories
+/// if requested.
+pub(crate) fn prune<Data>(
+    data_tree: &mut DataTree<OsStringDisplay, Data>,
+    minimal_ratio: f32,
+    collapse_chains: bool,
+) where
+    Data: Size + Into<u64> + Send,
+{
+    if minimal_ratio > 0.0 {
+        data_tree.par_cull_insignificant_data(minimal_ratio);
+    }
+    if collapse_chains {
+        data_tree.par_collapse_chains(|parent, child| {
+            Path::new(parent.as_os_str())
+                .join(child.as_os_str())
+                .pipe(OsStringDisplay::os_string_from)
+        });
     }
 }
@@ -0,0 +1,119 @@
+pub mod tar;
+pub mod zip;
+
+pub use tar::TarParser;
+
+use crate::weighted_tree::WeightedEntry;
+use derive_more::Display;
+use flate2::read::MultiGzDecoder;
+use std::io::{Error, ErrorKind, Read, Seek, SeekFrom};
+use zstd::stream::read::Decoder as ZstdDecoder;
+
+/// Item recorded in an archive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveEntry {
+    /// Path of the item inside the archive, whose components are separated by `/`.
+    pub path: String,
+    /// Uncompressed size of the item, which is `0` for directories and links.
+    pub size: u64,
+    /// Compressed size of the item, if the format records it.
+    pub compressed_size: Option<u64>,
+}
+
+impl ArchiveEntry {
+    /// Convert the entry into an entry of a weighted hierarchy, weighted by either its
+    /// uncompressed or its compressed size.
+    pub fn into_weighted(self, compressed: bool) -> Result<WeightedEntry, ArchiveError> {
+        let weight = if compressed {
+            self.compressed_size
+                .ok_or(ArchiveError::CompressedSizeUnrecorded)?
+        } else {
+            self.size
+        };
+        let key = self
+            .path
+            .split('/')
+            .filter(|component| !component.is_empty() && *component != ".")
+            .map(String::from)
+            .collect();
+        Ok(WeightedEntry { key, weight })
+    }
+}
+
+/// Error that occurs when reading an archive.
+#[derive(Debug, Display)]
+pub enum ArchiveError {
+    /// Failed to read the archive.
+    #[display(fmt = "{}", _0)]
+    Io(Error),
+    /// The archive is not a valid tar archive.
+    #[display(fmt = "Invalid tar archive")]
+    InvalidTar,
+    /// The archive is not a valid stream of its compression format.
+    #[display(fmt = "Invalid {} stream", _0)]
+    InvalidCompressedStream(&'static str),
+    /// The archive is not a valid zip archive.
+    #[display(fmt = "Invalid zip archive")]
+    InvalidZip,
+    /// The archive is compressed by an unsupported method.
+    #[display(fmt = "{} compression is not supported", _0)]
+    UnsupportedCompression(&'static str),
+    /// The format of the archive does not record the compressed sizes of its entries.
+    #[display(fmt = "The archive does not record the compressed sizes of its entries")]
+    CompressedSizeUnrecorded,
+}
+
+impl From<Error> for ArchiveError {
+    fn from(error: Error) -> Self {
+        ArchiveError::Io(error)
+    }
+}
+
+/// Read the entries of a tar archive (optionally compressed by gzip or Zstandard) or a zip
+/// archive.
+pub fn read_archive(mut archive: impl Read + Seek) -> Result<Vec<ArchiveEntry>, ArchiveError> {
+    let mut magic = Vec::with_capacity(4);
+    archive.by_ref().take(4).read_to_end(&mut magic)?;
+    archive.seek(SeekFrom::Start(0))?;
+    match magic.as_slice() {
+        [0x1F, 0x8B, ..] => read_tar(MultiGzDecoder::new(archive), Some("gzip")),
+        [0x28, 0xB5, 0x2F, 0xFD] => read_tar(ZstdDecoder::new(archive)?, Some("Zstandard")),
+        [b'P', b'K', 3, 4] | [b'P', b'K', 5, 6] => zip::read_entries(archive),
+        [b'B', b'Z', b'h', _] => Err(ArchiveError::UnsupportedCompression("bzip2")),
+        [0xFD, b'7', b'z', b'X'] => Err(ArchiveError::UnsupportedCompression("xz")),
+        _ => read_tar(archive, None),
+    }
+}
+
+/// Read the entries of a tar archive from a stream, which is decompressed by the named
+/// `compression` if any.
+fn read_tar(
+    mut archive: impl Read,
+    compression: Option<&'static str>,
+) -> Result<Vec<ArchiveEntry>, ArchiveError> {
+    let mut parser = TarParser::default();
+    let mut buffer = vec![0; 64 * 1024];
+    loop {
+        match archive.read(&mut buffer) {
+            Ok(0) => break parser.finish(),
+            Ok(length) => parser.push(&buffer[..length])?,
+            Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+            Err(error) => {
+                // Decompressors report corrupted streams by these kinds of errors.
+                let is_corrupted = matches!(
+                    error.kind(),
+                    ErrorKind::InvalidInput
+                        | ErrorKind::InvalidData
+                        | ErrorKind::UnexpectedEof
+                        | ErrorKind::Other
+                );
+                break match compression {
+                    Some(compression) if is_corrupted => {
+                        Err(ArchiveError::InvalidCompressedStream(compression))
+                    }
+                    _ => Err(error.into()),
+                };
+            }
+        }
+    }
+}
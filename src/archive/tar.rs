@@ -0,0 +1,264 @@
+use super::{ArchiveEntry, ArchiveError};
+use std::{cmp::min, mem::take, str::from_utf8};
+
+/// Size of the blocks of a tar archive.
+const BLOCK_SIZE: usize = 512;
+
+/// Largest size of the data of an extension, which is kept in memory, unlike the contents of
+/// the entries.
+const MAX_EXTENSION_SIZE: u64 = 1024 * 1024;
+
+/// Data of an entry that describes the next entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Extension {
+    /// Long path of the next entry (GNU).
+    LongName,
+    /// Extended attributes of the next entry (POSIX).
+    Pax,
+}
+
+/// Parser of tar archives that receives the archive in chunks of any size.
+///
+/// Only the headers are kept, the contents of the entries are skipped.
+///
+/// Sparse files of GNU tar, either in the old format (type `S`) or in the PAX formats, are
+/// reported by their real sizes instead of the sizes of their stored data.
+#[derive(Debug, Default)]
+pub struct TarParser {
+    /// Entries that were parsed.
+    entries: Vec<ArchiveEntry>,
+    /// Received part of the current header block.
+    block: Vec<u8>,
+    /// Extension whose data is being received.
+    extension: Option<Extension>,
+    /// Number of bytes of the extension that remain to be received.
+    extension_remaining: u64,
+    /// Received data of the extension.
+    extension_data: Vec<u8>,
+    /// Number of bytes of contents and padding that remain to be skipped.
+    skip: u64,
+    /// Path of the next entry from an extension.
+    next_path: Option<String>,
+    /// Size of the next entry from an extension.
+    next_size: Option<u64>,
+    /// Real path of the next entry, if it is a sparse file, from an extension.
+    next_sparse_path: Option<String>,
+    /// Real size of the next entry, if it is a sparse file, from an extension.
+    next_real_size: Option<u64>,
+    /// Whether the next block continues the map of a sparse file in the old GNU format.
+    sparse_map_continues: bool,
+    /// Number of bytes of contents and padding to skip after the map of a sparse file.
+    sparse_skip: u64,
+    /// Whether the end-of-archive block was reached.
+    finished: bool,
+}
+
+impl TarParser {
+    /// Parse the next chunk of the archive.
+    pub fn push(&mut self, mut chunk: &[u8]) -> Result<(), ArchiveError> {
+        while !chunk.is_empty() && !self.finished {
+            if self.extension_remaining > 0 {
+                let length = min(self.extension_remaining, chunk.len() as u64) as usize;
+                self.extension_data.extend_from_slice(&chunk[..length]);
+                self.extension_remaining -= length as u64;
+                chunk = &chunk[length..];
+                if self.extension_remaining == 0 {
+                    self.apply_extension()?;
+                }
+            } else if self.skip > 0 {
+                let length = min(self.skip, chunk.len() as u64) as usize;
+                self.skip -= length as u64;
+                chunk = &chunk[length..];
+            } else {
+                let length = min(BLOCK_SIZE - self.block.len(), chunk.len());
+                self.block.extend_from_slice(&chunk[..length]);
+                chunk = &chunk[length..];
+                if self.block.len() == BLOCK_SIZE {
+                    self.parse_header()?;
+                    self.block.clear();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Return the parsed entries after the whole archive was received.
+    pub fn finish(self) -> Result<Vec<ArchiveEntry>, ArchiveError> {
+        let is_complete = self.block.is_empty()
+            && self.extension_remaining == 0
+            && self.skip == 0
+            && self.extension.is_none()
+            && !self.sparse_map_continues;
+        if self.finished || is_complete {
+            Ok(self.entries)
+        } else {
+            Err(ArchiveError::InvalidTar)
+        }
+    }
+
+    fn parse_header(&mut self) -> Result<(), ArchiveError> {
+        let block = &self.block;
+        if self.sparse_map_continues {
+            self.sparse_map_continues = block[504] != 0;
+            if !self.sparse_map_continues {
+                self.skip = take(&mut self.sparse_skip);
+            }
+            return Ok(());
+        }
+        if block.iter().all(|byte| *byte == 0) {
+            self.finished = true;
+            return Ok(());
+        }
+
+        let checksum = parse_number(&block[148..156]).ok_or(ArchiveError::InvalidTar)?;
+        let actual_checksum: u64 = block
+            .iter()
+            .enumerate()
+            .map(|(index, byte)| match index {
+                148..=155 => u64::from(b' '),
+                _ => u64::from(*byte),
+            })
+            .sum();
+        if checksum != actual_checksum {
+            return Err(ArchiveError::InvalidTar);
+        }
+
+        let size = parse_number(&block[124..136]).ok_or(ArchiveError::InvalidTar)?;
+        // GNU headers (`ustar  \0`) store other fields where POSIX headers store the prefix.
+        let is_ustar = &block[257..263] == b"ustar\0";
+        let name = text(&block[..100]);
+        let prefix = if is_ustar {
+            text(&block[345..500])
+        } else {
+            String::new()
+        };
+        let type_flag = block[156];
+
+        let padding =
+            |size: u64| (BLOCK_SIZE as u64 - size % BLOCK_SIZE as u64) % BLOCK_SIZE as u64;
+        let mut data_size = size;
+        let mut is_old_sparse_map_extended = false;
+        match type_flag {
+            b'L' => self.extension = Some(Extension::LongName),
+            b'x' => self.extension = Some(Extension::Pax),
+            b'g' | b'K' => {}
+            _ => {
+                let next_path = self.next_path.take();
+                let path = match self.next_sparse_path.take().or(next_path) {
+                    Some(path) => path,
+                    None if prefix.is_empty() => name,
+                    None => format!("{}/{}", prefix, name),
+                };
+                data_size = self.next_size.take().unwrap_or(size);
+                let real_size = self.next_real_size.take();
+                let size = match type_flag {
+                    b'0' | b'\0' | b'7' => real_size.unwrap_or(data_size),
+                    b'S' => {
+                        is_old_sparse_map_extended = block[482] != 0;
+                        parse_number(&block[483..495]).ok_or(ArchiveError::InvalidTar)?
+                    }
+                    _ => 0, // links, directories and special files.
+                };
+                self.entries.push(ArchiveEntry {
+                    path,
+                    size,
+                    compressed_size: None,
+                });
+            }
+        }
+
+        if self.extension.is_some() {
+            if data_size > MAX_EXTENSION_SIZE {
+                return Err(ArchiveError::InvalidTar);
+            }
+            self.extension_remaining = data_size;
+            self.skip = padding(data_size);
+            if data_size == 0 {
+                self.apply_extension()?;
+            }
+        } else if is_old_sparse_map_extended {
+            self.sparse_map_continues = true;
+            self.sparse_skip = data_size + padding(data_size);
+        } else {
+            self.skip = data_size + padding(data_size);
+        }
+        Ok(())
+    }
+
+    fn apply_extension(&mut self) -> Result<(), ArchiveError> {
+        let data = take(&mut self.extension_data);
+        match self.extension.take() {
+            Some(Extension::LongName) => {
+                self.next_path = Some(text(&data));
+            }
+            Some(Extension::Pax) => {
+                // Records of `<length> <key>=<value>\n` where `<length>` counts the whole record.
+                let mut records = data.as_slice();
+                while !records.is_empty() {
+                    let space = records
+                        .iter()
+                        .position(|byte| *byte == b' ')
+                        .ok_or(ArchiveError::InvalidTar)?;
+                    let length: usize = from_utf8(&records[..space])
+                        .ok()
+                        .and_then(|length| length.parse().ok())
+                        .filter(|length| *length > space && *length <= records.len())
+                        .ok_or(ArchiveError::InvalidTar)?;
+                    let record = &records[space + 1..length];
+                    let record = record.strip_suffix(b"\n").unwrap_or(record);
+                    let parse_size = |size: &[u8]| {
+                        from_utf8(size)
+                            .ok()
+                            .and_then(|size| size.parse().ok())
+                            .ok_or(ArchiveError::InvalidTar)
+                    };
+                    if let Some(path) = record.strip_prefix(b"path=") {
+                        self.next_path = Some(String::from_utf8_lossy(path).into_owned());
+                    } else if let Some(path) = record.strip_prefix(b"GNU.sparse.name=") {
+                        self.next_sparse_path = Some(String::from_utf8_lossy(path).into_owned());
+                    } else if let Some(size) = record.strip_prefix(b"size=") {
+                        self.next_size = Some(parse_size(size)?);
+                    } else if let Some(size) = record
+                        .strip_prefix(b"GNU.sparse.realsize=")
+                        .or_else(|| record.strip_prefix(b"GNU.sparse.size="))
+                    {
+                        self.next_real_size = Some(parse_size(size)?);
+                    }
+                    records = &records[length..];
+                }
+            }
+            None => {}
+        }
+        Ok(())
+    }
+}
+
+/// Parse a numeric field, which is either octal text or a big-endian number whose first bit
+/// is set.
+fn parse_number(field: &[u8]) -> Option<u64> {
+    match field.split_first() {
+        Some((first, rest)) if first & 0x80 != 0 => rest
+            .iter()
+            .try_fold(u64::from(first & 0x7F), |number, byte| {
+                number.checked_mul(256)?.checked_add(u64::from(*byte))
+            }),
+        _ => {
+            let field = from_utf8(field).ok()?;
+            let field = field.trim_matches(|char| char == '\0' || char == ' ');
+            if field.is_empty() {
+                Some(0)
+            } else {
+                u64::from_str_radix(field, 8).ok()
+            }
+        }
+    }
+}
+
+/// Text of a field that ends at its first NUL character.
+fn text(field: &[u8]) -> String {
+    let end = field
+        .iter()
+        .position(|byte| *byte == 0)
+        .unwrap_or(field.len());
+    String::from_utf8_lossy(&field[..end]).into_owned()
+}
@@ -0,0 +1,155 @@
+use super::{ArchiveEntry, ArchiveError};
+use std::{
+    cmp::min,
+    convert::TryInto,
+    io::{Read, Seek, SeekFrom},
+};
+
+/// Signature of the end of central directory record.
+const END_SIGNATURE: &[u8] = b"PK\x05\x06";
+
+/// Signature of the ZIP64 end of central directory locator.
+const ZIP64_LOCATOR_SIGNATURE: &[u8] = b"PK\x06\x07";
+
+/// Signature of the ZIP64 end of central directory record.
+const ZIP64_END_SIGNATURE: &[u8] = b"PK\x06\x06";
+
+/// Signature of a central directory header.
+const HEADER_SIGNATURE: &[u8] = b"PK\x01\x02";
+
+/// Size of the fixed part of the end of central directory record.
+const END_SIZE: usize = 22;
+
+/// Value of a 32-bit field whose actual value is in the ZIP64 extra field.
+const ZIP64_MARKER: u64 = 0xFFFF_FFFF;
+
+/// Read the entries of a zip archive from its central directory, which records both the
+/// uncompressed and the compressed sizes.
+pub fn read_entries(mut archive: impl Read + Seek) -> Result<Vec<ArchiveEntry>, ArchiveError> {
+    let archive_size = archive.seek(SeekFrom::End(0))?;
+    let tail_size = min(archive_size, (END_SIZE + u16::MAX as usize) as u64);
+    let tail_start = archive_size - tail_size;
+    let tail = read_at(&mut archive, archive_size, tail_start, tail_size as usize)?;
+
+    // The record is followed by a comment of up to 65535 bytes.
+    let end_position = (0..=tail.len().saturating_sub(END_SIZE))
+        .rev()
+        .find(|position| tail[*position..].starts_with(END_SIGNATURE))
+        .ok_or(ArchiveError::InvalidZip)?;
+    let end = &tail[end_position..];
+    let mut directory_size = u64::from(u32_at(end, 12)?);
+    let mut directory_offset = u64::from(u32_at(end, 16)?);
+
+    if directory_size == ZIP64_MARKER || directory_offset == ZIP64_MARKER {
+        let locator = end_position
+            .checked_sub(20)
+            .map(|position| &tail[position..end_position])
+            .filter(|locator| locator.starts_with(ZIP64_LOCATOR_SIGNATURE))
+            .ok_or(ArchiveError::InvalidZip)?;
+        let zip64_end = read_at(&mut archive, archive_size, u64_at(locator, 8)?, 56)?;
+        if !zip64_end.starts_with(ZIP64_END_SIGNATURE) {
+            return Err(ArchiveError::InvalidZip);
+        }
+        directory_size = u64_at(&zip64_end, 40)?;
+        directory_offset = u64_at(&zip64_end, 48)?;
+    }
+
+    let directory_size = directory_size
+        .try_into()
+        .map_err(|_| ArchiveError::InvalidZip)?;
+    let directory = read_at(&mut archive, archive_size, directory_offset, directory_size)?;
+    let mut entries = Vec::new();
+    let mut rest = directory.as_slice();
+    while rest.starts_with(HEADER_SIGNATURE) {
+        let mut compressed_size = u64::from(u32_at(rest, 20)?);
+        let mut size = u64::from(u32_at(rest, 24)?);
+        let name_length = usize::from(u16_at(rest, 28)?);
+        let extra_length = usize::from(u16_at(rest, 30)?);
+        let comment_length = usize::from(u16_at(rest, 32)?);
+        let name = rest
+            .get(46..46 + name_length)
+            .ok_or(ArchiveError::InvalidZip)?;
+        let extra = rest
+            .get(46 + name_length..46 + name_length + extra_length)
+            .ok_or(ArchiveError::InvalidZip)?;
+
+        // The ZIP64 extra field only holds the sizes whose fields are saturated, in this order.
+        if size == ZIP64_MARKER || compressed_size == ZIP64_MARKER {
+            let mut fields = zip64_extra_field(extra)?;
+            for value in [&mut size, &mut compressed_size].iter_mut() {
+                if **value == ZIP64_MARKER {
+                    **value = u64_at(fields, 0)?;
+                    fields = &fields[8..];
+                }
+            }
+        }
+
+        entries.push(ArchiveEntry {
+            path: String::from_utf8_lossy(name).into_owned(),
+            size,
+            compressed_size: Some(compressed_size),
+        });
+        rest = rest
+            .get(46 + name_length + extra_length + comment_length..)
+            .ok_or(ArchiveError::InvalidZip)?;
+    }
+    Ok(entries)
+}
+
+/// Find the data of the ZIP64 extra field amongst the extra fields of a header.
+fn zip64_extra_field(mut extra: &[u8]) -> Result<&[u8], ArchiveError> {
+    while !extra.is_empty() {
+        let id = u16_at(extra, 0)?;
+        let size = usize::from(u16_at(extra, 2)?);
+        let data = extra.get(4..4 + size).ok_or(ArchiveError::InvalidZip)?;
+        if id == 0x0001 {
+            return Ok(data);
+        }
+        extra = &extra[4 + size..];
+    }
+    Err(ArchiveError::InvalidZip)
+}
+
+/// Read `size` bytes at `position`, which must lie within the archive of `archive_size` bytes
+/// so that a corrupted record cannot cause a huge allocation.
+fn read_at(
+    archive: &mut (impl Read + Seek),
+    archive_size: u64,
+    position: u64,
+    size: usize,
+) -> Result<Vec<u8>, ArchiveError> {
+    let is_within_archive = position
+        .checked_add(size as u64)
+        .map_or(false, |end| end <= archive_size);
+    if !is_within_archive {
+        return Err(ArchiveError::InvalidZip);
+    }
+    archive.seek(SeekFrom::Start(position))?;
+    let mut buffer = vec![0; size];
+    archive.read_exact(&mut buffer)?;
+    Ok(buffer)
+}
+
+fn u16_at(bytes: &[u8], offset: usize) -> Result<u16, ArchiveError> {
+    bytes
+        .get(offset..offset + 2)
+        .and_then(|bytes| bytes.try_into().ok())
+        .map(u16::from_le_bytes)
+        .ok_or(ArchiveError::InvalidZip)
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> Result<u32, ArchiveError> {
+    bytes
+        .get(offset..offset + 4)
+        .and_then(|bytes| bytes.try_into().ok())
+        .map(u32::from_le_bytes)
+        .ok_or(ArchiveError::InvalidZip)
+}
+
+fn u64_at(bytes: &[u8], offset: usize) -> Result<u64, ArchiveError> {
+    bytes
+        .get(offset..offset + 8)
+        .and_then(|bytes| bytes.try_into().ok())
+        .map(u64::from_le_bytes)
+        .ok_or(ArchiveError::InvalidZip)
+}
@@ -1,13 +1,20 @@
 pub mod fraction;
+pub mod interval;
 pub mod quantity;
 
 pub use fraction::Fraction;
+pub use interval::Interval;
 pub use quantity::Quantity;
 
 use crate::{
-    bytes_format::BytesFormat, runtime_error::RuntimeError, visualizer::ColumnWidthDistribution,
+    bytes_format::BytesFormat, output_format::OutputFormat, ownership::OwnerKind,
+    runtime_error::RuntimeError, sort_key::SortKey, svg_chart::ColorScheme,
+    visualizer::ColumnWidthDistribution,
+};
+use std::{
+    num::{NonZeroU64, NonZeroUsize},
+    path::PathBuf,
 };
-use std::{num::NonZeroUsize, path::PathBuf};
 use structopt::StructOpt;
 use strum::VariantNames;
 use terminal_size::{terminal_size, Width};
@@ -30,10 +37,126 @@ pub struct Args {
     #[structopt(name = "files")]
     pub files: Vec<PathBuf>,
 
+    /// Measure only the paths listed in a file (`-` for the standard input) instead of the
+    /// files, separated by NUL characters if there is any, or else by newlines.
+    #[structopt(
+        long,
+        value_name = "file",
+        conflicts_with_all = &["files", "cache", "live"]
+    )]
+    pub files_from: Option<PathBuf>,
+
+    /// Instead of measuring files, visualize a hierarchy read from a file (`-` for the standard
+    /// input) whose lines are keys followed by weights, e.g. `a;b;c 42` (the weights are shown
+    /// according to `--bytes-format`).
+    #[structopt(
+        long,
+        value_name = "file",
+        conflicts_with_all = &[
+            "files", "files-from", "watch", "cache", "history", "group-by-owner",
+            "group-by-extension", "show-age", "show-access-age", "show-capacity",
+            "relative-to-capacity",
+        ]
+    )]
+    pub weighted_input: Option<PathBuf>,
+
+    /// Instead of measuring files, show the entries of a tar archive (optionally compressed by
+    /// gzip or Zstandard) or a zip archive by their uncompressed sizes.
+    #[structopt(
+        long,
+        value_name = "file",
+        conflicts_with_all = &[
+            "files", "files-from", "weighted-input", "watch", "cache", "history",
+            "group-by-owner", "group-by-extension", "show-age", "show-access-age",
+            "show-capacity", "relative-to-capacity",
+        ]
+    )]
+    pub archive: Option<PathBuf>,
+
+    /// Instead of measuring files, show a dump of ncdu (`ncdu -o`) read from a file (`-` for
+    /// the standard input), by the apparent sizes (`--quantity=len`) or the disk usage in
+    /// blocks of 512 bytes (`--quantity=blocks`) of its items.
+    #[structopt(
+        long,
+        value_name = "file",
+        conflicts_with_all = &[
+            "files", "files-from", "weighted-input", "archive", "watch", "cache", "history",
+            "group-by-owner", "group-by-extension", "show-age", "show-access-age",
+            "show-capacity", "relative-to-capacity",
+        ]
+    )]
+    pub ncdu_import: Option<PathBuf>,
+
+    /// Instead of measuring files, show the output of `du` (e.g. `du -ab` or `du -k`) read from
+    /// a file (`-` for the standard input), whose lines are sizes followed by tabs and paths.
+    #[structopt(
+        long,
+        value_name = "file",
+        conflicts_with_all = &[
+            "files", "files-from", "weighted-input", "archive", "ncdu-import", "watch", "cache",
+            "history", "group-by-owner", "group-by-extension", "show-age", "show-access-age",
+            "show-capacity", "relative-to-capacity",
+        ]
+    )]
+    pub du_import: Option<PathBuf>,
+
+    /// Number of bytes in each unit of the sizes of `--du-import`, e.g. `1` for `du -b` and
+    /// `1024` for `du -k` (the default of GNU `du`).
+    #[structopt(long, value_name = "bytes", default_value = "1024")]
+    pub du_block_size: NonZeroU64,
+
+    /// Instead of visualizing, scan a single directory and write it to a file (`-` for the
+    /// standard output) in the dump format of ncdu, which can be browsed with `ncdu -f`.
+    ///
+    /// The dump records both the apparent sizes and the disk usage, the progress is reported
+    /// in `--quantity`, which is either `len` or `blocks`.
+    #[structopt(
+        long,
+        value_name = "file",
+        conflicts_with_all = &[
+            "files-from", "weighted-input", "archive", "ncdu-import", "du-import", "watch",
+            "cache", "history",
+        ]
+    )]
+    pub ncdu_export: Option<PathBuf>,
+
+    /// Measure the entries of `--archive` by their compressed sizes, which only zip archives
+    /// record.
+    #[structopt(long, requires = "archive")]
+    pub compressed_size: bool,
+
+    /// Separator of the components of the keys of `--weighted-input`.
+    #[structopt(long, value_name = "separator", default_value = "/")]
+    pub key_separator: String,
+
     /// How to display the numbers of bytes.
     #[structopt(long, possible_values = BytesFormat::VARIANTS, default_value = BytesFormat::default_value())]
     pub bytes_format: BytesFormat,
 
+    /// Format of the output: the visualization, a table of the visualized items whose columns
+    /// are the depth, the path, the raw and the formatted size, the percentages of the root and
+    /// of the parent, and the number of children, an HTML page that works offline, an SVG
+    /// image of a treemap or an icicle chart, folded stacks for flamegraph tools, metrics of
+    /// Prometheus that include the duration and the numbers of items and errors of the scan
+    /// (which imported hierarchies lack, so their metrics omit them), or a Markdown table or
+    /// nested list.
+    #[structopt(long, possible_values = OutputFormat::VARIANTS, default_value = OutputFormat::default_value())]
+    pub output_format: OutputFormat,
+
+    /// How to color the rectangles of the SVG charts of `--output-format`.
+    #[structopt(long, possible_values = ColorScheme::VARIANTS, default_value = ColorScheme::default_value())]
+    pub svg_colors: ColorScheme,
+
+    /// Add a bar of Unicode blocks of this width to every item of the Markdown outputs of
+    /// `--output-format`.
+    #[structopt(long, value_name = "width")]
+    pub markdown_bar: Option<NonZeroUsize>,
+
+    /// Write the output into a file that is replaced atomically instead of the standard output,
+    /// e.g. a `.prom` file of the textfile collector of node_exporter.
+    #[structopt(long, value_name = "file", conflicts_with = "watch")]
+    pub output_file: Option<PathBuf>,
+
     /// Print the tree top-down instead of bottom-up.
     #[structopt(long)]
     pub top_down: bool,
@@ -58,7 +181,54 @@ pub struct Args {
     #[structopt(long, default_value = "0.01")]
     pub minimal_ratio: Fraction,
 
-    /// Preserve order of entries.
+    /// Merge chains of single-child directories into one node.
+    #[structopt(long)]
+    pub collapse_chains: bool,
+
+    /// Name of the node that holds multiple files/directories.
+    #[structopt(long, value_name = "label", default_value = "(total)")]
+    pub root_label: String,
+
+    /// Nest multiple files/directories under the directories of their paths that follow the
+    /// longest common prefix, instead of placing them directly under the root node.
+    #[structopt(long)]
+    pub shared_prefix: bool,
+
+    /// Order of entries amongst their siblings.
+    #[structopt(long, possible_values = SortKey::VARIANTS, default_value = SortKey::default_value())]
+    pub sort: SortKey,
+
+    /// Show disk usage of files grouped by their extensions instead of the tree, along with the
+    /// numbers of files, the own disk usage of the directories is shown as a group of its own.
+    #[structopt(long)]
+    pub group_by_extension: bool,
+
+    /// Extensions of multiple parts to be recognized by `--group-by-extension`.
+    #[structopt(
+        long,
+        use_delimiter = true,
+        value_name = "extensions",
+        default_value = "tar.gz,tar.bz2,tar.xz,tar.zst"
+    )]
+    pub multi_part_extensions: Vec<String>,
+
+    /// Show disk usage of each owner of the files instead of the tree (POSIX only).
+    #[structopt(long, possible_values = OwnerKind::VARIANTS)]
+    pub group_by_owner: Option<OwnerKind>,
+
+    /// Show the ages of the newest and the oldest modifications amongst each item and its descendants.
+    #[structopt(long)]
+    pub show_age: bool,
+
+    /// Show the age of the latest access amongst each item and its descendants.
+    #[structopt(long)]
+    pub show_access_age: bool,
+
+    /// Only count files that have not been modified for the given number of days.
+    #[structopt(long, value_name = "days")]
+    pub untouched_for: Option<u64>,
+
+    /// Preserve order of entries (overrides `--sort`).
     #[structopt(long)]
     pub no_sort: bool,
 
@@ -69,14 +239,74 @@ pub struct Args {
     /// Report progress being made at the expense of performance.
     #[structopt(long)]
     pub progress: bool,
+
+    /// Rescan and redraw the visualization in place after every interval (e.g. `2`, `500ms`, `1m`),
+    /// showing the change of each item since the previous scan, until interrupted.
+    #[structopt(long, value_name = "interval", conflicts_with_all = &["show-age", "show-access-age"])]
+    pub watch: Option<Interval>,
+
+    /// Update the tree from filesystem events instead of rescanning it on every `--watch`
+    /// interval (Linux only).
+    #[structopt(long, requires = "watch")]
+    pub live: bool,
+
+    /// Reuse the scans of the directories whose entries are unchanged since the previous run
+    /// with the same cache file, then update the file.
+    #[structopt(long, value_name = "file", conflicts_with_all = &["watch", "untouched-for"])]
+    pub cache: Option<PathBuf>,
+
+    /// Ratio of the files of each unchanged directory to measure anew when using `--cache`,
+    /// since modifying a file does not change its directory.
+    #[structopt(long, value_name = "ratio", default_value = "0.01")]
+    pub cache_sample_ratio: Fraction,
+
+    /// Show the total, used and available capacity of the filesystem of each root in a header
+    /// (Linux only).
+    #[structopt(long)]
+    pub show_capacity: bool,
+
+    /// Make the bars and the percentages relative to the total capacity of the filesystems of
+    /// the roots instead of the total of the roots (Linux only).
+    #[structopt(long)]
+    pub relative_to_capacity: bool,
+
+    /// Append a snapshot of the top levels of the tree to a history directory.
+    ///
+    /// Snapshots are told apart by the measured quantity and the canonical paths of the roots.
+    #[structopt(
+        long,
+        value_name = "directory",
+        conflicts_with_all = &["watch", "group-by-owner", "group-by-extension"]
+    )]
+    pub history: Option<PathBuf>,
+
+    /// Number of levels of the tree to record in each snapshot of `--history`.
+    #[structopt(long, value_name = "levels", default_value = "3")]
+    pub history_depth: NonZeroUsize,
+
+    /// Instead of scanning, show the growth of every recorded item over the given number of
+    /// latest snapshots of `--history`, and when the filesystem is projected to be full.
+    #[structopt(long, value_name = "snapshots", requires = "history")]
+    pub history_trend: Option<usize>,
+
+    /// Produce the same output on every run and every machine: ties are sorted by name,
+    /// the width does not depend on the terminal, and progress is not reported.
+    #[structopt(long)]
+    pub reproducible: bool,
 }
 
+/// Total width of the visualization when `--reproducible` is set and the width is not specified.
+pub const REPRODUCIBLE_TOTAL_WIDTH: usize = 100;
+
 impl Args {
-    /// Deduce [`ColumnWidthDistribution`] from `--total-width` or `--column-width`.
+    /// Deduce [`ColumnWidthDistribution`] from `--total-width`, `--column-width` or `--reproducible`.
     pub(crate) fn column_width_distribution(
         &self,
     ) -> Result<ColumnWidthDistribution, RuntimeError> {
         Ok(match (self.total_width, self.column_width.as_deref()) {
+            (None, None) if self.reproducible => {
+                ColumnWidthDistribution::total(REPRODUCIBLE_TOTAL_WIDTH)
+            }
             (None, None) => {
                 let (Width(width), _) =
                     terminal_size().ok_or(RuntimeError::TerminalWidthInferenceFailure)?;
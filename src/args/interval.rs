@@ -0,0 +1,35 @@
+use derive_more::{AsRef, Deref, Display, Into};
+use std::{num::ParseFloatError, str::FromStr, time::Duration};
+
+/// Positive duration between two events, written as a number of seconds with an
+/// optional unit suffix: `ms`, `s`, `m` or `h`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, AsRef, Deref, Into)]
+pub struct Interval(Duration);
+
+/// Error that occurs when parsing an [`Interval`].
+#[derive(Debug, Clone, PartialEq, Eq, Display)]
+pub enum FromStrError {
+    ParseFloatError(ParseFloatError),
+    /// Provided value is not positive or is too large.
+    #[display(fmt = "out of range")]
+    OutOfRange,
+}
+
+impl FromStr for Interval {
+    type Err = FromStrError;
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let (number, scale) = [("ms", 0.001), ("s", 1.0), ("m", 60.0), ("h", 3600.0)]
+            .iter()
+            .find_map(|(suffix, scale)| text.strip_suffix(suffix).map(|number| (number, *scale)))
+            .unwrap_or((text, 1.0));
+        let seconds = number
+            .parse::<f64>()
+            .map_err(FromStrError::ParseFloatError)?
+            * scale;
+        let in_range = seconds > 0.0 && seconds < u64::MAX as f64; // also rejects NaN
+        if !in_range {
+            return Err(FromStrError::OutOfRange);
+        }
+        Ok(Interval(Duration::from_secs_f64(seconds)))
+    }
+}
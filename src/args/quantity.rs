@@ -17,10 +17,27 @@ pub enum Quantity {
     #[cfg(unix)]
     #[strum(serialize = "blocks")]
     BlockCount,
+    /// Measure apparent sizes multiplied by the numbers of days since the last
+    /// [modification](std::fs::Metadata::modified), to rank data that has long been unused
+    /// (displayed in byte-days, [`Bd`](crate::bytes_format::byte_days::BYTE_DAY_UNIT)).
+    #[strum(serialize = "cold-bytes")]
+    ColdBytes,
 }
 
 impl Quantity {
     pub(super) fn default_value() -> &'static str {
         Quantity::ApparentSize.as_ref()
     }
+
+    /// Unit of the measured quantity, in the plural form of the names of Prometheus metrics.
+    pub fn unit(self) -> &'static str {
+        match self {
+            Quantity::ApparentSize => "bytes",
+            #[cfg(unix)]
+            Quantity::BlockSize => "bytes",
+            #[cfg(unix)]
+            Quantity::BlockCount => "blocks",
+            Quantity::ColdBytes => "byte_days",
+        }
+    }
 }
@@ -1,8 +1,10 @@
+pub mod byte_days;
 pub mod formatter;
 pub mod output;
 pub mod parsed_value;
 pub mod scale_base;
 
+pub use byte_days::ByteDaysOutput;
 pub use formatter::Formatter;
 pub use output::Output;
 pub use parsed_value::ParsedValue;
@@ -39,4 +41,9 @@ impl BytesFormat {
             BinaryUnits => BINARY.parse_value(bytes).pipe(Output::Units),
         }
     }
+
+    /// Format a quantity of byte-days according to the settings.
+    pub fn format_byte_days(self, byte_days: u64) -> ByteDaysOutput {
+        self.format(byte_days).pipe(ByteDaysOutput)
+    }
 }
@@ -0,0 +1,31 @@
+use super::Output;
+use std::fmt::{Display, Error, Formatter};
+
+/// Unit symbol of a byte-day, which is a byte that has stayed unmodified for a day.
+pub const BYTE_DAY_UNIT: &str = "Bd";
+
+/// The [`DisplayOutput`](crate::size::Size::DisplayOutput) type of
+/// [`ByteDays`](crate::size::ByteDays).
+///
+/// Values with units end with [`BYTE_DAY_UNIT`] instead of the unit of bytes so that they are
+/// not mistaken for bytes, e.g. `750Bd` and `3KBd`. Plain numbers are displayed as-is.
+#[derive(Debug, Clone, Copy)]
+pub struct ByteDaysOutput(pub Output);
+
+impl Display for ByteDaysOutput {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> Result<(), Error> {
+        match self.0 {
+            Output::PlainNumber(value) => write!(formatter, "{}", value),
+            Output::Units(value) if value.exponent() == 0 => {
+                write!(formatter, "{}{}", value.coefficient(), BYTE_DAY_UNIT)
+            }
+            Output::Units(value) => write!(
+                formatter,
+                "{}{}{}",
+                value.coefficient(),
+                value.unit(),
+                BYTE_DAY_UNIT,
+            ),
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use crate::bytes_format::BytesFormat;
+use std::{
+    collections::HashSet,
+    io::Error,
+    path::{Path, PathBuf},
+};
+
+#[cfg(unix)]
+use std::{fs::metadata, os::unix::prelude::MetadataExt};
+
+#[cfg(target_os = "linux")]
+use libc::statvfs;
+#[cfg(target_os = "linux")]
+use std::{ffi::CString, mem::MaybeUninit, os::unix::prelude::OsStrExt};
+
+#[cfg(not(target_os = "linux"))]
+use std::io::ErrorKind;
+
+/// Capacity of the filesystem that holds a path.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Capacity {
+    /// Total number of bytes of the filesystem.
+    pub total: u64,
+    /// Number of bytes in use.
+    pub used: u64,
+    /// Number of bytes available to unprivileged users.
+    pub available: u64,
+}
+
+impl Capacity {
+    /// Query the capacity of the filesystem that holds `path` (Linux only).
+    #[cfg(target_os = "linux")]
+    pub fn of(path: &Path) -> Result<Self, Error> {
+        let path = CString::new(path.as_os_str().as_bytes())?;
+        let mut stats = MaybeUninit::<statvfs>::uninit();
+        // SAFETY: `path` is a valid NUL-terminated C string that outlives the call, and `stats`
+        // points to memory that can hold a `statvfs`, which the call only writes.
+        if unsafe { statvfs(path.as_ptr(), stats.as_mut_ptr()) } != 0 {
+            return Err(Error::last_os_error());
+        }
+        // SAFETY: `statvfs` returned 0, so it has initialized `stats`.
+        let stats = unsafe { stats.assume_init() };
+        // The widths of these fields vary by platform.
+        let block_size = stats.f_frsize as u64;
+        let blocks = stats.f_blocks as u64;
+        let free_blocks = stats.f_bfree as u64;
+        let available_blocks = stats.f_bavail as u64;
+        Ok(Capacity {
+            total: blocks.saturating_mul(block_size),
+            used: blocks
+                .saturating_sub(free_blocks)
+                .saturating_mul(block_size),
+            available: available_blocks.saturating_mul(block_size),
+        })
+    }
+
+    /// Query the capacity of the filesystem that holds `path` (Linux only).
+    #[cfg(not(target_os = "linux"))]
+    pub fn of(_: &Path) -> Result<Self, Error> {
+        Err(Error::new(
+            ErrorKind::Other,
+            "filesystem capacity is only available on Linux",
+        ))
+    }
+
+    /// Query the capacities of the distinct filesystems that hold the roots, each along with
+    /// the first of the roots that it holds (Linux only).
+    pub fn of_roots(roots: &[PathBuf]) -> Vec<(PathBuf, Result<Self, Error>)> {
+        let mut devices = HashSet::new();
+        roots
+            .iter()
+            .filter(|root| device(root).map_or(true, |device| devices.insert(device)))
+            .map(|root| (root.clone(), Capacity::of(root)))
+            .collect()
+    }
+
+    /// Ratio of the used bytes to the total bytes in percent.
+    pub fn used_percentage(self) -> u64 {
+        if self.total == 0 {
+            return 0;
+        }
+        (u128::from(self.used) * 100 / u128::from(self.total)) as u64
+    }
+}
+
+/// Which capacities to show above the visualization and how.
+#[derive(Debug, Clone)]
+pub struct CapacitySettings {
+    /// Roots whose filesystems are queried.
+    pub roots: Vec<PathBuf>,
+    /// Format of the numbers of bytes.
+    pub bytes_format: BytesFormat,
+}
+
+/// Capacities of the filesystems that hold some roots, described in the lines of a header.
+#[derive(Debug)]
+pub struct CapacityHeader {
+    /// One line per filesystem.
+    pub text: String,
+    /// Sum of the total bytes of the filesystems.
+    pub total: u64,
+    /// Roots whose filesystems could not be queried.
+    pub errors: Vec<(PathBuf, Error)>,
+}
+
+impl CapacitySettings {
+    /// Query the current capacities of the filesystems that hold the roots.
+    pub fn header(&self) -> CapacityHeader {
+        let format = self.bytes_format;
+        let mut header = CapacityHeader {
+            text: String::new(),
+            total: 0,
+            errors: Vec::new(),
+        };
+        for (root, capacity) in Capacity::of_roots(&self.roots) {
+            let capacity = match capacity {
+                Ok(capacity) => capacity,
+                Err(error) => {
+                    header.errors.push((root, error));
+                    continue;
+                }
+            };
+            header.total += capacity.total;
+            header.text += &format!(
+                "{}: {} total, {} used ({}%), {} available\n",
+                root.display(),
+                format.format(capacity.total),
+                format.format(capacity.used),
+                capacity.used_percentage(),
+                format.format(capacity.available),
+            );
+        }
+        header
+    }
+}
+
+/// Identifier of the device that holds a path (POSIX only).
+#[cfg(unix)]
+fn device(path: &Path) -> Option<u64> {
+    metadata(path).ok().map(|stats| stats.dev())
+}
+
+/// Identifier of the device that holds a path (POSIX only).
+#[cfg(not(unix))]
+fn device(_: &Path) -> Option<u64> {
+    None
+}
@@ -7,14 +7,19 @@ pub use Reflection as DataTreeReflection;
 use super::size::Size;
 
 /// Disk usage data of a filesystem tree.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DataTree<Name, Data: Size> {
     name: Name,
     data: Data,
     children: Vec<Self>,
+    is_dir: bool,
 }
 
+mod collapse;
+mod compare;
 mod constructors;
 mod getters;
 mod retain;
+mod serialize;
 mod sort;
+mod update;
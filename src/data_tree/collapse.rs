@@ -0,0 +1,46 @@
+use super::DataTree;
+use crate::size::Size;
+use rayon::prelude::*;
+
+impl<Name, Data> DataTree<Name, Data>
+where
+    Self: Send,
+    Data: Size,
+{
+    /// Merge the chain of single-child directories that starts at this node into this node.
+    ///
+    /// A directory is only merged with its only child if said child has children of its own,
+    /// so a directory that holds a single file remains a directory.
+    fn collapse_chain(&mut self, join_names: impl Fn(&Name, &Name) -> Name) {
+        while let [only_child] = self.children.as_slice() {
+            if only_child.children.is_empty() {
+                break;
+            }
+            let only_child = self.children.pop().expect("pop the only child");
+            self.name = join_names(&self.name, &only_child.name);
+            self.children = only_child.children;
+        }
+    }
+
+    /// Recursively merge all chains of single-child directories amongst the descendants,
+    /// in parallel.
+    ///
+    /// The name of each merged node is created by calling `join_names` upon the names of
+    /// the directories in the chain, the data of each merged node is the data of the top of
+    /// the chain.
+    pub fn par_collapse_chains(&mut self, join_names: impl Fn(&Name, &Name) -> Name + Copy + Sync) {
+        self.children.par_iter_mut().for_each(|child| {
+            child.collapse_chain(join_names);
+            child.par_collapse_chains(join_names);
+        });
+    }
+
+    /// Process the tree via [`par_collapse_chains`](Self::par_collapse_chains) method.
+    pub fn into_par_collapsed_chains(
+        mut self,
+        join_names: impl Fn(&Name, &Name) -> Name + Copy + Sync,
+    ) -> Self {
+        self.par_collapse_chains(join_names);
+        self
+    }
+}
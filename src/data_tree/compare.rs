@@ -0,0 +1,48 @@
+use super::DataTree;
+use crate::size::{Changed, Size};
+use rayon::prelude::*;
+use std::collections::BTreeMap;
+
+impl<Name, Data> DataTree<Name, Data>
+where
+    Name: Clone + Ord + Send + Sync,
+    Data: Size + Send + Sync,
+{
+    /// Pair the data of every node with the data of the node of the same path in a
+    /// previous tree, in parallel.
+    ///
+    /// If there is no `previous` tree, no node has previous data. Otherwise, the nodes that
+    /// do not exist in the `previous` tree are considered to be previously empty.
+    pub fn par_compare(&self, previous: Option<&Self>) -> DataTree<Name, Changed<Data>>
+    where
+        Changed<Data>: Size + Send,
+    {
+        let previous_children: BTreeMap<&Name, &Self> = previous
+            .map(|previous| &previous.children)
+            .into_iter()
+            .flatten()
+            .map(|child| (&child.name, child))
+            .collect();
+
+        let children = self
+            .children
+            .par_iter()
+            .map(|child| {
+                let previous_child = previous.map(|_| previous_children.get(&child.name));
+                match previous_child {
+                    None => child.par_compare(None),
+                    Some(None) => child
+                        .par_compare(Some(&DataTree::file(child.name.clone(), Data::default()))),
+                    Some(Some(previous_child)) => child.par_compare(Some(previous_child)),
+                }
+            })
+            .collect();
+
+        DataTree {
+            name: self.name.clone(),
+            data: Changed::new(self.data, previous.map(|previous| previous.data)),
+            children,
+            is_dir: self.is_dir,
+        }
+    }
+}
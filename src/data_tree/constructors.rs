@@ -9,6 +9,7 @@ impl<Name, Data: Size> DataTree<Name, Data> {
             name,
             data,
             children,
+            is_dir: true,
         }
     }
 
@@ -18,6 +19,7 @@ impl<Name, Data: Size> DataTree<Name, Data> {
             name,
             data,
             children: Vec::with_capacity(0),
+            is_dir: false,
         }
     }
 
@@ -16,4 +16,18 @@ impl<Name, Data: Size> DataTree<Name, Data> {
     pub fn children(&self) -> &Vec<Self> {
         &self.children
     }
+
+    /// Check whether the tree represents a directory rather than a file
+    pub fn is_dir(&self) -> bool {
+        self.is_dir
+    }
+
+    /// Count the tree itself and all its descendants
+    pub fn item_count(&self) -> usize {
+        1 + self
+            .children
+            .iter()
+            .map(DataTree::item_count)
+            .sum::<usize>()
+    }
 }
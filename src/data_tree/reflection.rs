@@ -21,6 +21,7 @@ impl<Name, Data: Size> From<DataTree<Name, Data>> for Reflection<Name, Data> {
             name,
             data,
             children,
+            ..
         } = source;
         let children: Vec<_> = children.into_iter().map(Reflection::from).collect();
         Reflection {
@@ -0,0 +1,110 @@
+use super::DataTree;
+use crate::{
+    os_string_display::{
+        escape::{escape, unescape},
+        OsStringDisplay,
+    },
+    size::Size,
+};
+use pipe_trait::Pipe;
+
+impl<Data> DataTree<OsStringDisplay, Data>
+where
+    Data: Size + From<u64> + Into<u64>,
+{
+    /// Serialize the top `max_depth` levels of the tree into lines of `<depth> <data> <name>`,
+    /// where `<depth>` of the root is `0`, `<data>` is the total of each node, and the names
+    /// of directories end with `/`.
+    pub fn to_text(&self, max_depth: usize) -> String {
+        fn write<Data>(
+            tree: &DataTree<OsStringDisplay, Data>,
+            depth: usize,
+            max_depth: usize,
+            text: &mut String,
+        ) where
+            Data: Size + From<u64> + Into<u64>,
+        {
+            if depth >= max_depth {
+                return;
+            }
+            let data: u64 = tree.data.into();
+            let name = escape(tree.name.as_os_str());
+            let suffix = if tree.is_dir { "/" } else { "" };
+            *text += &format!("{} {} {}{}\n", depth, data, name, suffix);
+            for child in &tree.children {
+                write(child, depth + 1, max_depth, text);
+            }
+        }
+
+        let mut text = String::new();
+        write(self, 0, max_depth, &mut text);
+        text
+    }
+
+    /// Parse the text created by [`to_text`](Self::to_text).
+    ///
+    /// Since the deeper levels are omitted, the own data of every directory is recovered as
+    /// the difference between its total and the totals of its children.
+    pub fn from_text(text: &str) -> Option<Self> {
+        /// Name, total and kind of a node whose children are still being read.
+        type Ancestor<Data> = (
+            OsStringDisplay,
+            u64,
+            bool,
+            Vec<DataTree<OsStringDisplay, Data>>,
+        );
+
+        let mut ancestors: Vec<Ancestor<Data>> = Vec::new();
+
+        fn finish<Data>(
+            (name, total, is_dir, children): Ancestor<Data>,
+        ) -> DataTree<OsStringDisplay, Data>
+        where
+            Data: Size + From<u64> + Into<u64>,
+        {
+            if !is_dir {
+                return DataTree::file(name, total.into());
+            }
+            let children_total: u64 = children.iter().map(|child| child.data.into()).sum();
+            let own_data: Data = total.saturating_sub(children_total).into();
+            DataTree::dir(name, own_data, children)
+        }
+
+        let mut root = None;
+        for line in text.lines() {
+            let mut words = line.split(' ');
+            let depth: usize = words.next()?.parse().ok()?;
+            let data: u64 = words.next()?.parse().ok()?;
+            let name = words.next()?;
+            let (name, is_dir) = match name.strip_suffix('/') {
+                Some(name) => (name, true),
+                None => (name, false),
+            };
+            let name = unescape(name)?.pipe(OsStringDisplay::from);
+            if words.next().is_some() || root.is_some() || depth > ancestors.len() {
+                return None;
+            }
+            while ancestors.len() > depth {
+                let node = finish(ancestors.pop()?);
+                match ancestors.last_mut() {
+                    Some((_, _, _, children)) => children.push(node),
+                    None => root = Some(node),
+                }
+            }
+            if root.is_some() {
+                return None; // a second root.
+            }
+            if let Some((_, _, false, _)) = ancestors.last() {
+                return None; // a child of a file.
+            }
+            ancestors.push((name, data, is_dir, Vec::new()));
+        }
+        while let Some(node) = ancestors.pop().map(finish) {
+            match ancestors.last_mut() {
+                Some((_, _, _, children)) => children.push(node),
+                None => root = Some(node),
+            }
+        }
+        root
+    }
+}
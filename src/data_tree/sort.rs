@@ -16,6 +16,31 @@ where
         self.children.sort_by(compare);
     }
 
+    /// Sort all descendants recursively by their numbers of items, most first, then by
+    /// `compare`, in parallel.
+    ///
+    /// The numbers of items are counted bottom-up once. Return the number of items of the tree.
+    pub fn par_sort_by_item_count(
+        &mut self,
+        compare: impl Fn(&Self, &Self) -> Ordering + Copy + Sync,
+    ) -> usize {
+        let counts: Vec<usize> = self
+            .children
+            .par_iter_mut()
+            .map(|child| child.par_sort_by_item_count(compare))
+            .collect();
+        let mut counted: Vec<_> = counts.into_iter().zip(self.children.drain(..)).collect();
+        counted.sort_by(|(left_count, left), (right_count, right)| {
+            right_count
+                .cmp(left_count)
+                .then_with(|| compare(left, right))
+        });
+        let count = 1 + counted.iter().map(|(count, _)| count).sum::<usize>();
+        self.children
+            .extend(counted.into_iter().map(|(_, child)| child));
+        count
+    }
+
     /// Process the tree via [`par_sort_by`](Self::par_sort_by) method.
     pub fn into_par_sorted(
         mut self,
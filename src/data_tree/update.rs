@@ -0,0 +1,78 @@
+use super::DataTree;
+use crate::size::Size;
+
+impl<Name, Data> DataTree<Name, Data>
+where
+    Name: PartialEq,
+    Data: Size,
+{
+    /// Replace the descendant at `path`, or remove it if `replacement` is `None`, then
+    /// recompute the data of the nodes along `path`.
+    ///
+    /// The `path` consists of the names of the descendants from a child of this node down to
+    /// the replaced descendant. The replaced descendant is added if it does not exist yet.
+    ///
+    /// `get_own_data` is called with every proper prefix of `path` (starting with the empty
+    /// prefix of this node) and should return the data of the corresponding node itself,
+    /// excluding its children. `post_process_children` is applied to every changed list of
+    /// children.
+    ///
+    /// Returns `false` without changing anything if `path` is empty or if a node along `path`,
+    /// other than the replaced descendant, does not exist.
+    pub fn update_descendant(
+        &mut self,
+        path: &[Name],
+        replacement: Option<Self>,
+        mut get_own_data: impl FnMut(&[Name]) -> Data,
+        post_process_children: impl Fn(&mut Vec<Self>),
+    ) -> bool {
+        !path.is_empty()
+            && self.update_descendant_at(
+                path,
+                0,
+                replacement,
+                &mut get_own_data,
+                &post_process_children,
+            )
+    }
+
+    /// Recursive part of [`update_descendant`](Self::update_descendant), in which this node
+    /// is at `path[..depth]`.
+    fn update_descendant_at(
+        &mut self,
+        path: &[Name],
+        depth: usize,
+        replacement: Option<Self>,
+        get_own_data: &mut impl FnMut(&[Name]) -> Data,
+        post_process_children: &impl Fn(&mut Vec<Self>),
+    ) -> bool {
+        let name = &path[depth];
+        let index = self.children.iter().position(|child| &child.name == name);
+        if depth + 1 == path.len() {
+            match (index, replacement) {
+                (Some(index), Some(replacement)) => self.children[index] = replacement,
+                (Some(index), None) => {
+                    self.children.remove(index);
+                }
+                (None, Some(replacement)) => self.children.push(replacement),
+                (None, None) => return true,
+            }
+        } else {
+            let updated = index.map_or(false, |index| {
+                self.children[index].update_descendant_at(
+                    path,
+                    depth + 1,
+                    replacement,
+                    get_own_data,
+                    post_process_children,
+                )
+            });
+            if !updated {
+                return false;
+            }
+        }
+        post_process_children(&mut self.children);
+        self.data = get_own_data(&path[..depth]) + self.children.iter().map(DataTree::data).sum();
+        true
+    }
+}
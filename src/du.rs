@@ -0,0 +1,121 @@
+use super::weighted_tree::WeightedEntry;
+use derive_more::Display;
+use std::collections::HashMap;
+
+/// Line of the output of `du`, e.g. `4096\t./src` whose path is `./src`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuEntry {
+    /// Path of the item as printed by `du`.
+    pub path: String,
+    /// Size of the item including its descendants, in bytes.
+    pub size: u64,
+}
+
+/// Error that occurs when parsing the output of `du`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum ParseError {
+    /// A line is not a size followed by a tab and a path.
+    #[display(fmt = "Invalid line {}", _0)]
+    InvalidLine(usize),
+}
+
+/// Parse lines of sizes in units of `block_size` bytes followed by tabs and paths, as printed
+/// by `du` (e.g. `du -ab` with `block_size` of `1` or `du -k` with `block_size` of `1024`).
+///
+/// Blank lines are skipped.
+pub fn parse_du_output(text: &str, block_size: u64) -> Result<Vec<DuEntry>, ParseError> {
+    let mut entries = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let error = ParseError::InvalidLine(index + 1);
+        let tab = line.find('\t').ok_or(error)?;
+        let (size, path) = line.split_at(tab);
+        let size = size
+            .trim()
+            .parse::<u64>()
+            .ok()
+            .and_then(|size| size.checked_mul(block_size))
+            .ok_or(error)?;
+        let path = &path[1..];
+        if path.is_empty() {
+            return Err(error);
+        }
+        entries.push(DuEntry {
+            path: path.to_string(),
+            size,
+        });
+    }
+    Ok(entries)
+}
+
+/// Hierarchy rebuilt from the output of `du`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuHierarchy {
+    /// Path of the only item without listed ancestors, whose key the keys of the entries are
+    /// relative to, or `None` if there are several such items.
+    pub root: Option<String>,
+    /// The items weighted by their own sizes.
+    pub entries: Vec<WeightedEntry>,
+}
+
+impl DuHierarchy {
+    /// Rebuild the hierarchy from the paths of the entries.
+    ///
+    /// Since `du` prints the total size of every directory, the own size of an item is its
+    /// size minus the sizes of its nearest listed descendants, so that nothing is counted twice.
+    /// The last size of a path that is printed more than once is used.
+    pub fn new(entries: Vec<DuEntry>) -> Self {
+        let mut paths = Vec::new();
+        let mut sizes = HashMap::new();
+        for DuEntry { path, size } in entries {
+            let key = key(&path);
+            if sizes.insert(key.clone(), size).is_none() {
+                paths.push((key, path));
+            }
+        }
+
+        let mut descendant_sizes: HashMap<&[String], u64> = HashMap::new();
+        let mut tops = Vec::new();
+        for (key, path) in &paths {
+            let parent = (0..key.len())
+                .rev()
+                .map(|length| &key[..length])
+                .find(|prefix| sizes.contains_key(*prefix));
+            match parent {
+                Some(parent) => *descendant_sizes.entry(parent).or_default() += sizes[key],
+                None => tops.push((key, path)),
+            }
+        }
+
+        let (root, root_length) = match tops.as_slice() {
+            [(key, path)] => (Some(path.to_string()), key.len()),
+            _ => (None, 0),
+        };
+
+        let entries = paths
+            .iter()
+            .map(|(key, _)| {
+                let descendant_size = descendant_sizes
+                    .get(key.as_slice())
+                    .copied()
+                    .unwrap_or_default();
+                WeightedEntry {
+                    key: key[root_length..].to_vec(),
+                    weight: sizes[key].saturating_sub(descendant_size),
+                }
+            })
+            .collect();
+
+        DuHierarchy { root, entries }
+    }
+}
+
+/// Components of a path, ignoring empty components and `.`.
+fn key(path: &str) -> Vec<String> {
+    path.split('/')
+        .filter(|component| !component.is_empty() && *component != ".")
+        .map(String::from)
+        .collect()
+}
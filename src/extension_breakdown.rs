@@ -0,0 +1,138 @@
+use crate::{data_tree::DataTree, os_string_display::OsStringDisplay, size::Size};
+use std::{collections::BTreeMap, ffi::OsStr};
+
+/// Total data and number of the items that share a bucket.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Bucket<Data: Size> {
+    /// Total data of the items.
+    pub data: Data,
+    /// Number of the items.
+    pub count: usize,
+}
+
+/// Disk usage of the files of a [`DataTree`] grouped by their extensions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtensionBreakdown<Data: Size> {
+    /// Buckets keyed by lowercase extensions, `None` holds the files without an extension.
+    pub buckets: BTreeMap<Option<String>, Bucket<Data>>,
+    /// Own data of the directories, which excludes the data of their descendants.
+    pub directories: Bucket<Data>,
+}
+
+impl<Data> ExtensionBreakdown<Data>
+where
+    Data: Size + From<u64> + Into<u64>,
+{
+    /// Fold all files of a [`DataTree`] into buckets keyed by their extensions, and the own
+    /// data of all directories into a bucket of their own.
+    ///
+    /// Extensions in `multi_part_extensions` (such as `tar.gz`) take precedence over
+    /// the last part of the names.
+    pub fn from_tree<Name>(tree: &DataTree<Name, Data>, multi_part_extensions: &[String]) -> Self
+    where
+        Name: AsRef<OsStr>,
+    {
+        fn fold<Name, Data>(
+            tree: &DataTree<Name, Data>,
+            multi_part_extensions: &[String],
+            breakdown: &mut ExtensionBreakdown<Data>,
+        ) where
+            Name: AsRef<OsStr>,
+            Data: Size + From<u64> + Into<u64>,
+        {
+            if !tree.is_dir() {
+                let extension = extension_of(tree.name().as_ref(), multi_part_extensions);
+                let bucket = breakdown.buckets.entry(extension).or_default();
+                bucket.data += tree.data();
+                bucket.count += 1;
+                return;
+            }
+            let total: u64 = tree.data().into();
+            let children_total: u64 = tree
+                .children()
+                .iter()
+                .map(|child| child.data().into())
+                .sum();
+            let own_data: Data = total.saturating_sub(children_total).into();
+            breakdown.directories.data += own_data;
+            breakdown.directories.count += 1;
+            for child in tree.children() {
+                fold(child, multi_part_extensions, breakdown);
+            }
+        }
+
+        let mut breakdown = ExtensionBreakdown {
+            buckets: BTreeMap::new(),
+            directories: Bucket::default(),
+        };
+        fold(tree, multi_part_extensions, &mut breakdown);
+        breakdown
+    }
+
+    /// Create a [`DataTree`] whose root is `root_name` and whose children are the buckets.
+    ///
+    /// Each child is named after its extension and the number of its files (such as
+    /// `*.rs (12 files)`), the directories are represented by a child named after their number
+    /// (such as `(3 directories)`) if there is any.
+    pub fn into_data_tree(
+        self,
+        root_name: OsStringDisplay,
+        post_process_children: impl FnOnce(&mut Vec<DataTree<OsStringDisplay, Data>>),
+    ) -> DataTree<OsStringDisplay, Data> {
+        let directories = Some(self.directories)
+            .filter(|directories| directories.count > 0)
+            .map(|Bucket { data, count }| {
+                let name = format!("({})", counted(count, "directory", "directories"));
+                (name, data)
+            });
+        let mut children: Vec<_> = self
+            .buckets
+            .into_iter()
+            .map(|(extension, Bucket { data, count })| {
+                let extension = extension.map_or_else(
+                    || "(no extension)".to_string(),
+                    |extension| format!("*.{}", extension),
+                );
+                let name = format!("{} ({})", extension, counted(count, "file", "files"));
+                (name, data)
+            })
+            .chain(directories)
+            .map(|(name, data)| DataTree::file(OsStringDisplay::os_string_from(name), data))
+            .collect();
+        post_process_children(&mut children);
+        DataTree::dir(root_name, Data::default(), children)
+    }
+}
+
+/// Describe a number of items in the singular or the plural form.
+fn counted(count: usize, singular: &str, plural: &str) -> String {
+    let noun = if count == 1 { singular } else { plural };
+    format!("{} {}", count, noun)
+}
+
+/// Get the lowercase extension of a file name.
+///
+/// Extensions in `multi_part_extensions` are recognized as a whole, the longest match wins.
+/// Names without a stem (such as `.bashrc`) have no extension.
+pub fn extension_of(name: &OsStr, multi_part_extensions: &[String]) -> Option<String> {
+    let name = name.to_string_lossy().to_lowercase();
+
+    let multi_part_extension = multi_part_extensions
+        .iter()
+        .map(|extension| extension.trim_start_matches('.').to_lowercase())
+        .filter(|extension| !extension.is_empty())
+        .filter(|extension| {
+            name.len() > extension.len() + 1
+                && name.ends_with(extension.as_str())
+                && name[..name.len() - extension.len()].ends_with('.')
+        })
+        .max_by_key(String::len);
+    if multi_part_extension.is_some() {
+        return multi_part_extension;
+    }
+
+    match name.rfind('.') {
+        Some(index) if index > 0 && index + 1 < name.len() => Some(name[index + 1..].to_string()),
+        _ => None,
+    }
+}
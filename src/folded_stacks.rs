@@ -0,0 +1,80 @@
+use crate::{data_tree::DataTree, size::Size};
+use std::{
+    fmt::{Display, Error, Formatter},
+    num::NonZeroUsize,
+};
+
+/// Lines of `frame;frame;frame <weight>` of a [`DataTree`], which flamegraph tools such as
+/// `inferno`, `flamegraph.pl` and speedscope can read as a flame chart.
+///
+/// Every leaf has a line of its path from the root and its data. Every other node whose data
+/// is more than the sum of its children has a line of the remainder. Nodes whose weights are
+/// zero have no lines.
+///
+/// Since the format has no escaping, semicolons and line breaks in names are written as
+/// `\x3B`, `\n` and `\r` like the non-UTF-8 bytes of an
+/// [`OsStringDisplay`](crate::os_string_display::OsStringDisplay).
+#[derive(Debug)]
+pub struct FoldedStacks<'a, Name, Data>
+where
+    Name: Display,
+    Data: Size,
+{
+    /// The tree to fold.
+    pub data_tree: &'a DataTree<Name, Data>,
+    /// Maximum number of levels of a stack, deeper nodes are counted toward their ancestors.
+    pub max_depth: NonZeroUsize,
+}
+
+impl<'a, Name, Data> Display for FoldedStacks<'a, Name, Data>
+where
+    Name: Display,
+    Data: Size + Into<u64>,
+{
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> Result<(), Error> {
+        fn write_node<Name, Data>(
+            formatter: &mut Formatter<'_>,
+            node: &DataTree<Name, Data>,
+            stack: &str,
+            remaining_depth: usize,
+        ) -> Result<(), Error>
+        where
+            Name: Display,
+            Data: Size + Into<u64>,
+        {
+            let frame = escape(&node.name().to_string());
+            let stack = if stack.is_empty() {
+                frame
+            } else {
+                format!("{};{}", stack, frame)
+            };
+            let size: u64 = node.data().into();
+            let children = if remaining_depth > 1 {
+                node.children().as_slice()
+            } else {
+                &[]
+            };
+            let children_size: u64 = children
+                .iter()
+                .map(|child| -> u64 { child.data().into() })
+                .sum();
+            let own_size = size.saturating_sub(children_size);
+            if own_size > 0 {
+                writeln!(formatter, "{} {}", stack, own_size)?;
+            }
+            for child in children {
+                write_node(formatter, child, &stack, remaining_depth - 1)?;
+            }
+            Ok(())
+        }
+
+        write_node(formatter, self.data_tree, "", self.max_depth.get())
+    }
+}
+
+/// Escape the characters that would break a frame or a line.
+fn escape(name: &str) -> String {
+    name.replace(';', "\\x3B")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
@@ -63,15 +63,13 @@ where
                             path,
                             error,
                         }));
-                        return Info {
-                            data: Data::default(),
-                            children: Vec::new(),
-                        };
+                        return Info::default();
                     }
                     Ok(stats) => stats,
                 };
 
-                let children: Vec<_> = if stats.file_type().is_dir() {
+                let is_dir = stats.file_type().is_dir();
+                let children: Vec<_> = if is_dir {
                     match read_dir(path) {
                         Err(error) => {
                             reporter.report(Event::EncounterError(ErrorReport {
@@ -79,7 +77,11 @@ where
                                 path,
                                 error,
                             }));
-                            return Info::default();
+                            return Info {
+                                data: Data::default(),
+                                children: Vec::new(),
+                                is_dir,
+                            };
                         }
                         Ok(entries) => entries,
                     }
@@ -103,7 +105,11 @@ where
                 let data = get_data(&stats);
                 reporter.report(Event::ReceiveData(data));
 
-                Info { data, children }
+                Info {
+                    data,
+                    children,
+                    is_dir,
+                }
             },
 
             join_path: |prefix, name| prefix.join(&name.0),
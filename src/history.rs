@@ -0,0 +1,192 @@
+pub mod trend;
+
+pub use trend::Trend;
+
+use crate::{
+    data_tree::DataTree,
+    os_string_display::{
+        escape::{escape, unescape},
+        OsStringDisplay,
+    },
+    size::Size,
+};
+use std::{
+    fs::{create_dir_all, hard_link, read_dir, read_to_string, remove_file, write},
+    io::{Error, ErrorKind},
+    path::PathBuf,
+    process,
+};
+
+/// First word of a snapshot file.
+const MAGIC: &str = "dirt-snapshot-v1";
+
+/// Extension of the names of snapshot files.
+const EXTENSION: &str = "snapshot";
+
+/// Top levels of a tree recorded at some point in time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot<Data: Size> {
+    /// Time of the recording in seconds since the Unix epoch.
+    pub time: u64,
+    /// Identifies what was measured.
+    pub label: String,
+    /// Canonical paths of the measured roots.
+    pub roots: Vec<PathBuf>,
+    /// The recorded levels of the tree.
+    pub tree: DataTree<OsStringDisplay, Data>,
+}
+
+impl<Data> Snapshot<Data>
+where
+    Data: Size + From<u64> + Into<u64>,
+{
+    /// Serialize the top `max_depth` levels of a tree into a snapshot.
+    pub fn text(
+        time: u64,
+        label: &str,
+        roots: &[PathBuf],
+        tree: &DataTree<OsStringDisplay, Data>,
+        max_depth: usize,
+    ) -> String {
+        let mut header = format!("{} {} {}", MAGIC, label, time);
+        for root in roots {
+            header += " ";
+            header += &escape(root.as_os_str());
+        }
+        format!("{}\n{}", header, tree.to_text(max_depth))
+    }
+
+    /// Parse the text created by [`text`](Self::text).
+    pub fn from_text(text: &str) -> Option<Self> {
+        let (header, body) = text.split_at(text.find('\n')?);
+        let mut words = header.split(' ');
+        if words.next()? != MAGIC {
+            return None;
+        }
+        let label = words.next()?.to_string();
+        let time = words.next()?.parse().ok()?;
+        let roots = words
+            .map(|word| unescape(word).map(PathBuf::from))
+            .collect::<Option<_>>()?;
+        let tree = DataTree::from_text(&body[1..])?;
+        Some(Snapshot {
+            time,
+            label,
+            roots,
+            tree,
+        })
+    }
+}
+
+/// Directory of snapshots appended by successive runs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryStore {
+    /// The directory, which is created when the first snapshot is recorded.
+    pub directory: PathBuf,
+}
+
+impl HistoryStore {
+    /// Record the top `max_depth` levels of a tree as a new snapshot.
+    ///
+    /// Snapshots recorded within the same second are given distinct names by a numeric suffix.
+    pub fn record<Data>(
+        &self,
+        time: u64,
+        label: &str,
+        roots: &[PathBuf],
+        tree: &DataTree<OsStringDisplay, Data>,
+        max_depth: usize,
+    ) -> Result<(), Error>
+    where
+        Data: Size + From<u64> + Into<u64>,
+    {
+        create_dir_all(&self.directory)?;
+        let temporary_path =
+            self.directory
+                .join(format!(".{}.{}.{}.tmp", time, label, process::id(),));
+        write(
+            &temporary_path,
+            Snapshot::text(time, label, roots, tree, max_depth),
+        )?;
+        // Linking never replaces an existing file, unlike renaming.
+        let mut suffix = 0;
+        let result = loop {
+            let name = format!("{}-{}.{}.{}", time, suffix, label, EXTENSION);
+            match hard_link(&temporary_path, self.directory.join(name)) {
+                Err(error) if error.kind() == ErrorKind::AlreadyExists => suffix += 1,
+                result => break result,
+            }
+        };
+        remove_file(&temporary_path)?;
+        result
+    }
+
+    /// Load the latest `count` snapshots of a label and a set of roots, from the oldest to
+    /// the newest.
+    ///
+    /// Files that are not valid snapshots are skipped with a warning.
+    pub fn load<Data>(
+        &self,
+        label: &str,
+        roots: &[PathBuf],
+        count: usize,
+    ) -> Result<Vec<Snapshot<Data>>, Error>
+    where
+        Data: Size + From<u64> + Into<u64>,
+    {
+        let mut snapshots = Vec::new();
+        for entry in read_dir(&self.directory)? {
+            let path = entry?.path();
+            if path
+                .extension()
+                .map_or(true, |extension| extension != EXTENSION)
+            {
+                continue;
+            }
+            match Snapshot::<Data>::from_text(&read_to_string(&path)?) {
+                None => eprintln!("[warning] Ignored an invalid snapshot {:?}", path),
+                Some(snapshot) if snapshot.label == label && snapshot.roots == roots => {
+                    snapshots.push(snapshot)
+                }
+                Some(_) => {}
+            }
+        }
+        snapshots.sort_by_key(|snapshot| snapshot.time);
+        let skipped = snapshots.len().saturating_sub(count);
+        snapshots.drain(..skipped);
+        Ok(snapshots)
+    }
+}
+
+/// Where and how to record the snapshot of a run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistorySettings {
+    /// The directory of snapshots.
+    pub store: HistoryStore,
+    /// Identifies what is measured.
+    pub label: String,
+    /// Canonical paths of the measured roots.
+    pub roots: Vec<PathBuf>,
+    /// Number of levels of the tree to record.
+    pub max_depth: usize,
+}
+
+/// Format a time in seconds since the Unix epoch as a date in UTC, e.g. `"2021-03-23"`.
+pub fn format_date(time: u64) -> String {
+    // https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let days = time / (24 * 60 * 60) + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153; // the year starts in March.
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = era * 400 + year_of_era + if month <= 2 { 1 } else { 0 };
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
@@ -0,0 +1,189 @@
+use super::{format_date, Snapshot};
+use crate::{
+    capacity::Capacity, data_tree::DataTree, os_string_display::OsStringDisplay, size::Size,
+};
+use std::fmt::{Display, Error, Formatter};
+
+/// Width of a column of numbers.
+const COLUMN_WIDTH: usize = 10;
+
+/// Number of seconds in a day.
+const DAY: f64 = (24 * 60 * 60) as f64;
+
+/// Growth of every recorded item between the oldest and the newest of a series of snapshots.
+///
+/// The growth per day is fitted by least squares to every snapshot of the series, so that
+/// a single outlying snapshot cannot decide it, nor the projection of when the filesystem
+/// is full.
+#[derive(Debug)]
+pub struct Trend<'a, Data: Size> {
+    /// The snapshots from the oldest to the newest.
+    pub snapshots: &'a [Snapshot<Data>],
+    /// Capacity of the filesystem, to project when it is full.
+    pub capacity: Option<Capacity>,
+    /// The current time in seconds since the Unix epoch.
+    pub now: u64,
+    /// Format to be used to [`display`](Size::display) the data.
+    pub bytes_format: Data::DisplayFormat,
+}
+
+impl<'a, Data> Trend<'a, Data>
+where
+    Data: Size + From<u64> + Into<u64>,
+{
+    /// Display a quantity, or a change of it if `sign` is given.
+    fn display(&self, value: u64, sign: Option<char>) -> String {
+        let data: Data = value.into();
+        let text = match sign {
+            None => data.display(self.bytes_format).to_string(),
+            Some(sign) => format!("{}{}", sign, data.display(self.bytes_format)),
+        };
+        format!("{:>width$}", text, width = COLUMN_WIDTH)
+    }
+
+    /// Display a change.
+    fn display_change(&self, change: f64) -> String {
+        let sign = if change < 0.0 { '-' } else { '+' };
+        self.display(change.abs().round() as u64, Some(sign))
+    }
+
+    /// Fit the growth per day of the item at the path of `names` below the roots by least
+    /// squares, the item counts as empty in the snapshots that lack it.
+    ///
+    /// Returns `None` if the snapshots do not span any time.
+    fn growth_per_day(&self, names: &[OsStringDisplay]) -> Option<f64> {
+        let start = self.snapshots.first()?.time;
+        let points: Vec<(f64, f64)> = self
+            .snapshots
+            .iter()
+            .map(|snapshot| {
+                let day = snapshot.time.saturating_sub(start) as f64 / DAY;
+                let value: u64 = find(&snapshot.tree, names).map_or(0, |node| node.data().into());
+                (day, value as f64)
+            })
+            .collect();
+        let count = points.len() as f64;
+        let mean_day = points.iter().map(|(day, _)| day).sum::<f64>() / count;
+        let mean_value = points.iter().map(|(_, value)| value).sum::<f64>() / count;
+        let (covariance, variance) =
+            points
+                .iter()
+                .fold((0.0, 0.0), |(covariance, variance), (day, value)| {
+                    let deviation = day - mean_day;
+                    (
+                        covariance + deviation * (value - mean_value),
+                        variance + deviation * deviation,
+                    )
+                });
+        if variance > 0.0 {
+            Some(covariance / variance)
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, Data> Display for Trend<'a, Data>
+where
+    Data: Size + From<u64> + Into<u64>,
+{
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> Result<(), Error> {
+        let (oldest, newest) = match (self.snapshots.first(), self.snapshots.last()) {
+            (Some(oldest), Some(newest)) => (oldest, newest),
+            _ => return writeln!(formatter, "There is no snapshot"),
+        };
+        let days = newest.time.saturating_sub(oldest.time) as f64 / DAY;
+        writeln!(
+            formatter,
+            "{} snapshots from {} to {} ({:.1} days)",
+            self.snapshots.len(),
+            format_date(oldest.time),
+            format_date(newest.time),
+            days,
+        )?;
+        writeln!(
+            formatter,
+            "{:>width$} {:>width$} {:>width$}  PATH",
+            "SIZE",
+            "CHANGE",
+            "PER DAY",
+            width = COLUMN_WIDTH,
+        )?;
+
+        let mut rows = Vec::new();
+        collect_rows(&newest.tree, String::new(), Vec::new(), &mut rows);
+        for (path, names, current) in rows {
+            let previous: u64 = find(&oldest.tree, &names).map_or(0, |node| node.data().into());
+            let change = current as f64 - previous as f64;
+            let per_day = match self.growth_per_day(&names) {
+                Some(growth) => self.display_change(growth),
+                None => format!("{:>width$}", "-", width = COLUMN_WIDTH),
+            };
+            writeln!(
+                formatter,
+                "{} {} {}  {}",
+                self.display(current, None),
+                self.display_change(change),
+                per_day,
+                path,
+            )?;
+        }
+
+        let capacity = match self.capacity {
+            None => return Ok(()),
+            Some(capacity) => capacity,
+        };
+        writeln!(
+            formatter,
+            "Filesystem: {} total, {} used, {} available",
+            self.display(capacity.total, None).trim_start(),
+            self.display(capacity.used, None).trim_start(),
+            self.display(capacity.available, None).trim_start(),
+        )?;
+        let growth = self.growth_per_day(&[]).unwrap_or_default();
+        if growth > 0.0 {
+            let remaining_days = capacity.available as f64 / growth;
+            let full_time = self.now.saturating_add((remaining_days * DAY) as u64);
+            writeln!(
+                formatter,
+                "At {} per day, the filesystem is full by {}",
+                self.display_change(growth).trim_start(),
+                format_date(full_time),
+            )
+        } else {
+            writeln!(formatter, "The usage is not growing")
+        }
+    }
+}
+
+/// Collect the path, the names below the root and the quantity of every item of a tree.
+fn collect_rows<Data>(
+    tree: &DataTree<OsStringDisplay, Data>,
+    prefix: String,
+    names: Vec<OsStringDisplay>,
+    rows: &mut Vec<(String, Vec<OsStringDisplay>, u64)>,
+) where
+    Data: Size + From<u64> + Into<u64>,
+{
+    let path = if prefix.is_empty() {
+        tree.name().to_string()
+    } else {
+        format!("{}/{}", prefix, tree.name())
+    };
+    rows.push((path.clone(), names.clone(), tree.data().into()));
+    for child in tree.children() {
+        let mut names = names.clone();
+        names.push(child.name().clone());
+        collect_rows(child, path.clone(), names, rows);
+    }
+}
+
+/// Find the item at the path of `names` below the root of a tree.
+fn find<'a, Data: Size>(
+    tree: &'a DataTree<OsStringDisplay, Data>,
+    names: &[OsStringDisplay],
+) -> Option<&'a DataTree<OsStringDisplay, Data>> {
+    names.iter().try_fold(tree, |node, name| {
+        node.children().iter().find(|child| child.name() == name)
+    })
+}
@@ -0,0 +1,116 @@
+use crate::{data_tree::DataTree, size::Size};
+use std::{
+    fmt::{Display, Error, Formatter, Write},
+    num::NonZeroUsize,
+};
+
+/// Page that [`HtmlReport`] fills with its title and its data.
+const TEMPLATE: &str = include_str!("html_report/template.html");
+
+/// Self-contained HTML page with a zoomable treemap and a collapsible table of a [`DataTree`],
+/// which requires no network resources.
+#[derive(Debug)]
+pub struct HtmlReport<'a, Name, Data>
+where
+    Name: Display,
+    Data: Size,
+{
+    /// The tree to report.
+    pub data_tree: &'a DataTree<Name, Data>,
+    /// Format to be used to [`display`](Size::display) the data.
+    pub bytes_format: Data::DisplayFormat,
+    /// Maximum number of levels that should be embedded.
+    pub max_depth: NonZeroUsize,
+}
+
+impl<'a, Name, Data> HtmlReport<'a, Name, Data>
+where
+    Name: Display,
+    Data: Size + Into<u64>,
+{
+    /// Serialize the tree into JSON, in which every node is an object of its name (`n`), its
+    /// size (`s`), its formatted size (`f`) and its children (`c`, omitted when there are none).
+    pub fn json(&self) -> String {
+        fn write_node<Name, Data>(
+            json: &mut String,
+            node: &DataTree<Name, Data>,
+            bytes_format: Data::DisplayFormat,
+            remaining_depth: usize,
+        ) where
+            Name: Display,
+            Data: Size + Into<u64>,
+        {
+            json.push_str("{\"n\":");
+            write_string(json, &node.name().to_string());
+            let size: u64 = node.data().into();
+            write!(json, ",\"s\":{},\"f\":", size).expect("write to a string");
+            write_string(json, &node.data().display(bytes_format).to_string());
+            if remaining_depth > 1 && !node.children().is_empty() {
+                json.push_str(",\"c\":[");
+                for (index, child) in node.children().iter().enumerate() {
+                    if index > 0 {
+                        json.push(',');
+                    }
+                    write_node(json, child, bytes_format, remaining_depth - 1);
+                }
+                json.push(']');
+            }
+            json.push('}');
+        }
+
+        let mut json = String::new();
+        write_node(
+            &mut json,
+            self.data_tree,
+            self.bytes_format,
+            self.max_depth.get(),
+        );
+        json
+    }
+}
+
+impl<'a, Name, Data> Display for HtmlReport<'a, Name, Data>
+where
+    Name: Display,
+    Data: Size + Into<u64>,
+{
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> Result<(), Error> {
+        let title = escape_html(&self.data_tree.name().to_string());
+        let mut parts = TEMPLATE.splitn(2, "__DATA__");
+        let head = parts.next().expect("template has a head");
+        let tail = parts
+            .next()
+            .expect("template has a placeholder of the data");
+        write!(
+            formatter,
+            "{}{}{}",
+            head.replace("__TITLE__", &title),
+            self.json(),
+            tail.replace("__TITLE__", &title),
+        )
+    }
+}
+
+/// Write a JSON string that can be embedded in a `<script>` element.
+fn write_string(json: &mut String, string: &str) {
+    json.push('"');
+    for char in string.chars() {
+        match char {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '<' => json.push_str("\\u003c"), // prevents `</script>` from closing the element.
+            '\u{0}'..='\u{1F}' => {
+                write!(json, "\\u{:04x}", char as u32).expect("write to a string")
+            }
+            _ => json.push(char),
+        }
+    }
+    json.push('"');
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,16 @@
+use std::{
+    fs::read,
+    io::{stdin, Error, Read},
+    path::Path,
+};
+
+/// Read the content of a file, or of the standard input if the file is `-`.
+pub fn read_input(source: &Path) -> Result<Vec<u8>, Error> {
+    if source == Path::new("-") {
+        let mut content = Vec::new();
+        stdin().read_to_end(&mut content)?;
+        Ok(content)
+    } else {
+        read(source)
+    }
+}
@@ -1,17 +1,41 @@
 #![deny(warnings)]
 
+pub mod age_format;
 pub mod app;
+pub mod archive;
 pub mod args;
 pub mod bytes_format;
+pub mod capacity;
 pub mod data_tree;
+pub mod du;
+pub mod extension_breakdown;
+pub mod folded_stacks;
 pub mod fs_tree_builder;
+pub mod history;
+pub mod html_report;
+pub mod input;
+pub mod listed_tree_builder;
+#[cfg(target_os = "linux")]
+pub mod live;
+pub mod markdown;
+pub mod ncdu;
 pub mod os_string_display;
+pub mod output_format;
+pub mod overlapping_roots;
+pub mod ownership;
+pub mod prometheus;
 pub mod reporter;
 pub mod runtime_error;
+pub mod scan_cache;
+pub mod shared_prefix;
 pub mod size;
 pub mod size_getters;
+pub mod sort_key;
+pub mod svg_chart;
+pub mod tabular;
 pub mod tree_builder;
 pub mod visualizer;
+pub mod weighted_tree;
 
 /// The main program.
 pub fn main() {
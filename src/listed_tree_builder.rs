@@ -0,0 +1,165 @@
+use super::{
+    data_tree::DataTree,
+    input::read_input,
+    os_string_display::OsStringDisplay,
+    reporter::{error_report::Operation::*, ErrorReport, Event, Reporter},
+    size::Size,
+    tree_builder::{Info, TreeBuilder},
+};
+use pipe_trait::Pipe;
+use std::{
+    collections::{HashMap, HashSet},
+    ffi::OsString,
+    fs::{symlink_metadata, Metadata},
+    io::Error,
+    path::{Component, Path, PathBuf},
+};
+
+/// Build a [`DataTree`] from a list of paths using [`From`] or [`Into`].
+///
+/// The paths are arranged by their components under a root named `.`, and only the listed
+/// items are measured: the contents of a listed directory are ignored unless they are also
+/// listed, and the directories that merely lead to the listed items hold nothing but the
+/// totals of their descendants.
+#[derive(Debug)]
+pub struct ListedTreeBuilder<Data, GetData, Report, PostProcessChildren>
+where
+    Data: Size + Send + Sync,
+    GetData: Fn(&Metadata) -> Data + Sync,
+    Report: Reporter<Data> + Sync,
+    PostProcessChildren: Fn(&mut Vec<DataTree<OsStringDisplay, Data>>) + Copy + Send + Sync,
+{
+    /// The listed files and/or directories.
+    pub paths: Vec<PathBuf>,
+    /// Returns size of an item.
+    pub get_data: GetData,
+    /// Reports progress to external system.
+    pub reporter: Report,
+    /// Processes lists of children after forming.
+    pub post_process_children: PostProcessChildren,
+}
+
+impl<Data, GetData, Report, PostProcessChildren>
+    From<ListedTreeBuilder<Data, GetData, Report, PostProcessChildren>>
+    for DataTree<OsStringDisplay, Data>
+where
+    Data: Size + Send + Sync,
+    GetData: Fn(&Metadata) -> Data + Sync,
+    Report: Reporter<Data> + Sync,
+    PostProcessChildren: Fn(&mut Vec<DataTree<OsStringDisplay, Data>>) + Copy + Send + Sync,
+{
+    fn from(builder: ListedTreeBuilder<Data, GetData, Report, PostProcessChildren>) -> Self {
+        let ListedTreeBuilder {
+            paths,
+            get_data,
+            reporter,
+            post_process_children,
+        } = builder;
+
+        // `./a/b` and `a/b` are the same item, and `.` is the root itself.
+        let mut listed = HashSet::new();
+        let mut known = HashSet::new();
+        let mut children: HashMap<PathBuf, Vec<OsStringDisplay>> = HashMap::new();
+        for path in paths {
+            let path: PathBuf = path
+                .components()
+                .filter(|component| *component != Component::CurDir)
+                .collect();
+            let mut parent = PathBuf::new();
+            for component in path.components() {
+                let child = parent.join(component);
+                if !known.contains(&child) {
+                    children
+                        .entry(parent)
+                        .or_default()
+                        .push(component.as_os_str().pipe(OsStringDisplay::os_string_from));
+                    known.insert(child.clone());
+                }
+                parent = child;
+            }
+            listed.insert(path);
+        }
+
+        TreeBuilder::<PathBuf, OsStringDisplay, Data, _, _, PostProcessChildren> {
+            name: ".".pipe(OsStringDisplay::os_string_from),
+
+            path: PathBuf::new(),
+
+            get_info: |path| {
+                let children = children.get(path).cloned().unwrap_or_default();
+
+                if !listed.contains(path) {
+                    return Info {
+                        data: Data::default(),
+                        children,
+                        is_dir: true,
+                    };
+                }
+
+                let path = if path.as_os_str().is_empty() {
+                    Path::new(".")
+                } else {
+                    path.as_path()
+                };
+                let (data, is_dir) = match symlink_metadata(path) {
+                    Err(error) => {
+                        reporter.report(Event::EncounterError(ErrorReport {
+                            operation: SymlinkMetadata,
+                            path,
+                            error,
+                        }));
+                        (Data::default(), false)
+                    }
+                    Ok(stats) => {
+                        let data = get_data(&stats);
+                        reporter.report(Event::ReceiveData(data));
+                        (data, stats.is_dir())
+                    }
+                };
+
+                Info {
+                    data,
+                    is_dir: is_dir || !children.is_empty(),
+                    children,
+                }
+            },
+
+            join_path: |prefix, name| prefix.join(&name.0),
+
+            post_process_children,
+        }
+        .into()
+    }
+}
+
+/// Split a list of paths by NUL characters if there is any, or else by newlines.
+///
+/// Empty entries are skipped.
+pub fn parse_file_list(list: &[u8]) -> Vec<PathBuf> {
+    let separator = if list.contains(&b'\0') { b'\0' } else { b'\n' };
+    list.split(|byte| *byte == separator)
+        .map(|entry| match entry {
+            [rest @ .., b'\r'] if separator == b'\n' => rest,
+            _ => entry,
+        })
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| bytes_to_os_string(entry.to_vec()).into())
+        .collect()
+}
+
+/// Read and [parse](parse_file_list) a list of paths from a file, or from the standard input
+/// if the file is `-`.
+pub fn read_file_list(source: &Path) -> Result<Vec<PathBuf>, Error> {
+    Ok(parse_file_list(&read_input(source)?))
+}
+
+#[cfg(unix)]
+fn bytes_to_os_string(bytes: Vec<u8>) -> OsString {
+    use std::os::unix::ffi::OsStringExt;
+    OsString::from_vec(bytes)
+}
+
+#[cfg(not(unix))]
+fn bytes_to_os_string(bytes: Vec<u8>) -> OsString {
+    String::from_utf8_lossy(&bytes).into_owned().into()
+}
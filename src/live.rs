@@ -0,0 +1,290 @@
+pub mod inotify;
+
+pub use inotify::Inotify;
+
+use crate::{
+    data_tree::DataTree, fs_tree_builder::FsTreeBuilder, os_string_display::OsStringDisplay,
+    reporter::Reporter, size::Size,
+};
+use inotify::{Event, WatchDescriptor, IN_IGNORED, IN_Q_OVERFLOW};
+use libc::ENOSPC;
+use std::{
+    collections::{BTreeSet, HashMap},
+    fs::{symlink_metadata, Metadata},
+    io::Error,
+    mem::take,
+    path::{Path, PathBuf},
+};
+
+/// Location of an item: the index of its root and the names of the nodes between the root
+/// and the item.
+type Location = (usize, Vec<OsStringDisplay>);
+
+/// Statistics of a call to [`LiveTree::update`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct UpdateSummary {
+    /// Number of received filesystem events.
+    pub events: usize,
+    /// Number of rescanned items.
+    pub rescanned: usize,
+    /// Number of directories that cannot be watched, which are rescanned on every update.
+    pub unwatched: usize,
+}
+
+/// Trees of a set of roots which are kept up to date by filesystem events (Linux only).
+///
+/// Only the items affected by the events are rescanned, then the data of their ancestors
+/// are recomputed. The directories beyond the limit of watches are rescanned on every
+/// update instead, and everything is rescanned if the queue of events overflows.
+#[derive(Debug)]
+pub struct LiveTree<Data, GetData, Report, PostProcessChildren>
+where
+    Data: Size + Send + Sync,
+    GetData: Fn(&Metadata) -> Data + Copy + Sync,
+    Report: Reporter<Data> + Sync,
+    PostProcessChildren: Fn(&mut Vec<DataTree<OsStringDisplay, Data>>) + Copy + Send + Sync,
+{
+    roots: Vec<PathBuf>,
+    trees: Vec<DataTree<OsStringDisplay, Data>>,
+    get_data: GetData,
+    reporter: Report,
+    post_process_children: PostProcessChildren,
+    inotify: Inotify,
+    watches: HashMap<WatchDescriptor, Location>,
+    unwatched: BTreeSet<Location>,
+}
+
+impl<Data, GetData, Report, PostProcessChildren>
+    LiveTree<Data, GetData, Report, PostProcessChildren>
+where
+    Data: Size + Send + Sync,
+    GetData: Fn(&Metadata) -> Data + Copy + Sync,
+    Report: Reporter<Data> + Sync,
+    PostProcessChildren: Fn(&mut Vec<DataTree<OsStringDisplay, Data>>) + Copy + Send + Sync,
+{
+    /// Scan the roots and start watching their directories.
+    pub fn new(
+        roots: Vec<PathBuf>,
+        get_data: GetData,
+        reporter: Report,
+        post_process_children: PostProcessChildren,
+    ) -> Result<Self, Error> {
+        let mut live_tree = LiveTree {
+            roots,
+            trees: Vec::new(),
+            get_data,
+            reporter,
+            post_process_children,
+            inotify: Inotify::new()?,
+            watches: HashMap::new(),
+            unwatched: BTreeSet::new(),
+        };
+        live_tree.rescan_all();
+        Ok(live_tree)
+    }
+
+    /// The up-to-date trees, one for each root.
+    pub fn trees(&self) -> &[DataTree<OsStringDisplay, Data>] {
+        &self.trees
+    }
+
+    /// Apply the pending filesystem events to the trees.
+    pub fn update(&mut self) -> Result<UpdateSummary, Error> {
+        let events = self.inotify.read_events()?;
+
+        if events.iter().any(|event| event.mask & IN_Q_OVERFLOW != 0) {
+            self.rescan_all();
+            return Ok(UpdateSummary {
+                events: events.len(),
+                rescanned: self.roots.len(),
+                unwatched: self.unwatched.len(),
+            });
+        }
+
+        let mut pending = self.unwatched.clone();
+        for Event {
+            watch_descriptor,
+            mask,
+            name,
+        } in &events
+        {
+            if mask & IN_IGNORED != 0 {
+                self.watches.remove(watch_descriptor);
+                continue;
+            }
+            if let Some((root_index, names)) = self.watches.get(watch_descriptor) {
+                let mut names = names.clone();
+                names.extend(name.clone().map(OsStringDisplay::from));
+                pending.insert((*root_index, names));
+            }
+        }
+
+        // Locations are sorted, so every item comes right after its rescanned ancestor.
+        let mut rescanned: Vec<Location> = Vec::new();
+        for location in pending {
+            let is_covered = rescanned.last().map_or(false, |(root_index, names)| {
+                *root_index == location.0 && location.1.starts_with(names)
+            });
+            if !is_covered {
+                rescanned.push(location);
+            }
+        }
+
+        // The watches of moved directories are kept by the kernel, so they are removed from
+        // all old locations before any new location watches them again.
+        for location in &rescanned {
+            self.unwatch_tree(location);
+        }
+        for location in &rescanned {
+            self.rescan(location.clone());
+        }
+
+        Ok(UpdateSummary {
+            events: events.len(),
+            rescanned: rescanned.len(),
+            unwatched: self.unwatched.len(),
+        })
+    }
+
+    /// Build the tree of a path.
+    fn scan(&self, path: PathBuf) -> DataTree<OsStringDisplay, Data> {
+        FsTreeBuilder {
+            root: path,
+            get_data: self.get_data,
+            reporter: &self.reporter,
+            post_process_children: self.post_process_children,
+        }
+        .into()
+    }
+
+    /// Rescan all roots and watch all their directories anew.
+    fn rescan_all(&mut self) {
+        for (watch_descriptor, _) in self.watches.drain() {
+            self.inotify.rm_watch(watch_descriptor).ok(); // the kernel may have removed it.
+        }
+        self.unwatched.clear();
+        self.trees = self
+            .roots
+            .iter()
+            .map(|root| self.scan(root.clone()))
+            .collect();
+        for root_index in 0..self.roots.len() {
+            self.watch_tree(root_index, Vec::new());
+        }
+    }
+
+    /// Rescan an item, or its closest ancestor that is already in the tree.
+    fn rescan(&mut self, (root_index, mut names): Location) {
+        loop {
+            let path = join_names(&self.roots[root_index], &names);
+
+            if names.is_empty() {
+                self.trees[root_index] = self.scan(path);
+                self.watch_tree(root_index, names);
+                return;
+            }
+
+            let replacement = symlink_metadata(&path).ok().map(|_| self.scan(path));
+            let root = &self.roots[root_index];
+            let get_data = self.get_data;
+            let get_own_data = |prefix: &[OsStringDisplay]| {
+                symlink_metadata(join_names(root, prefix))
+                    .map(|metadata| get_data(&metadata))
+                    .unwrap_or_default()
+            };
+            let is_updated = self.trees[root_index].update_descendant(
+                &names,
+                replacement,
+                get_own_data,
+                self.post_process_children,
+            );
+
+            if is_updated {
+                self.watch_tree(root_index, names);
+                return;
+            }
+
+            names.pop(); // the parent is not in the tree yet, rescan it instead.
+        }
+    }
+
+    /// Stop watching the directories of the subtree at a location.
+    fn unwatch_tree(&mut self, (root_index, names): &Location) {
+        let is_within = |location: &Location| {
+            location.0 == *root_index && location.1.starts_with(names.as_slice())
+        };
+        let watch_descriptors: Vec<_> = self
+            .watches
+            .iter()
+            .filter(|(_, location)| is_within(location))
+            .map(|(watch_descriptor, _)| *watch_descriptor)
+            .collect();
+        for watch_descriptor in watch_descriptors {
+            self.watches.remove(&watch_descriptor);
+            self.inotify.rm_watch(watch_descriptor).ok(); // the kernel may have removed it.
+        }
+        let unwatched = take(&mut self.unwatched);
+        self.unwatched = unwatched
+            .into_iter()
+            .filter(|location| !is_within(location))
+            .collect();
+    }
+
+    /// Watch the directories of the subtree at a location.
+    fn watch_tree(&mut self, root_index: usize, names: Vec<OsStringDisplay>) {
+        fn collect_directories<Data: Size>(
+            tree: &DataTree<OsStringDisplay, Data>,
+            path: PathBuf,
+            names: Vec<OsStringDisplay>,
+            directories: &mut Vec<(PathBuf, Vec<OsStringDisplay>)>,
+        ) {
+            if !tree.is_dir() {
+                return;
+            }
+            for child in tree.children() {
+                let mut names = names.clone();
+                names.push(child.name().clone());
+                collect_directories(
+                    child,
+                    path.join(child.name().as_os_str()),
+                    names,
+                    directories,
+                );
+            }
+            directories.push((path, names));
+        }
+
+        let mut tree = &self.trees[root_index];
+        for name in &names {
+            match tree.children().iter().find(|child| child.name() == name) {
+                None => return,
+                Some(child) => tree = child,
+            }
+        }
+
+        let mut directories = Vec::new();
+        let path = join_names(&self.roots[root_index], &names);
+        collect_directories(tree, path, names, &mut directories);
+
+        for (path, names) in directories {
+            let location = (root_index, names);
+            match self.inotify.add_watch(&path) {
+                Ok(watch_descriptor) => {
+                    self.unwatched.remove(&location);
+                    self.watches.insert(watch_descriptor, location);
+                }
+                Err(error) if error.raw_os_error() == Some(ENOSPC) => {
+                    self.unwatched.insert(location);
+                }
+                Err(_) => {} // the directory has been removed in the meantime.
+            }
+        }
+    }
+}
+
+/// Join a root with the names of its descendants.
+fn join_names(root: &Path, names: &[OsStringDisplay]) -> PathBuf {
+    names
+        .iter()
+        .fold(root.to_path_buf(), |path, name| path.join(name.as_os_str()))
+}
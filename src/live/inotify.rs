@@ -0,0 +1,149 @@
+use libc::{
+    c_int, inotify_add_watch, inotify_event, inotify_init1, inotify_rm_watch, IN_CLOEXEC,
+    IN_DONT_FOLLOW, IN_NONBLOCK, IN_ONLYDIR,
+};
+use std::{
+    ffi::{CString, OsString},
+    fs::File,
+    io::{Error, ErrorKind, Read},
+    mem::size_of,
+    os::unix::prelude::{AsRawFd, FromRawFd, OsStrExt, OsStringExt},
+    path::Path,
+};
+
+pub use libc::{
+    IN_ATTRIB, IN_CLOSE_WRITE, IN_CREATE, IN_DELETE, IN_DELETE_SELF, IN_IGNORED, IN_MODIFY,
+    IN_MOVED_FROM, IN_MOVED_TO, IN_MOVE_SELF, IN_Q_OVERFLOW,
+};
+
+/// Events that affect the disk usage of the items in a directory.
+const WATCHED_EVENTS: u32 = IN_MODIFY
+    | IN_ATTRIB
+    | IN_CLOSE_WRITE
+    | IN_MOVED_FROM
+    | IN_MOVED_TO
+    | IN_CREATE
+    | IN_DELETE
+    | IN_DELETE_SELF
+    | IN_MOVE_SELF
+    | IN_ONLYDIR
+    | IN_DONT_FOLLOW;
+
+/// Size of the fixed part of `struct inotify_event`.
+const EVENT_HEADER_SIZE: usize = size_of::<inotify_event>();
+
+/// Identifier of a watched directory.
+pub type WatchDescriptor = c_int;
+
+/// Event read from an [`Inotify`] instance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    /// The watched directory, or `-1` if the queue overflowed.
+    pub watch_descriptor: WatchDescriptor,
+    /// Bit mask of the kinds of the event.
+    pub mask: u32,
+    /// Name of the affected item in the watched directory, if the event is not about
+    /// the directory itself.
+    pub name: Option<OsString>,
+}
+
+/// Non-blocking inotify instance (Linux only).
+#[derive(Debug)]
+pub struct Inotify {
+    file: File,
+}
+
+impl Inotify {
+    /// Create an inotify instance.
+    pub fn new() -> Result<Self, Error> {
+        // SAFETY: `inotify_init1` takes no pointers, the flags are valid.
+        let fd = unsafe { inotify_init1(IN_NONBLOCK | IN_CLOEXEC) };
+        if fd < 0 {
+            return Err(Error::last_os_error());
+        }
+        // SAFETY: `fd` is non-negative, so it is a descriptor that `inotify_init1` has just
+        // opened, and nothing else refers to it, so the file can own it and close it.
+        let file = unsafe { File::from_raw_fd(fd) };
+        Ok(Inotify { file })
+    }
+
+    /// Watch the changes to the items in a directory.
+    ///
+    /// Watching a directory that is already watched returns its existing descriptor.
+    pub fn add_watch(&self, path: &Path) -> Result<WatchDescriptor, Error> {
+        let path = CString::new(path.as_os_str().as_bytes())
+            .map_err(|error| Error::new(ErrorKind::InvalidInput, error))?;
+        let fd = self.file.as_raw_fd();
+        // SAFETY: `fd` is the inotify instance that `self` owns, `path` is a valid
+        // NUL-terminated C string that outlives the call.
+        let watch_descriptor = unsafe { inotify_add_watch(fd, path.as_ptr(), WATCHED_EVENTS) };
+        if watch_descriptor < 0 {
+            return Err(Error::last_os_error());
+        }
+        Ok(watch_descriptor)
+    }
+
+    /// Stop watching a directory.
+    ///
+    /// The kernel then queues an event of [`IN_IGNORED`] for the descriptor.
+    pub fn rm_watch(&self, watch_descriptor: WatchDescriptor) -> Result<(), Error> {
+        let fd = self.file.as_raw_fd();
+        // SAFETY: `fd` is the inotify instance that `self` owns, an unknown descriptor only
+        // makes the call fail.
+        if unsafe { inotify_rm_watch(fd, watch_descriptor) } < 0 {
+            return Err(Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// Read all pending events without blocking.
+    pub fn read_events(&mut self) -> Result<Vec<Event>, Error> {
+        let mut events = Vec::new();
+        let mut buffer = vec![0; 64 * 1024];
+        loop {
+            let length = match self.file.read(&mut buffer) {
+                Ok(length) => length,
+                Err(error) if error.kind() == ErrorKind::WouldBlock => return Ok(events),
+                Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+                Err(error) => return Err(error),
+            };
+            if length == 0 {
+                return Ok(events);
+            }
+            events.extend(parse_events(&buffer[..length]));
+        }
+    }
+}
+
+/// Parse a sequence of `struct inotify_event`.
+fn parse_events(mut buffer: &[u8]) -> Vec<Event> {
+    let field = |buffer: &[u8], offset: usize| {
+        let mut bytes = [0; 4];
+        bytes.copy_from_slice(&buffer[offset..offset + 4]);
+        bytes
+    };
+    let mut events = Vec::new();
+    while buffer.len() >= EVENT_HEADER_SIZE {
+        let watch_descriptor = c_int::from_ne_bytes(field(buffer, 0));
+        let mask = u32::from_ne_bytes(field(buffer, 4));
+        let name_length = u32::from_ne_bytes(field(buffer, 12)) as usize;
+        let end = (EVENT_HEADER_SIZE + name_length).min(buffer.len());
+        let name: Vec<u8> = buffer[EVENT_HEADER_SIZE..end]
+            .iter()
+            .copied()
+            .take_while(|byte| *byte != 0)
+            .collect();
+        let name = if name.is_empty() {
+            None
+        } else {
+            Some(OsString::from_vec(name))
+        };
+        events.push(Event {
+            watch_descriptor,
+            mask,
+            name,
+        });
+        buffer = &buffer[end..];
+    }
+    events
+}
@@ -0,0 +1,167 @@
+use crate::{
+    data_tree::DataTree,
+    size::Size,
+    visualizer::traversal::{traverse, Visit},
+};
+use std::{
+    cmp::max,
+    fmt::{Display, Error, Formatter},
+    iter::repeat,
+    num::NonZeroUsize,
+};
+
+/// Blocks whose widths are from zero to seven eighths of a full block.
+const PARTIAL_BLOCKS: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+/// Block that fills the whole width of a character.
+const FULL_BLOCK: char = '█';
+
+/// Layout of a [`Markdown`] summary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarkdownStyle {
+    /// Table whose names are indented by their depths.
+    Table,
+    /// List whose items are nested under their parents.
+    List,
+}
+
+/// Summary of a [`DataTree`] in Markdown, which renders well in issues and pull requests,
+/// with the same items as a top-down [`Visualizer`](crate::visualizer::Visualizer).
+#[derive(Debug)]
+pub struct Markdown<'a, Name, Data>
+where
+    Name: Display,
+    Data: Size,
+{
+    /// The tree to summarize.
+    pub data_tree: &'a DataTree<Name, Data>,
+    /// Format to be used to [`display`](Size::display) the data.
+    pub bytes_format: Data::DisplayFormat,
+    /// Maximum number of levels that should be summarized.
+    pub max_depth: NonZeroUsize,
+    /// Quantity that the percentages are relative to (defaults to the data of the root, which
+    /// is also the minimum).
+    pub proportion_total: Option<u64>,
+    /// Layout of the summary.
+    pub style: MarkdownStyle,
+    /// Number of characters of the bar of Unicode blocks of every item, no bars if `None`.
+    pub bar_width: Option<NonZeroUsize>,
+}
+
+impl<'a, Name, Data> Display for Markdown<'a, Name, Data>
+where
+    Name: Display,
+    Data: Size + Into<u64>,
+{
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> Result<(), Error> {
+        let root_size: u64 = self.data_tree.data().into();
+        let total = self
+            .proportion_total
+            .map_or(root_size, |total| max(total, root_size));
+        let mut lines = Vec::new();
+        if self.style == MarkdownStyle::Table {
+            lines.push(match self.bar_width {
+                None => "| Name | Size | Percentage |\n| :--- | ---: | ---: |".to_string(),
+                Some(_) => {
+                    "| Name | Size | Percentage | Bar |\n| :--- | ---: | ---: | :--- |".to_string()
+                }
+            });
+        }
+        traverse(self.data_tree, self.max_depth, |node, visit| {
+            let Visit { ancestors, .. }: Visit<()> = visit;
+            let depth = ancestors.len();
+            let name = escape(&node.name().to_string());
+            let size: u64 = node.data().into();
+            let formatted_size = node.data().display(self.bytes_format);
+            let percentage = if total == 0 {
+                "0%".to_string()
+            } else {
+                format!("{}%", rounded_div::u64(size * 100, total))
+            };
+            let bar = self
+                .bar_width
+                .map(|width| format!("`{}`", bar(size, total, width.get())));
+            lines.push(match (self.style, bar) {
+                (MarkdownStyle::Table, None) => format!(
+                    "| {}{} | {} | {} |",
+                    "&emsp;".repeat(depth),
+                    name,
+                    formatted_size,
+                    percentage,
+                ),
+                (MarkdownStyle::Table, Some(bar)) => format!(
+                    "| {}{} | {} | {} | {} |",
+                    "&emsp;".repeat(depth),
+                    name,
+                    formatted_size,
+                    percentage,
+                    bar,
+                ),
+                (MarkdownStyle::List, None) => format!(
+                    "{}- {}: {} ({})",
+                    "  ".repeat(depth),
+                    name,
+                    formatted_size,
+                    percentage,
+                ),
+                (MarkdownStyle::List, Some(bar)) => format!(
+                    "{}- {} {}: {} ({})",
+                    "  ".repeat(depth),
+                    bar,
+                    name,
+                    formatted_size,
+                    percentage,
+                ),
+            });
+        });
+        lines
+            .iter()
+            .try_for_each(|line| writeln!(formatter, "{}", line))
+    }
+}
+
+/// Bar of `width` characters whose filled part is proportional to `part` in `whole`, with a
+/// precision of an eighth of a character.
+fn bar(part: u64, whole: u64, width: usize) -> String {
+    let eighths = if whole == 0 {
+        0
+    } else {
+        (part as f64 / whole as f64 * (width * 8) as f64).round() as usize
+    };
+    let eighths = eighths.min(width * 8);
+    let mut bar: String = repeat(FULL_BLOCK).take(eighths / 8).collect();
+    if eighths % 8 != 0 {
+        bar.push(PARTIAL_BLOCKS[eighths % 8]);
+    }
+    let padding = width - bar.chars().count();
+    bar.extend(repeat(' ').take(padding));
+    bar
+}
+
+/// Escape the characters of a name that Markdown would otherwise interpret.
+fn escape(name: &str) -> String {
+    let mut escaped = String::with_capacity(name.len());
+    let leading_digits = name.chars().take_while(char::is_ascii_digit).count();
+    for (index, char) in name.chars().enumerate() {
+        match char {
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '|' | '#' | '~' => {
+                escaped.push('\\');
+                escaped.push(char);
+            }
+            '&' => escaped.push_str("&amp;"),
+            '\n' => escaped.push_str("\\\\n"),
+            '\r' => escaped.push_str("\\\\r"),
+            // prevents a name from starting a list of its own.
+            '-' | '+' if index == 0 => {
+                escaped.push('\\');
+                escaped.push(char);
+            }
+            '.' | ')' if index == leading_digits && index > 0 => {
+                escaped.push('\\');
+                escaped.push(char);
+            }
+            _ => escaped.push(char),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,256 @@
+pub mod json;
+pub mod scan;
+
+pub use scan::{scan, NcduData};
+
+use crate::{
+    data_tree::DataTree,
+    os_string_display::{
+        escape::{os_str_bytes, os_string_from_bytes},
+        OsStringDisplay,
+    },
+    size::Size,
+};
+use derive_more::Display;
+use json::{write_string, Value};
+use std::{
+    collections::HashSet,
+    ffi::OsString,
+    io::{Error, Write},
+};
+
+/// Major version of the dump format.
+const MAJOR_VERSION: u64 = 1;
+
+/// Minor version of the dump format that is written.
+const MINOR_VERSION: u64 = 2;
+
+/// Item of a dump of [ncdu](https://dev.yorhel.nl/ncdu) (`ncdu -o`).
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct NcduEntry {
+    /// Name of the item, or the whole path of the root.
+    pub name: OsString,
+    /// Apparent size.
+    pub asize: u64,
+    /// Disk usage.
+    pub dsize: u64,
+    /// Device, which is only recorded for the root and the directories on another device
+    /// than their parents.
+    pub dev: Option<u64>,
+    /// Inode.
+    pub ino: Option<u64>,
+    /// Number of hard links of a file.
+    pub nlink: Option<u64>,
+    /// Whether the file has more than one hard link (`hlnkc`).
+    pub hardlink: bool,
+    /// Whether an error occurred when reading the item.
+    pub read_error: bool,
+    /// Why the item was excluded from the scan, e.g. `pattern` or `otherfs`.
+    pub excluded: Option<String>,
+    /// Children of a directory, `None` for other items.
+    pub children: Option<Vec<NcduEntry>>,
+}
+
+/// Size of the items to take from a dump.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NcduSize {
+    /// Apparent size (`asize`).
+    Apparent,
+    /// Disk usage (`dsize`) in blocks of 512 bytes, rounded up.
+    Blocks,
+}
+
+/// Error that occurs when parsing a dump.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum ParseError {
+    /// The text is not a dump of ncdu.
+    #[display(fmt = "Not an ncdu dump")]
+    InvalidDump,
+    /// The dump is in an unknown version of the format.
+    #[display(fmt = "Unsupported ncdu dump version {}", _0)]
+    UnsupportedVersion(u64),
+}
+
+impl NcduEntry {
+    /// Parse a dump, returning its root.
+    pub fn parse_dump(text: &[u8]) -> Result<Self, ParseError> {
+        let items = match Value::parse(text) {
+            Some(Value::Array(items)) => items,
+            _ => return Err(ParseError::InvalidDump),
+        };
+        let mut items = items.into_iter();
+        let major_version = items.next().and_then(|version| version.as_u64());
+        match major_version {
+            Some(MAJOR_VERSION) => {}
+            Some(version) => return Err(ParseError::UnsupportedVersion(version)),
+            None => return Err(ParseError::InvalidDump),
+        }
+        items
+            .nth(2) // skip the minor version and the metadata.
+            .and_then(NcduEntry::from_value)
+            .ok_or(ParseError::InvalidDump)
+    }
+
+    /// Convert an object (a file) or an array of an object followed by children (a directory).
+    fn from_value(value: Value) -> Option<Self> {
+        match value {
+            Value::Array(items) => {
+                let mut items = items.into_iter();
+                let mut entry = NcduEntry::from_info(items.next()?)?;
+                let children = items
+                    .map(NcduEntry::from_value)
+                    .collect::<Option<Vec<_>>>()?;
+                entry.children = Some(children);
+                Some(entry)
+            }
+            info @ Value::Object(_) => NcduEntry::from_info(info),
+            _ => None,
+        }
+    }
+
+    fn from_info(mut info: Value) -> Option<Self> {
+        if !matches!(info, Value::Object(_)) {
+            return None;
+        }
+        let name = match info.take("name")? {
+            Value::String(name) => name,
+            _ => return None,
+        };
+        let mut number = |key| match info.take(key) {
+            None => Some(None),
+            Some(value) => value.as_u64().map(Some),
+        };
+        let asize = number("asize")?.unwrap_or_default();
+        let dsize = number("dsize")?.unwrap_or_default();
+        let dev = number("dev")?;
+        let ino = number("ino")?;
+        let nlink = number("nlink")?;
+        let hardlink = info.take("hlnkc") == Some(Value::Bool(true));
+        let read_error = info.take("read_error") == Some(Value::Bool(true));
+        let excluded = match info.take("excluded") {
+            Some(Value::String(reason)) => Some(String::from_utf8_lossy(&reason).into_owned()),
+            _ => None,
+        };
+        Some(NcduEntry {
+            name: os_string_from_bytes(name.clone())
+                .unwrap_or_else(|| String::from_utf8_lossy(&name).into_owned().into()),
+            asize,
+            dsize,
+            dev,
+            ino,
+            nlink,
+            hardlink,
+            read_error,
+            excluded,
+            children: None,
+        })
+    }
+
+    /// Convert the entry into a [`DataTree`], counting each hard-linked file only once.
+    pub fn into_data_tree<Data, PostProcessChildren>(
+        self,
+        size: NcduSize,
+        post_process_children: PostProcessChildren,
+    ) -> DataTree<OsStringDisplay, Data>
+    where
+        Data: Size + From<u64>,
+        PostProcessChildren: Fn(&mut Vec<DataTree<OsStringDisplay, Data>>) + Copy,
+    {
+        fn convert<Data, PostProcessChildren>(
+            entry: NcduEntry,
+            device: Option<u64>,
+            counted_hardlinks: &mut HashSet<(Option<u64>, u64)>,
+            size: NcduSize,
+            post_process_children: PostProcessChildren,
+        ) -> DataTree<OsStringDisplay, Data>
+        where
+            Data: Size + From<u64>,
+            PostProcessChildren: Fn(&mut Vec<DataTree<OsStringDisplay, Data>>) + Copy,
+        {
+            let device = entry.dev.or(device);
+            let mut data = match size {
+                NcduSize::Apparent => entry.asize,
+                NcduSize::Blocks => entry.dsize / 512 + u64::from(entry.dsize % 512 != 0),
+            };
+            if let (true, Some(inode)) = (entry.hardlink, entry.ino) {
+                if !counted_hardlinks.insert((device, inode)) {
+                    data = 0;
+                }
+            }
+            let name = OsStringDisplay::from(entry.name);
+            match entry.children {
+                None => DataTree::file(name, data.into()),
+                Some(children) => {
+                    let mut children: Vec<_> = children
+                        .into_iter()
+                        .map(|child| {
+                            convert(
+                                child,
+                                device,
+                                counted_hardlinks,
+                                size,
+                                post_process_children,
+                            )
+                        })
+                        .collect();
+                    post_process_children(&mut children);
+                    DataTree::dir(name, data.into(), children)
+                }
+            }
+        }
+
+        convert(self, None, &mut HashSet::new(), size, post_process_children)
+    }
+
+    /// Write a dump whose root is this entry.
+    pub fn write_dump(&self, output: &mut impl Write, timestamp: u64) -> Result<(), Error> {
+        writeln!(
+            output,
+            "[{},{},{{\"progname\":\"dirt\",\"progver\":\"{}\",\"timestamp\":{}}},",
+            MAJOR_VERSION,
+            MINOR_VERSION,
+            env!("CARGO_PKG_VERSION"),
+            timestamp,
+        )?;
+        self.write(output)?;
+        writeln!(output, "]")
+    }
+
+    fn write(&self, output: &mut impl Write) -> Result<(), Error> {
+        match &self.children {
+            None => self.write_info(output),
+            Some(children) => {
+                output.write_all(b"[")?;
+                self.write_info(output)?;
+                for child in children {
+                    output.write_all(b",\n")?;
+                    child.write(output)?;
+                }
+                output.write_all(b"]")
+            }
+        }
+    }
+
+    fn write_info(&self, output: &mut impl Write) -> Result<(), Error> {
+        output.write_all(b"{\"name\":")?;
+        write_string(output, &os_str_bytes(&self.name))?;
+        write!(output, ",\"asize\":{},\"dsize\":{}", self.asize, self.dsize)?;
+        let numbers = [("dev", self.dev), ("ino", self.ino), ("nlink", self.nlink)];
+        for (key, value) in numbers.iter() {
+            if let Some(value) = value {
+                write!(output, ",\"{}\":{}", key, value)?;
+            }
+        }
+        if self.hardlink {
+            output.write_all(b",\"hlnkc\":true")?;
+        }
+        if self.read_error {
+            output.write_all(b",\"read_error\":true")?;
+        }
+        if let Some(reason) = &self.excluded {
+            output.write_all(b",\"excluded\":")?;
+            write_string(output, reason.as_bytes())?;
+        }
+        output.write_all(b"}")
+    }
+}
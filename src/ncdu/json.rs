@@ -0,0 +1,234 @@
+use std::{
+    char::from_u32,
+    io::{Error, Write},
+};
+
+/// Maximum number of nested arrays and objects, beyond which a document is rejected instead
+/// of exhausting the stack of the recursive parser.
+pub const MAX_DEPTH: usize = 1024;
+
+/// JSON value.
+///
+/// Strings are kept as bytes since ncdu writes names that are not valid UTF-8 as-is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    /// Text of a number.
+    Number(String),
+    String(Vec<u8>),
+    Array(Vec<Value>),
+    /// Members in their order of appearance.
+    Object(Vec<(Vec<u8>, Value)>),
+}
+
+impl Value {
+    /// Parse a whole JSON document, which is nested no deeper than [`MAX_DEPTH`].
+    pub fn parse(text: &[u8]) -> Option<Self> {
+        let mut parser = Parser {
+            text,
+            position: 0,
+            depth: 0,
+        };
+        let value = parser.value()?;
+        parser.skip_whitespaces();
+        if parser.position == text.len() {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    /// Take the value of a member out of an object.
+    pub fn take(&mut self, key: &str) -> Option<Value> {
+        match self {
+            Value::Object(members) => members
+                .iter()
+                .position(|(name, _)| name == key.as_bytes())
+                .map(|index| members.swap_remove(index).1),
+            _ => None,
+        }
+    }
+
+    /// The number if the value is a non-negative integer.
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            Value::Number(number) => number.parse().ok(),
+            _ => None,
+        }
+    }
+}
+
+struct Parser<'a> {
+    text: &'a [u8],
+    position: usize,
+    /// Number of arrays and objects that enclose the current position.
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<u8> {
+        self.text.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Option<u8> {
+        let byte = self.peek()?;
+        self.position += 1;
+        Some(byte)
+    }
+
+    fn skip_whitespaces(&mut self) {
+        while let Some(b' ') | Some(b'\t') | Some(b'\n') | Some(b'\r') = self.peek() {
+            self.position += 1;
+        }
+    }
+
+    fn literal(&mut self, literal: &str, value: Value) -> Option<Value> {
+        let end = self.position + literal.len();
+        if self.text.get(self.position..end)? == literal.as_bytes() {
+            self.position = end;
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    fn value(&mut self) -> Option<Value> {
+        self.skip_whitespaces();
+        match self.peek()? {
+            b'n' => self.literal("null", Value::Null),
+            b't' => self.literal("true", Value::Bool(true)),
+            b'f' => self.literal("false", Value::Bool(false)),
+            b'"' => self.string().map(Value::String),
+            b'[' => self.nested(Parser::array),
+            b'{' => self.nested(Parser::object),
+            b'-' | b'0'..=b'9' => self.number(),
+            _ => None,
+        }
+    }
+
+    fn nested(&mut self, parse: fn(&mut Self) -> Option<Value>) -> Option<Value> {
+        if self.depth == MAX_DEPTH {
+            return None;
+        }
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn number(&mut self) -> Option<Value> {
+        let start = self.position;
+        while let Some(b'-') | Some(b'+') | Some(b'.') | Some(b'e') | Some(b'E')
+        | Some(b'0'..=b'9') = self.peek()
+        {
+            self.position += 1;
+        }
+        let number = std::str::from_utf8(&self.text[start..self.position]).ok()?;
+        Some(Value::Number(number.to_string()))
+    }
+
+    fn string(&mut self) -> Option<Vec<u8>> {
+        if self.next()? != b'"' {
+            return None;
+        }
+        let mut string = Vec::new();
+        loop {
+            match self.next()? {
+                b'"' => return Some(string),
+                b'\\' => match self.next()? {
+                    byte @ b'"' | byte @ b'\\' | byte @ b'/' => string.push(byte),
+                    b'b' => string.push(0x08),
+                    b'f' => string.push(0x0C),
+                    b'n' => string.push(b'\n'),
+                    b'r' => string.push(b'\r'),
+                    b't' => string.push(b'\t'),
+                    b'u' => {
+                        let mut code = self.hex()?;
+                        if (0xD800..0xDC00).contains(&code) {
+                            // A surrogate pair.
+                            if self.next()? != b'\\' || self.next()? != b'u' {
+                                return None;
+                            }
+                            let low = self.hex()?;
+                            if !(0xDC00..0xE000).contains(&low) {
+                                return None;
+                            }
+                            code = 0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00);
+                        }
+                        let mut buffer = [0; 4];
+                        let character = from_u32(code)?.encode_utf8(&mut buffer);
+                        string.extend_from_slice(character.as_bytes());
+                    }
+                    _ => return None,
+                },
+                byte => string.push(byte),
+            }
+        }
+    }
+
+    fn hex(&mut self) -> Option<u32> {
+        let end = self.position + 4;
+        let hex = std::str::from_utf8(self.text.get(self.position..end)?).ok()?;
+        self.position = end;
+        u32::from_str_radix(hex, 16).ok()
+    }
+
+    fn array(&mut self) -> Option<Value> {
+        self.next()?; // `[`
+        let mut items = Vec::new();
+        self.skip_whitespaces();
+        if self.peek()? == b']' {
+            self.next()?;
+            return Some(Value::Array(items));
+        }
+        loop {
+            items.push(self.value()?);
+            self.skip_whitespaces();
+            match self.next()? {
+                b',' => continue,
+                b']' => return Some(Value::Array(items)),
+                _ => return None,
+            }
+        }
+    }
+
+    fn object(&mut self) -> Option<Value> {
+        self.next()?; // `{`
+        let mut members = Vec::new();
+        self.skip_whitespaces();
+        if self.peek()? == b'}' {
+            self.next()?;
+            return Some(Value::Object(members));
+        }
+        loop {
+            self.skip_whitespaces();
+            let key = self.string()?;
+            self.skip_whitespaces();
+            if self.next()? != b':' {
+                return None;
+            }
+            members.push((key, self.value()?));
+            self.skip_whitespaces();
+            match self.next()? {
+                b',' => continue,
+                b'}' => return Some(Value::Object(members)),
+                _ => return None,
+            }
+        }
+    }
+}
+
+/// Write bytes as a JSON string, escaping only quotes, backslashes and control characters.
+pub fn write_string(output: &mut impl Write, string: &[u8]) -> Result<(), Error> {
+    output.write_all(b"\"")?;
+    for byte in string {
+        match byte {
+            b'"' => output.write_all(b"\\\"")?,
+            b'\\' => output.write_all(b"\\\\")?,
+            0x00..=0x1F | 0x7F => write!(output, "\\u{:04x}", byte)?,
+            _ => output.write_all(&[*byte])?,
+        }
+    }
+    output.write_all(b"\"")
+}
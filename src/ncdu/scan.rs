@@ -0,0 +1,242 @@
+use super::NcduEntry;
+use crate::{
+    data_tree::DataTree,
+    fs_tree_builder::FsTreeBuilder,
+    os_string_display::OsStringDisplay,
+    reporter::{Event, Reporter},
+    size::{Blocks, Bytes, Size},
+};
+use std::{
+    collections::HashSet,
+    fs::Metadata,
+    iter::Sum,
+    ops::{Add, AddAssign, Mul},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+/// Measured quantity of an item and its descendants, paired with the fields that a dump
+/// records for the item itself.
+///
+/// **NOTE:** Addition sums the measured quantities but keeps the fields of the left operand,
+/// like [`Owned`](crate::size::Owned) does, so the fields of a directory stay its own.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct NcduData<Data> {
+    /// Total measured quantity of the item and its descendants.
+    pub size: Data,
+    /// Apparent size of the item itself.
+    pub asize: u64,
+    /// Disk usage of the item itself.
+    pub dsize: u64,
+    /// Device of the item itself.
+    pub dev: Option<u64>,
+    /// Inode of the item itself.
+    pub ino: Option<u64>,
+    /// Number of hard links of the item itself.
+    pub nlink: Option<u64>,
+}
+
+impl<Data: Copy> NcduData<Data> {
+    /// Pair the measured quantity of a single item with the fields of its metadata.
+    pub fn new(size: Data, stats: &Metadata) -> Self {
+        let (dev, ino, nlink) = identity(stats);
+        NcduData {
+            size,
+            asize: stats.len(),
+            dsize: disk_usage(stats),
+            dev,
+            ino,
+            nlink,
+        }
+    }
+}
+
+impl<Data: Add<Output = Data>> Add for NcduData<Data> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        NcduData {
+            size: self.size + rhs.size,
+            ..self
+        }
+    }
+}
+
+impl<Data: Add<Output = Data> + Copy> AddAssign for NcduData<Data> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<Data: Add<Output = Data> + Default> Sum for NcduData<Data> {
+    fn sum<Iter: Iterator<Item = Self>>(iter: Iter) -> Self {
+        iter.fold(NcduData::default(), Add::add)
+    }
+}
+
+macro_rules! ncdu_data {
+    ($data:ident = $inner:ty) => {
+        impl Size for NcduData<$data> {
+            type Inner = $inner;
+            type DisplayFormat = <$data as Size>::DisplayFormat;
+            type DisplayOutput = <$data as Size>::DisplayOutput;
+            fn display(self, format: Self::DisplayFormat) -> Self::DisplayOutput {
+                self.size.display(format)
+            }
+        }
+
+        impl From<NcduData<$data>> for $inner {
+            fn from(data: NcduData<$data>) -> Self {
+                data.size.into()
+            }
+        }
+
+        impl From<$inner> for NcduData<$data> {
+            fn from(inner: $inner) -> Self {
+                NcduData {
+                    size: inner.into(),
+                    ..NcduData::default()
+                }
+            }
+        }
+
+        impl Mul<NcduData<$data>> for $inner {
+            type Output = NcduData<$data>;
+            fn mul(self, rhs: NcduData<$data>) -> Self::Output {
+                NcduData {
+                    size: self * rhs.size,
+                    ..rhs
+                }
+            }
+        }
+    };
+}
+
+ncdu_data!(Bytes = u64);
+ncdu_data!(Blocks = u64);
+
+/// Reporter that remembers the paths of the errors before passing them on.
+struct RecordErrors<Report> {
+    reporter: Report,
+    paths: Mutex<HashSet<PathBuf>>,
+}
+
+impl<Data, Report> Reporter<Data> for RecordErrors<Report>
+where
+    Data: Size,
+    Report: Reporter<Data>,
+{
+    fn report(&self, event: Event<Data>) {
+        if let Event::EncounterError(error_report) = &event {
+            if let Ok(mut paths) = self.paths.lock() {
+                paths.insert(error_report.path.to_path_buf());
+            }
+        }
+        self.reporter.report(event);
+    }
+}
+
+/// Scan a directory tree into an entry of an ncdu dump, whose progress is reported in the
+/// quantity measured by `get_data`.
+///
+/// The root is named after `name`, ncdu writes the whole path of the scanned directory there.
+pub fn scan<Data, GetData, Report>(
+    root: &Path,
+    name: impl AsRef<Path>,
+    get_data: GetData,
+    reporter: Report,
+) -> NcduEntry
+where
+    Data: Copy + Send + Sync,
+    NcduData<Data>: Size + Send + Sync,
+    GetData: Fn(&Metadata) -> Data + Sync,
+    Report: Reporter<NcduData<Data>> + Sync,
+{
+    let reporter = RecordErrors {
+        reporter,
+        paths: Mutex::default(),
+    };
+    let tree: DataTree<OsStringDisplay, NcduData<Data>> = FsTreeBuilder {
+        root: root.to_path_buf(),
+        get_data: |stats: &Metadata| NcduData::new(get_data(stats), stats),
+        reporter: &reporter,
+        post_process_children: |_: &mut Vec<DataTree<OsStringDisplay, NcduData<Data>>>| {},
+    }
+    .into();
+    let failed_paths = reporter
+        .paths
+        .into_inner()
+        .unwrap_or_else(|error| error.into_inner());
+    let mut entry = convert(&tree, root.to_path_buf(), None, &failed_paths);
+    entry.name = name.as_ref().as_os_str().to_os_string();
+    entry
+}
+
+/// Convert a scanned node whose path is `path` into an entry.
+fn convert<Data>(
+    tree: &DataTree<OsStringDisplay, NcduData<Data>>,
+    path: PathBuf,
+    parent_device: Option<u64>,
+    failed_paths: &HashSet<PathBuf>,
+) -> NcduEntry
+where
+    NcduData<Data>: Size,
+{
+    let NcduData {
+        asize,
+        dsize,
+        dev,
+        ino,
+        nlink,
+        ..
+    } = tree.data();
+    let mut entry = NcduEntry {
+        name: tree.name().as_os_str().to_os_string(),
+        asize,
+        dsize,
+        ino,
+        read_error: failed_paths.contains(&path),
+        ..NcduEntry::default()
+    };
+    if tree.is_dir() {
+        if dev != parent_device {
+            entry.dev = dev;
+        }
+        let children = tree
+            .children()
+            .iter()
+            .map(|child| {
+                let path = path.join(child.name().as_os_str());
+                convert(child, path, dev, failed_paths)
+            })
+            .collect();
+        entry.children = Some(children);
+    } else if let Some(link_count) = nlink.filter(|count| *count > 1) {
+        entry.hardlink = true;
+        entry.nlink = Some(link_count);
+    }
+    entry
+}
+
+/// Device, inode and number of hard links of an item.
+#[cfg(unix)]
+fn identity(stats: &Metadata) -> (Option<u64>, Option<u64>, Option<u64>) {
+    use std::os::unix::prelude::MetadataExt;
+    (Some(stats.dev()), Some(stats.ino()), Some(stats.nlink()))
+}
+
+/// Device, inode and number of hard links of an item.
+#[cfg(not(unix))]
+fn identity(_: &Metadata) -> (Option<u64>, Option<u64>, Option<u64>) {
+    (None, None, None)
+}
+
+#[cfg(unix)]
+fn disk_usage(stats: &Metadata) -> u64 {
+    use std::os::unix::prelude::MetadataExt;
+    stats.blocks() * 512
+}
+
+#[cfg(not(unix))]
+fn disk_usage(stats: &Metadata) -> u64 {
+    stats.len()
+}
@@ -1,3 +1,5 @@
+pub mod escape;
+
 use derive_more::{AsMut, AsRef, Deref, DerefMut, From, FromStr};
 use std::{
     ffi::{OsStr, OsString},
@@ -7,7 +9,8 @@ use std::{
 /// [`Display`] inner [`OsStr`] or [`OsString`].
 ///
 /// If the inner string can be converted to UTF-8, displays the UTF-8.
-/// Otherwise, displays its [`Debug`] form.
+/// Otherwise, displays a quoted form in which backslashes, double quotes and control
+/// characters are escaped by a fixed table and invalid bytes are written as `\xNN`.
 #[derive(
     Debug,
     Default,
@@ -71,7 +74,87 @@ where
         if let Some(utf8) = inner.to_str() {
             write!(formatter, "{}", utf8)
         } else {
-            write!(formatter, "{:?}", inner)
+            write_escaped(inner, formatter)
+        }
+    }
+}
+
+/// Write a valid character of a non-UTF-8 name.
+///
+/// The table is spelled out here instead of relying on [`char::escape_debug`], whose output
+/// changes along with the Unicode tables of the standard library:
+/// * `\0`, `\t`, `\n`, `\r`, `\"` and `\\` are written as such,
+/// * the other control characters (`U+0000` to `U+001F` and `U+007F` to `U+009F`) are written
+///   as `\u{NN}`,
+/// * every other character is written as-is.
+fn write_char_escaped(formatter: &mut Formatter<'_>, character: char) -> Result<(), Error> {
+    match character {
+        '\0' => write!(formatter, "\\0"),
+        '\t' => write!(formatter, "\\t"),
+        '\n' => write!(formatter, "\\n"),
+        '\r' => write!(formatter, "\\r"),
+        '"' => write!(formatter, "\\\""),
+        '\\' => write!(formatter, "\\\\"),
+        '\u{0}'..='\u{1F}' | '\u{7F}'..='\u{9F}' => {
+            write!(formatter, "\\u{{{:X}}}", character as u32)
         }
+        _ => write!(formatter, "{}", character),
     }
 }
+
+/// Write a non-UTF-8 [`OsStr`] in a quoted and escaped form.
+///
+/// The form resembles the [`Debug`] form but is implemented here so that
+/// the output does not change along with the standard library.
+#[cfg(unix)]
+fn write_escaped(text: &OsStr, formatter: &mut Formatter<'_>) -> Result<(), Error> {
+    use std::{os::unix::ffi::OsStrExt, str::from_utf8};
+
+    fn write_valid(formatter: &mut Formatter<'_>, valid: &str) -> Result<(), Error> {
+        valid
+            .chars()
+            .try_for_each(|character| write_char_escaped(formatter, character))
+    }
+
+    write!(formatter, "\"")?;
+    let mut bytes = text.as_bytes();
+    loop {
+        match from_utf8(bytes) {
+            Ok(valid) => {
+                write_valid(formatter, valid)?;
+                break;
+            }
+            Err(error) => {
+                let (valid, rest) = bytes.split_at(error.valid_up_to());
+                let valid = from_utf8(valid).expect("valid part of the string");
+                let (broken, rest) = rest.split_at(error.error_len().unwrap_or(rest.len()));
+                write_valid(formatter, valid)?;
+                broken
+                    .iter()
+                    .try_for_each(|byte| write!(formatter, "\\x{:02X}", byte))?;
+                bytes = rest;
+            }
+        }
+    }
+    write!(formatter, "\"")
+}
+
+/// Write a non-UTF-16 [`OsStr`] in a quoted and escaped form, in which unpaired surrogates are
+/// written as `\u{DNNN}`.
+#[cfg(windows)]
+fn write_escaped(text: &OsStr, formatter: &mut Formatter<'_>) -> Result<(), Error> {
+    use std::{char::decode_utf16, os::windows::ffi::OsStrExt};
+
+    write!(formatter, "\"")?;
+    decode_utf16(text.encode_wide()).try_for_each(|unit| match unit {
+        Ok(character) => write_char_escaped(formatter, character),
+        Err(error) => write!(formatter, "\\u{{{:X}}}", error.unpaired_surrogate()),
+    })?;
+    write!(formatter, "\"")
+}
+
+/// Write a non-UTF-8 [`OsStr`] in its [`Debug`] form.
+#[cfg(not(any(unix, windows)))]
+fn write_escaped(text: &OsStr, formatter: &mut Formatter<'_>) -> Result<(), Error> {
+    write!(formatter, "{:?}", text)
+}
@@ -0,0 +1,68 @@
+use std::ffi::{OsStr, OsString};
+
+/// Escape a name into a single word of printable ASCII characters, so that it can be
+/// stored in a line-based text format.
+///
+/// Bytes of printable ASCII characters other than the backslash are kept as-is, all other
+/// bytes (including spaces) are written as `\xNN`.
+///
+/// **NOTE:** On platforms other than POSIX, names that are not valid Unicode are converted lossily.
+pub fn escape(name: &OsStr) -> String {
+    let mut result = String::new();
+    for byte in os_str_bytes(name).iter().copied() {
+        if byte.is_ascii_graphic() && byte != b'\\' {
+            result.push(byte as char);
+        } else {
+            result += &format!("\\x{:02x}", byte);
+        }
+    }
+    result
+}
+
+/// Reverse [`escape`], returning `None` if `text` is not a valid escaped name.
+pub fn unescape(text: &str) -> Option<OsString> {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut rest = text.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'\\' {
+            let hex = tail.get(..3).filter(|hex| hex[0] == b'x')?;
+            let hex = std::str::from_utf8(&hex[1..]).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            rest = &tail[3..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    os_string_from_bytes(bytes)
+}
+
+/// Bytes of a name.
+///
+/// **NOTE:** On platforms other than POSIX, names that are not valid Unicode are converted lossily.
+#[cfg(unix)]
+pub fn os_str_bytes(name: &OsStr) -> Vec<u8> {
+    use std::os::unix::prelude::OsStrExt;
+    name.as_bytes().to_vec()
+}
+
+/// Bytes of a name.
+///
+/// **NOTE:** On platforms other than POSIX, names that are not valid Unicode are converted lossily.
+#[cfg(not(unix))]
+pub fn os_str_bytes(name: &OsStr) -> Vec<u8> {
+    name.to_string_lossy().into_owned().into_bytes()
+}
+
+/// Name from its bytes, returning `None` if the platform cannot represent it.
+#[cfg(unix)]
+pub fn os_string_from_bytes(bytes: Vec<u8>) -> Option<OsString> {
+    use std::os::unix::prelude::OsStringExt;
+    Some(OsString::from_vec(bytes))
+}
+
+/// Name from its bytes, returning `None` if the platform cannot represent it.
+#[cfg(not(unix))]
+pub fn os_string_from_bytes(bytes: Vec<u8>) -> Option<OsString> {
+    String::from_utf8(bytes).ok().map(OsString::from)
+}
@@ -0,0 +1,162 @@
+use crate::{
+    folded_stacks::FoldedStacks,
+    html_report::HtmlReport,
+    markdown::{Markdown, MarkdownStyle},
+    prometheus::PrometheusMetrics,
+    reporter::ProgressReport,
+    size::Size,
+    svg_chart::{ChartKind, ColorScheme, SvgChart, DEFAULT_SVG_HEIGHT, DEFAULT_SVG_WIDTH},
+    tabular::{Delimiter, Tabular},
+    visualizer::Visualizer,
+};
+use std::{
+    fmt::Display,
+    fs::{rename, File},
+    io::{self, Write},
+    num::NonZeroUsize,
+    path::Path,
+    time::Duration,
+};
+use strum::{AsRefStr, EnumString, EnumVariantNames};
+
+/// Format of the output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AsRefStr, EnumString, EnumVariantNames)]
+pub enum OutputFormat {
+    /// Tree with proportion bars.
+    #[strum(serialize = "text")]
+    Text,
+    /// Comma-separated values, one row per node.
+    #[strum(serialize = "csv")]
+    Csv,
+    /// Tab-separated values, one row per node.
+    #[strum(serialize = "tsv")]
+    Tsv,
+    /// Self-contained HTML page with an interactive treemap and a collapsible table.
+    #[strum(serialize = "html")]
+    Html,
+    /// Static SVG image of a squarified treemap.
+    #[strum(serialize = "svg-treemap")]
+    SvgTreemap,
+    /// Static SVG image of an icicle chart.
+    #[strum(serialize = "svg-icicle")]
+    SvgIcicle,
+    /// Folded stacks of the paths of the nodes for flamegraph tools.
+    #[strum(serialize = "folded")]
+    Folded,
+    /// Metrics in the text exposition format of Prometheus.
+    #[strum(serialize = "prometheus")]
+    Prometheus,
+    /// Markdown table whose names are indented by their depths.
+    #[strum(serialize = "markdown-table")]
+    MarkdownTable,
+    /// Nested Markdown list.
+    #[strum(serialize = "markdown-list")]
+    MarkdownList,
+}
+
+/// Information besides the tree that some formats need.
+#[derive(Debug, Clone, Copy)]
+pub struct RenderContext<'a, Data: Size> {
+    /// How to color the rectangles of the charts.
+    pub color_scheme: ColorScheme,
+    /// Name of the measured quantity.
+    pub quantity: &'a str,
+    /// Unit of the measured quantity.
+    pub unit: &'a str,
+    /// Progress of the scan that produced the tree, if it is known.
+    pub progress: Option<ProgressReport<Data>>,
+    /// Time taken by the scan that produced the tree, if the tree was scanned.
+    pub scan_duration: Option<Duration>,
+    /// Width of the bars of the Markdown outputs, no bars if `None`.
+    pub markdown_bar: Option<NonZeroUsize>,
+}
+
+impl OutputFormat {
+    pub(crate) fn default_value() -> &'static str {
+        OutputFormat::Text.as_ref()
+    }
+
+    /// Render the tree of a [`Visualizer`] in this format.
+    pub fn render<Name, Data>(
+        self,
+        visualizer: Visualizer<Name, Data>,
+        context: RenderContext<Data>,
+    ) -> String
+    where
+        Name: Display,
+        Data: Size + Into<u64>,
+    {
+        let tabular = |delimiter| {
+            Tabular {
+                data_tree: visualizer.data_tree,
+                bytes_format: visualizer.bytes_format,
+                max_depth: visualizer.max_depth,
+                delimiter,
+            }
+            .to_string()
+        };
+        let markdown = |style| {
+            Markdown {
+                data_tree: visualizer.data_tree,
+                bytes_format: visualizer.bytes_format,
+                max_depth: visualizer.max_depth,
+                proportion_total: visualizer.proportion_total,
+                style,
+                bar_width: context.markdown_bar,
+            }
+            .to_string()
+        };
+        let svg_chart = |kind| {
+            SvgChart {
+                data_tree: visualizer.data_tree,
+                bytes_format: visualizer.bytes_format,
+                max_depth: visualizer.max_depth,
+                kind,
+                color_scheme: context.color_scheme,
+                width: DEFAULT_SVG_WIDTH,
+                height: DEFAULT_SVG_HEIGHT,
+            }
+            .to_string()
+        };
+        match self {
+            OutputFormat::Text => visualizer.to_string(),
+            OutputFormat::Csv => tabular(Delimiter::Comma),
+            OutputFormat::Tsv => tabular(Delimiter::Tab),
+            OutputFormat::Html => HtmlReport {
+                data_tree: visualizer.data_tree,
+                bytes_format: visualizer.bytes_format,
+                max_depth: visualizer.max_depth,
+            }
+            .to_string(),
+            OutputFormat::SvgTreemap => svg_chart(ChartKind::Treemap),
+            OutputFormat::SvgIcicle => svg_chart(ChartKind::Icicle),
+            OutputFormat::Folded => FoldedStacks {
+                data_tree: visualizer.data_tree,
+                max_depth: visualizer.max_depth,
+            }
+            .to_string(),
+            OutputFormat::Prometheus => PrometheusMetrics {
+                data_tree: visualizer.data_tree,
+                max_depth: visualizer.max_depth,
+                quantity: context.quantity,
+                unit: context.unit,
+                progress: context.progress,
+                scan_duration: context.scan_duration,
+            }
+            .to_string(),
+            OutputFormat::MarkdownTable => markdown(MarkdownStyle::Table),
+            OutputFormat::MarkdownList => markdown(MarkdownStyle::List),
+        }
+    }
+}
+
+/// Write the output to a file, replacing the previous file atomically so that readers such as
+/// the textfile collector of node_exporter never see a partial file.
+pub fn write_output_file(path: &Path, output: &str) -> io::Result<()> {
+    let mut temporary_path = path.as_os_str().to_owned();
+    temporary_path.push(".tmp");
+    let mut file = File::create(&temporary_path)?;
+    file.write_all(output.as_bytes())?;
+    file.sync_all()?; // the content must be on the disk before the rename makes it visible.
+    rename(&temporary_path, path)
+}
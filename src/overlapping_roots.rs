@@ -0,0 +1,128 @@
+use derive_more::Display;
+use std::{
+    fs::canonicalize,
+    path::{Path, PathBuf},
+};
+
+#[cfg(unix)]
+use std::{fs::symlink_metadata, os::unix::prelude::MetadataExt};
+
+/// How a root is covered by another root.
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq)]
+pub enum OverlapKind {
+    /// Both roots are the same item.
+    #[display(fmt = "the same as")]
+    Identical,
+    /// The root is a descendant of the other root.
+    #[display(fmt = "inside")]
+    Descendant,
+}
+
+/// Root that is dropped because it is already covered by another root.
+#[derive(Debug, Display, Clone, PartialEq, Eq)]
+#[display(fmt = "Ignored {:?}, which is {} {:?}", root, kind, covering_root)]
+pub struct OverlappingRoot {
+    /// The dropped root.
+    pub root: PathBuf,
+    /// How the root is covered.
+    pub kind: OverlapKind,
+    /// The root that covers the dropped root.
+    pub covering_root: PathBuf,
+}
+
+/// Identity of a root that does not depend on the path used to reach it.
+#[derive(Debug)]
+struct RootIdentity {
+    /// Absolute path without symbolic links, except for the last component which is measured
+    /// as-is.
+    canonical_path: Option<PathBuf>,
+    /// Device and inode of the root (POSIX only).
+    inode: Option<(u64, u64)>,
+}
+
+impl RootIdentity {
+    fn new(root: &Path) -> Self {
+        RootIdentity {
+            canonical_path: canonical_root(root),
+            inode: inode(root),
+        }
+    }
+
+    fn is_identical_to(&self, other: &Self) -> bool {
+        let same_path =
+            self.canonical_path.is_some() && self.canonical_path == other.canonical_path;
+        let same_inode = self.inode.is_some() && self.inode == other.inode;
+        same_path || same_inode
+    }
+
+    fn is_descendant_of(&self, other: &Self) -> bool {
+        match (&self.canonical_path, &other.canonical_path) {
+            (Some(path), Some(ancestor)) => path != ancestor && path.starts_with(ancestor),
+            _ => false,
+        }
+    }
+}
+
+/// Absolute path of a root without symbolic links, except for the root itself which is
+/// measured as-is rather than followed.
+pub fn canonical_root(root: &Path) -> Option<PathBuf> {
+    match (root.parent(), root.file_name()) {
+        (Some(parent), Some(name)) if !parent.as_os_str().is_empty() => {
+            canonicalize(parent).ok().map(|parent| parent.join(name))
+        }
+        (None, _) | (Some(_), None) => canonicalize(root).ok(),
+        (Some(_), Some(name)) => canonicalize(".").ok().map(|parent| parent.join(name)),
+    }
+}
+
+/// Device and inode of an item (POSIX only).
+#[cfg(unix)]
+fn inode(path: &Path) -> Option<(u64, u64)> {
+    symlink_metadata(path)
+        .ok()
+        .map(|stats| (stats.dev(), stats.ino()))
+}
+
+/// Device and inode of an item (POSIX only).
+#[cfg(not(unix))]
+fn inode(_: &Path) -> Option<(u64, u64)> {
+    None
+}
+
+/// Drop the roots that are inside other roots or identical to earlier roots, so that no item
+/// is counted twice.
+///
+/// Roots that cannot be resolved are kept, their errors are reported when they are scanned.
+pub fn remove_overlapping_roots(roots: &[PathBuf]) -> (Vec<PathBuf>, Vec<OverlappingRoot>) {
+    let identities: Vec<_> = roots.iter().map(|root| RootIdentity::new(root)).collect();
+    let overlap = |index: usize| {
+        let identity = &identities[index];
+        identities
+            .iter()
+            .enumerate()
+            .filter(|(other_index, _)| *other_index != index)
+            .find_map(|(other_index, other)| {
+                if other_index < index && identity.is_identical_to(other) {
+                    Some((OverlapKind::Identical, other_index))
+                } else if identity.is_descendant_of(other) {
+                    Some((OverlapKind::Descendant, other_index))
+                } else {
+                    None
+                }
+            })
+    };
+
+    let mut kept = Vec::with_capacity(roots.len());
+    let mut dropped = Vec::new();
+    for (index, root) in roots.iter().enumerate() {
+        match overlap(index) {
+            None => kept.push(root.clone()),
+            Some((kind, other_index)) => dropped.push(OverlappingRoot {
+                root: root.clone(),
+                kind,
+                covering_root: roots[other_index].clone(),
+            }),
+        }
+    }
+    (kept, dropped)
+}
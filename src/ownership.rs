@@ -0,0 +1,248 @@
+use crate::{
+    data_tree::DataTree,
+    os_string_display::OsStringDisplay,
+    size::{Owned, Owner, Size},
+};
+use rayon::prelude::*;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use strum::{AsRefStr, EnumString, EnumVariantNames};
+
+/// Which owner of the items to group disk usage by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AsRefStr, EnumString, EnumVariantNames)]
+pub enum OwnerKind {
+    /// Group by the users that own the items.
+    #[strum(serialize = "user")]
+    User,
+    /// Group by the groups that own the items.
+    #[strum(serialize = "group")]
+    Group,
+}
+
+impl OwnerKind {
+    /// Get the identifier of the owner of this kind.
+    pub const fn id(self, owner: Owner) -> u32 {
+        match self {
+            OwnerKind::User => owner.uid,
+            OwnerKind::Group => owner.gid,
+        }
+    }
+}
+
+/// Names of users and groups by their identifiers.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct AccountNames {
+    /// Names of users by their identifiers.
+    pub users: HashMap<u32, String>,
+    /// Names of groups by their identifiers.
+    pub groups: HashMap<u32, String>,
+}
+
+impl AccountNames {
+    /// Look up the names of the owners of the given kind through the name service switch,
+    /// which also covers the accounts of network directories such as LDAP.
+    ///
+    /// Identifiers that cannot be resolved are left out, they are then displayed as numbers.
+    pub fn lookup(kind: OwnerKind, ids: impl IntoIterator<Item = u32>) -> Self {
+        let mut names = AccountNames::default();
+        let table = match kind {
+            OwnerKind::User => &mut names.users,
+            OwnerKind::Group => &mut names.groups,
+        };
+        table.extend(
+            ids.into_iter()
+                .filter_map(|id| lookup_name(kind, id).map(|name| (id, name))),
+        );
+        names
+    }
+
+    /// Get the name of an owner of the given kind, or its identifier if it has no name.
+    pub fn name(&self, kind: OwnerKind, id: u32) -> String {
+        let names = match kind {
+            OwnerKind::User => &self.users,
+            OwnerKind::Group => &self.groups,
+        };
+        names.get(&id).cloned().unwrap_or_else(|| id.to_string())
+    }
+}
+
+/// Look up the name of a user or a group with `getpwuid_r` or `getgrgid_r`.
+#[cfg(unix)]
+fn lookup_name(kind: OwnerKind, id: u32) -> Option<String> {
+    use libc::{c_char, c_int, getgrgid_r, getpwuid_r, group, passwd, ERANGE};
+    use std::{ffi::CStr, mem::MaybeUninit, ptr::null_mut};
+
+    /// Largest buffer to try before giving up on an entry.
+    const MAX_BUFFER_SIZE: usize = 1 << 20;
+
+    /// Call a reentrant lookup function with growing buffers until the entry fits.
+    fn call<Entry>(
+        lookup: impl Fn(*mut Entry, &mut [c_char], *mut *mut Entry) -> c_int,
+        name: impl Fn(&Entry) -> *mut c_char,
+    ) -> Option<String> {
+        let mut buffer: Vec<c_char> = vec![0; 1024];
+        loop {
+            let mut entry = MaybeUninit::<Entry>::uninit();
+            let mut result = null_mut();
+            match lookup(entry.as_mut_ptr(), &mut buffer, &mut result) {
+                ERANGE if buffer.len() < MAX_BUFFER_SIZE => {
+                    let size = buffer.len() * 2;
+                    buffer.resize(size, 0);
+                }
+                0 if !result.is_null() => {
+                    // SAFETY: a successful lookup initializes the entry, whose strings are
+                    // NUL-terminated and stored in the buffer that is still alive.
+                    let name = unsafe { CStr::from_ptr(name(&*result)) };
+                    return Some(name.to_string_lossy().into_owned());
+                }
+                _ => return None,
+            }
+        }
+    }
+
+    match kind {
+        OwnerKind::User => call(
+            // SAFETY: the pointers are valid and the length is that of the buffer.
+            |entry, buffer, result| unsafe {
+                getpwuid_r(id, entry, buffer.as_mut_ptr(), buffer.len(), result)
+            },
+            |entry: &passwd| entry.pw_name,
+        ),
+        OwnerKind::Group => call(
+            // SAFETY: the pointers are valid and the length is that of the buffer.
+            |entry, buffer, result| unsafe {
+                getgrgid_r(id, entry, buffer.as_mut_ptr(), buffer.len(), result)
+            },
+            |entry: &group| entry.gr_name,
+        ),
+    }
+}
+
+/// Names of owners are only available on POSIX systems.
+#[cfg(not(unix))]
+fn lookup_name(_: OwnerKind, _: u32) -> Option<String> {
+    None
+}
+
+/// Collect the identifiers of the owners of the given kind of all items of a tree.
+pub fn owner_ids<Data>(
+    tree: &DataTree<OsStringDisplay, Owned<Data>>,
+    kind: OwnerKind,
+) -> BTreeSet<u32>
+where
+    Owned<Data>: Size,
+{
+    fn collect<Data>(
+        tree: &DataTree<OsStringDisplay, Owned<Data>>,
+        kind: OwnerKind,
+        ids: &mut BTreeSet<u32>,
+    ) where
+        Owned<Data>: Size,
+    {
+        ids.extend(tree.data().owner.map(|owner| kind.id(owner)));
+        for child in tree.children() {
+            collect(child, kind, ids);
+        }
+    }
+
+    let mut ids = BTreeSet::new();
+    collect(tree, kind, &mut ids);
+    ids
+}
+
+/// Regroup a tree by the owners of its items.
+///
+/// The children of the returned root are the owners, each of which holds the parts of
+/// the children of the original root that contain items it owns. Nodes without owners
+/// (such as the node that holds multiple roots) only contribute their descendants.
+///
+/// A part keeps the owner of its node only if the node belongs to the owner of the part,
+/// the nodes of the owners and the parts of the nodes of other owners have no owners.
+pub fn group_by_owner<Data>(
+    tree: &DataTree<OsStringDisplay, Owned<Data>>,
+    kind: OwnerKind,
+    names: &AccountNames,
+    post_process_children: impl Fn(&mut Vec<DataTree<OsStringDisplay, Owned<Data>>>) + Copy + Sync,
+) -> DataTree<OsStringDisplay, Owned<Data>>
+where
+    Data: Default + Copy + Send + Sync,
+    Owned<Data>: Size + Send + Sync,
+{
+    /// Own size and children of the part of a node that belongs to an owner.
+    type Part<Data> = (Data, Vec<DataTree<OsStringDisplay, Owned<Data>>>);
+
+    /// Split a tree into the parts of its owners in a single traversal.
+    fn split<Data>(
+        tree: &DataTree<OsStringDisplay, Owned<Data>>,
+        kind: OwnerKind,
+        post_process_children: impl Fn(&mut Vec<DataTree<OsStringDisplay, Owned<Data>>>) + Copy + Sync,
+    ) -> BTreeMap<u32, Part<Data>>
+    where
+        Data: Default + Copy + Send + Sync,
+        Owned<Data>: Size + Send + Sync,
+    {
+        let child_parts: Vec<_> = tree
+            .children()
+            .par_iter()
+            .map(|child| {
+                split(child, kind, post_process_children)
+                    .into_iter()
+                    .map(|(id, (own_size, children))| {
+                        (id, node(child, kind, id, own_size, children))
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        let mut parts: BTreeMap<u32, Part<Data>> = BTreeMap::new();
+        for (id, child) in child_parts.into_iter().flatten() {
+            parts.entry(id).or_default().1.push(child);
+        }
+        let data = tree.data();
+        if let Some(owner) = data.owner {
+            parts.entry(kind.id(owner)).or_default().0 = data.own_size;
+        }
+        for (_, children) in parts.values_mut() {
+            post_process_children(children);
+        }
+        parts
+    }
+
+    /// Create the part of a node that belongs to the owner whose identifier is `id`.
+    fn node<Data>(
+        tree: &DataTree<OsStringDisplay, Owned<Data>>,
+        kind: OwnerKind,
+        id: u32,
+        own_size: Data,
+        children: Vec<DataTree<OsStringDisplay, Owned<Data>>>,
+    ) -> DataTree<OsStringDisplay, Owned<Data>>
+    where
+        Data: Copy,
+        Owned<Data>: Size,
+    {
+        let data = Owned {
+            size: own_size,
+            own_size,
+            owner: tree.data().owner.filter(|owner| kind.id(*owner) == id),
+        };
+        if tree.is_dir() {
+            DataTree::dir(tree.name().clone(), data, children)
+        } else {
+            DataTree::file(tree.name().clone(), data)
+        }
+    }
+
+    let mut children: Vec<_> = split(tree, kind, post_process_children)
+        .into_iter()
+        .map(|(id, (own_size, children))| {
+            let name = OsStringDisplay::os_string_from(names.name(kind, id));
+            let data = Owned {
+                size: own_size,
+                own_size,
+                owner: None,
+            };
+            DataTree::dir(name, data, children)
+        })
+        .collect();
+    post_process_children(&mut children);
+
+    DataTree::dir(tree.name().clone(), Owned::default(), children)
+}
@@ -0,0 +1,123 @@
+use crate::{data_tree::DataTree, reporter::ProgressReport, size::Size};
+use std::{
+    fmt::{Display, Error, Formatter},
+    num::NonZeroUsize,
+    time::Duration,
+};
+
+/// Metrics of a [`DataTree`] in the text exposition format of Prometheus, which the textfile
+/// collector of node_exporter can read.
+///
+/// Every node within `max_depth` is a sample of `dirt_<unit>` (e.g. `dirt_bytes` or
+/// `dirt_blocks`) labeled by its path and the measured quantity. The statistics of the scan
+/// are only written when they are known.
+#[derive(Debug)]
+pub struct PrometheusMetrics<'a, Name, Data>
+where
+    Name: Display,
+    Data: Size,
+{
+    /// The tree to measure.
+    pub data_tree: &'a DataTree<Name, Data>,
+    /// Maximum number of levels that should be measured.
+    pub max_depth: NonZeroUsize,
+    /// Value of the `quantity` label.
+    pub quantity: &'a str,
+    /// Unit of the measured quantity, which names the metric of the nodes.
+    pub unit: &'a str,
+    /// Numbers of scanned items and of errors.
+    pub progress: Option<ProgressReport<Data>>,
+    /// Time taken to scan the tree.
+    pub scan_duration: Option<Duration>,
+}
+
+impl<'a, Name, Data> Display for PrometheusMetrics<'a, Name, Data>
+where
+    Name: Display,
+    Data: Size + Into<u64>,
+{
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> Result<(), Error> {
+        fn write_node<Name, Data>(
+            formatter: &mut Formatter<'_>,
+            node: &DataTree<Name, Data>,
+            parent_path: Option<&str>,
+            metric: &str,
+            quantity: &str,
+            remaining_depth: usize,
+        ) -> Result<(), Error>
+        where
+            Name: Display,
+            Data: Size + Into<u64>,
+        {
+            let name = node.name().to_string();
+            let path = match parent_path {
+                None => name,
+                Some(parent_path) if parent_path.ends_with('/') => {
+                    format!("{}{}", parent_path, name)
+                }
+                Some(parent_path) => format!("{}/{}", parent_path, name),
+            };
+            let size: u64 = node.data().into();
+            writeln!(
+                formatter,
+                "{}{{path=\"{}\",quantity=\"{}\"}} {}",
+                metric,
+                escape(&path),
+                escape(quantity),
+                size,
+            )?;
+            if remaining_depth > 1 {
+                for child in node.children() {
+                    write_node(
+                        formatter,
+                        child,
+                        Some(&path),
+                        metric,
+                        quantity,
+                        remaining_depth - 1,
+                    )?;
+                }
+            }
+            Ok(())
+        }
+
+        let metric = format!("dirt_{}", self.unit);
+        write_header(formatter, &metric, "Measured quantity of a path.")?;
+        write_node(
+            formatter,
+            self.data_tree,
+            None,
+            &metric,
+            self.quantity,
+            self.max_depth.get(),
+        )?;
+        if let Some(scan_duration) = self.scan_duration {
+            let name = "dirt_scan_duration_seconds";
+            write_header(formatter, name, "Duration of the scan.")?;
+            writeln!(formatter, "{} {}", name, scan_duration.as_secs_f64())?;
+        }
+        if let Some(ProgressReport { items, errors, .. }) = self.progress {
+            let name = "dirt_scanned_items";
+            write_header(formatter, name, "Number of scanned items.")?;
+            writeln!(formatter, "{} {}", name, items)?;
+            let name = "dirt_scan_errors";
+            write_header(formatter, name, "Number of errors of the scan.")?;
+            writeln!(formatter, "{} {}", name, errors)?;
+        }
+        Ok(())
+    }
+}
+
+/// Write the `HELP` and the `TYPE` lines of a gauge.
+fn write_header(formatter: &mut Formatter<'_>, name: &str, help: &str) -> Result<(), Error> {
+    writeln!(formatter, "# HELP {} {}", name, help)?;
+    writeln!(formatter, "# TYPE {} gauge", name)
+}
+
+/// Escape a label value.
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
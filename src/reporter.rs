@@ -24,6 +24,10 @@ pub trait ParallelReporter<Data: Size>: Reporter<Data> {
     type DestructionError;
     /// Stop all threads.
     fn destroy(self) -> Result<(), Self::DestructionError>;
+    /// Progress that has been made so far, if the reporter keeps track of it.
+    fn progress(&self) -> Option<ProgressReport<Data>> {
+        None
+    }
 }
 
 impl<Data, Target> Reporter<Data> for &Target
@@ -116,4 +116,7 @@ where
         self.stop_progress_reporter();
         self.progress_reporter_handle.join()
     }
+    fn progress(&self) -> Option<ProgressReport<Data>> {
+        *self.progress.read().ok()?
+    }
 }
@@ -13,6 +13,9 @@ pub struct ProgressReport<Data: Size> {
 }
 
 impl<Data: Size + Into<u64>> ProgressReport<Data> {
+    /// Do nothing.
+    pub const SILENT: fn(Self) = |_| {};
+
     /// Print progress to stderr.
     pub const TEXT: fn(Self) = |report| {
         let ProgressReport {
@@ -6,4 +6,63 @@ pub enum RuntimeError {
     /// Failed to get terminal width.
     #[display(fmt = "Failed to get terminal width")]
     TerminalWidthInferenceFailure,
+    /// Ownership of files is not available on the current platform.
+    #[display(fmt = "Grouping by owner is only available on POSIX")]
+    OwnershipUnsupported,
+    /// Live updates are not available on the current platform.
+    #[display(fmt = "Live updates are only available on Linux")]
+    LiveUpdatesUnsupported,
+    /// Only the text visualization can be redrawn by `--watch`.
+    #[display(fmt = "Watching is only available with --output-format=text")]
+    WatchUnsupported,
+    /// Failed to start watching the filesystem.
+    #[display(fmt = "Failed to watch the filesystem for changes")]
+    FilesystemWatchFailure,
+    /// The scan cache cannot store the measured data.
+    #[display(fmt = "The cache does not support ages, owners or cold bytes")]
+    CacheUnsupported,
+    /// Failed to read or write the history.
+    #[display(fmt = "Failed to access the history")]
+    HistoryFailure,
+    /// The history has too few snapshots to show a trend.
+    #[display(fmt = "At least two snapshots are required to show a trend")]
+    InsufficientHistory,
+    /// Only the text visualization can show the trend of `--history-trend`.
+    #[display(fmt = "The trend is only available with --output-format=text")]
+    TrendUnsupported,
+    /// Capacities of filesystems are not available on the current platform.
+    #[display(fmt = "Filesystem capacity is only available on Linux")]
+    CapacityUnsupported,
+    /// The measured quantity cannot be compared to the capacity of a filesystem.
+    #[display(fmt = "Only sizes in bytes can be relative to the filesystem capacity")]
+    CapacityIncomparable,
+    /// Sorting by modification time was requested for data that carry no modification times.
+    #[display(fmt = "Sorting by mtime is unavailable with owners or imported hierarchies")]
+    ModificationTimeUnavailable,
+    /// Failed to read the list of files of `--files-from`.
+    #[display(fmt = "Failed to read the list of files")]
+    FileListFailure,
+    /// Failed to read or parse the hierarchy of `--weighted-input`.
+    #[display(fmt = "Failed to read the weighted hierarchy")]
+    WeightedInputFailure,
+    /// Failed to read the entries of `--archive`.
+    #[display(fmt = "Failed to read the archive")]
+    ArchiveFailure,
+    /// Failed to read, parse or write a dump of ncdu.
+    #[display(fmt = "Failed to access the ncdu dump")]
+    NcduFailure,
+    /// The measured quantity is not recorded in dumps of ncdu.
+    #[display(
+        fmt = "Dumps of ncdu only record apparent sizes (--quantity=len) and disk usage (--quantity=blocks)"
+    )]
+    NcduQuantityUnsupported,
+    /// A dump of ncdu can only hold one root.
+    #[display(fmt = "Only one directory can be written to an ncdu dump")]
+    NcduMultipleRoots,
+    /// Failed to read or parse the output of `du` of `--du-import`.
+    #[display(fmt = "Failed to read the output of du")]
+    DuImportFailure,
+    /// Failed to write the file of `--output-file`.
+    #[display(fmt = "Failed to write the output file")]
+    OutputFileFailure,
 }
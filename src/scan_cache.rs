@@ -0,0 +1,242 @@
+pub mod builder;
+
+pub use builder::CachedFsTreeBuilder;
+
+use crate::{
+    args::Fraction,
+    os_string_display::{
+        escape::{escape, unescape},
+        OsStringDisplay,
+    },
+};
+use derive_more::Display;
+use std::{
+    collections::HashMap,
+    fs::{read_to_string, rename, write, Metadata},
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// First word of a cache file.
+const MAGIC: &str = "dirt-scan-cache-v1";
+
+/// State of a directory that changes whenever an entry is added to, removed from or
+/// renamed in the directory.
+///
+/// **Staleness:** Modifying the content of a file or of a subdirectory does not change
+/// the key of the directory, so the key can only vouch for the list of entries.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DirectoryKey {
+    /// Device that holds the directory (POSIX only, `0` elsewhere).
+    pub device: u64,
+    /// Inode of the directory (POSIX only, `0` elsewhere).
+    pub inode: u64,
+    /// Modification time in nanoseconds since the Unix epoch.
+    pub modified: i128,
+    /// Status change time in nanoseconds since the Unix epoch (POSIX only, `0` elsewhere).
+    pub changed: i128,
+}
+
+impl DirectoryKey {
+    /// Extract the key of a directory from its [`Metadata`].
+    #[cfg(unix)]
+    pub fn from_metadata(metadata: &Metadata) -> Self {
+        use std::os::unix::prelude::MetadataExt;
+        let nanoseconds = |seconds: i64, nanoseconds: i64| {
+            i128::from(seconds) * 1_000_000_000 + i128::from(nanoseconds)
+        };
+        DirectoryKey {
+            device: metadata.dev(),
+            inode: metadata.ino(),
+            modified: nanoseconds(metadata.mtime(), metadata.mtime_nsec()),
+            changed: nanoseconds(metadata.ctime(), metadata.ctime_nsec()),
+        }
+    }
+
+    /// Extract the key of a directory from its [`Metadata`].
+    #[cfg(not(unix))]
+    pub fn from_metadata(metadata: &Metadata) -> Self {
+        let modified = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| modified.duration_since(std::time::UNIX_EPOCH).ok())
+            .map_or(0, |duration| duration.as_nanos() as i128);
+        DirectoryKey {
+            modified,
+            ..DirectoryKey::default()
+        }
+    }
+}
+
+/// Cached scan result of a directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedDirectory {
+    /// Key of the directory at the time of the scan.
+    pub key: DirectoryKey,
+    /// Names and measured quantities of the entries that are not directories.
+    pub files: Vec<(OsStringDisplay, u64)>,
+    /// Names of the entries that are directories.
+    pub subdirectories: Vec<OsStringDisplay>,
+}
+
+/// Scan results of directories from a previous run, keyed by their paths.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ScanCache {
+    /// Identifies what was measured, results of a different label are never reused.
+    pub label: String,
+    /// The cached directories.
+    pub directories: HashMap<PathBuf, CachedDirectory>,
+}
+
+/// Where and how to cache the scans of a run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CacheSettings {
+    /// The cache file.
+    pub file: PathBuf,
+    /// Identifies what is measured.
+    pub label: String,
+    /// Approximate ratio of the files of each unchanged directory to measure anew.
+    pub sample_ratio: Fraction,
+}
+
+/// Error that occurs when parsing a [`ScanCache`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum ParseError {
+    /// The text does not start with the expected header.
+    #[display(fmt = "Not a scan cache")]
+    InvalidHeader,
+    /// A line is malformed.
+    #[display(fmt = "Invalid line {}", _0)]
+    InvalidLine(usize),
+}
+
+impl ScanCache {
+    /// Create an empty cache.
+    pub fn new(label: String) -> Self {
+        ScanCache {
+            label,
+            directories: HashMap::new(),
+        }
+    }
+
+    /// Serialize the cache into a line-based text format.
+    pub fn to_text(&self) -> String {
+        let mut text = format!("{} {}\n", MAGIC, escape(self.label.as_ref()));
+        let mut directories: Vec<_> = self.directories.iter().collect();
+        directories.sort_by(|(left, _), (right, _)| left.cmp(right));
+        for (path, directory) in directories {
+            let DirectoryKey {
+                device,
+                inode,
+                modified,
+                changed,
+            } = directory.key;
+            text += &format!(
+                "D {} {} {} {} {}\n",
+                device,
+                inode,
+                modified,
+                changed,
+                escape(path.as_os_str()),
+            );
+            for (name, data) in &directory.files {
+                text += &format!("F {} {}\n", data, escape(name.as_os_str()));
+            }
+            for name in &directory.subdirectories {
+                text += &format!("S {}\n", escape(name.as_os_str()));
+            }
+        }
+        text
+    }
+
+    /// Parse the text created by [`to_text`](Self::to_text).
+    pub fn from_text(text: &str) -> Result<Self, ParseError> {
+        let mut lines = text.lines();
+        let label = lines
+            .next()
+            .and_then(|header| header.strip_prefix(MAGIC)?.strip_prefix(' '))
+            .and_then(unescape)
+            .and_then(|label| label.into_string().ok())
+            .ok_or(ParseError::InvalidHeader)?;
+
+        let mut directories: Vec<(PathBuf, CachedDirectory)> = Vec::new();
+        for (index, line) in lines.enumerate() {
+            let error = ParseError::InvalidLine(index + 2);
+            let words: Vec<&str> = line.split(' ').collect();
+            let name = |word: &str| unescape(word).map(OsStringDisplay::from).ok_or(error);
+            match (words.as_slice(), directories.last_mut()) {
+                (["D", device, inode, modified, changed, path], _) => {
+                    let key = DirectoryKey {
+                        device: parse_number(device, error)?,
+                        inode: parse_number(inode, error)?,
+                        modified: parse_number(modified, error)?,
+                        changed: parse_number(changed, error)?,
+                    };
+                    let path = PathBuf::from(unescape(path).ok_or(error)?);
+                    let directory = CachedDirectory {
+                        key,
+                        files: Vec::new(),
+                        subdirectories: Vec::new(),
+                    };
+                    directories.push((path, directory));
+                }
+                (["F", data, file_name], Some((_, directory))) => {
+                    let data = parse_number(data, error)?;
+                    directory.files.push((name(file_name)?, data));
+                }
+                (["S", subdirectory_name], Some((_, directory))) => {
+                    directory.subdirectories.push(name(subdirectory_name)?);
+                }
+                _ => return Err(error),
+            }
+        }
+
+        Ok(ScanCache {
+            label,
+            directories: directories.into_iter().collect(),
+        })
+    }
+
+    /// Load the cache from a file.
+    ///
+    /// A missing or unreadable file, or a file of a different label, results in an empty
+    /// cache, so that everything is scanned.
+    pub fn load(path: &Path, label: String) -> Self {
+        let cache = match read_to_string(path) {
+            Ok(text) => ScanCache::from_text(&text).unwrap_or_else(|error| {
+                eprintln!("[warning] Ignored the cache {:?}: {}", path, error);
+                ScanCache::default()
+            }),
+            Err(error) if error.kind() == ErrorKind::NotFound => ScanCache::default(),
+            Err(error) => {
+                eprintln!("[warning] Failed to read the cache {:?}: {}", path, error);
+                ScanCache::default()
+            }
+        };
+        if cache.label == label {
+            cache
+        } else {
+            ScanCache::new(label)
+        }
+    }
+
+    /// Save the cache to a file, replacing the previous file atomically.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut temporary_path = path.as_os_str().to_owned();
+        temporary_path.push(".tmp");
+        write(&temporary_path, self.to_text())?;
+        rename(&temporary_path, path)
+    }
+
+    /// Replace the directories under `root` with the directories of a new scan of `root`.
+    pub fn merge(&mut self, root: &Path, scanned: ScanCache) {
+        self.directories.retain(|path, _| !path.starts_with(root));
+        self.directories.extend(scanned.directories);
+    }
+}
+
+/// Parse a number of a cache line, returning `error` on failure.
+fn parse_number<Number: FromStr>(word: &str, error: ParseError) -> Result<Number, ParseError> {
+    word.parse().map_err(|_| error)
+}
@@ -0,0 +1,219 @@
+use super::{CachedDirectory, DirectoryKey, ScanCache};
+use crate::{
+    args::Fraction,
+    data_tree::DataTree,
+    os_string_display::OsStringDisplay,
+    reporter::{
+        error_report::Operation::{self, *},
+        ErrorReport, Event, Reporter,
+    },
+    size::Size,
+};
+use pipe_trait::Pipe;
+use rayon::prelude::*;
+use std::{
+    fs::{read_dir, symlink_metadata, Metadata},
+    io::Error,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+/// Build a [`DataTree`] from a directory tree like [`FsTreeBuilder`](crate::fs_tree_builder::FsTreeBuilder),
+/// but reuse the entries of the directories that are unchanged since they were cached.
+///
+/// A directory whose [key](DirectoryKey) matches its cache entry is neither read nor are
+/// its files measured, except for a sample of the files: since a modification of the
+/// content of a file does not change the key of its directory, the sampled files are
+/// measured anew, and the whole directory is rescanned if any of them differs from
+/// the cache. The sample rotates between runs so that every file is eventually checked.
+#[derive(Debug)]
+pub struct CachedFsTreeBuilder<'a, Data, GetData, Report, PostProcessChildren>
+where
+    Data: Size + From<u64> + Into<u64> + Send + Sync,
+    GetData: Fn(&Metadata) -> Data + Sync,
+    Report: Reporter<Data> + Sync,
+    PostProcessChildren: Fn(&mut Vec<DataTree<OsStringDisplay, Data>>) + Copy + Send + Sync,
+{
+    /// Root of the directory tree.
+    pub root: PathBuf,
+    /// Returns size of an item.
+    pub get_data: GetData,
+    /// Reports progress to external system.
+    pub reporter: Report,
+    /// Processes lists of children after forming.
+    pub post_process_children: PostProcessChildren,
+    /// Results of the previous scan.
+    pub cache: &'a ScanCache,
+    /// Approximate ratio of the files of each unchanged directory to measure anew.
+    pub sample_ratio: Fraction,
+}
+
+impl<'a, Data, GetData, Report, PostProcessChildren>
+    CachedFsTreeBuilder<'a, Data, GetData, Report, PostProcessChildren>
+where
+    Data: Size + From<u64> + Into<u64> + Send + Sync,
+    GetData: Fn(&Metadata) -> Data + Sync,
+    Report: Reporter<Data> + Sync,
+    PostProcessChildren: Fn(&mut Vec<DataTree<OsStringDisplay, Data>>) + Copy + Send + Sync,
+{
+    /// Build the tree, and the cache of its directories for the next run.
+    pub fn build(self) -> (DataTree<OsStringDisplay, Data>, ScanCache) {
+        let name = self.root.file_name().map_or_else(
+            || ".".pipe(OsStringDisplay::os_string_from),
+            OsStringDisplay::os_string_from,
+        );
+        let sample_offset = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs());
+        let (tree, directories) = self.scan(self.root.clone(), name, sample_offset);
+        let cache = ScanCache {
+            label: self.cache.label.clone(),
+            directories: directories.into_iter().collect(),
+        };
+        (tree, cache)
+    }
+
+    /// Build the tree of a path, and collect the cache entries of its directories.
+    fn scan(
+        &self,
+        path: PathBuf,
+        name: OsStringDisplay,
+        sample_offset: u64,
+    ) -> (
+        DataTree<OsStringDisplay, Data>,
+        Vec<(PathBuf, CachedDirectory)>,
+    ) {
+        let stats = match symlink_metadata(&path) {
+            Err(error) => {
+                self.report_error(SymlinkMetadata, &path, error);
+                return (DataTree::file(name, Data::default()), Vec::new());
+            }
+            Ok(stats) => stats,
+        };
+
+        let data = (self.get_data)(&stats);
+        self.reporter.report(Event::ReceiveData(data));
+
+        if !stats.is_dir() {
+            return (DataTree::file(name, data), Vec::new());
+        }
+
+        let key = DirectoryKey::from_metadata(&stats);
+        let cached = self
+            .cache
+            .directories
+            .get(&path)
+            .filter(|cached| cached.key == key)
+            .filter(|cached| self.check_samples(&path, cached, sample_offset));
+        let directory = match cached {
+            Some(cached) => {
+                for (_, data) in &cached.files {
+                    let data: Data = (*data).into();
+                    self.reporter.report(Event::ReceiveData(data));
+                }
+                cached.clone()
+            }
+            None => match self.read_directory(&path, key) {
+                Some(directory) => directory,
+                None => return (DataTree::dir(name, data, Vec::new()), Vec::new()),
+            },
+        };
+
+        let (subtrees, descendants): (Vec<_>, Vec<_>) = directory
+            .subdirectories
+            .par_iter()
+            .map(|child_name| {
+                let child_path = path.join(child_name.as_os_str());
+                self.scan(child_path, child_name.clone(), sample_offset)
+            })
+            .unzip();
+
+        let mut children: Vec<_> = directory
+            .files
+            .iter()
+            .map(|(file_name, data)| {
+                let data: Data = (*data).into();
+                DataTree::file(file_name.clone(), data)
+            })
+            .chain(subtrees)
+            .collect();
+        (self.post_process_children)(&mut children);
+
+        let mut directories: Vec<_> = descendants.into_iter().flatten().collect();
+        directories.push((path, directory));
+        (DataTree::dir(name, data, children), directories)
+    }
+
+    /// Check whether the sampled files of a cached directory are still measured the same.
+    fn check_samples(&self, path: &Path, cached: &CachedDirectory, sample_offset: u64) -> bool {
+        let ratio: f32 = self.sample_ratio.into();
+        if ratio <= 0.0 {
+            return true;
+        }
+        let stride = (1.0 / ratio).round().max(1.0) as u64;
+        cached
+            .files
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| (*index as u64 + sample_offset) % stride == 0)
+            .all(|(_, (file_name, data))| {
+                symlink_metadata(path.join(file_name.as_os_str())).map_or(false, |stats| {
+                    let measured: u64 = (self.get_data)(&stats).into();
+                    measured == *data
+                })
+            })
+    }
+
+    /// List and measure the entries of a directory.
+    fn read_directory(&self, path: &Path, key: DirectoryKey) -> Option<CachedDirectory> {
+        let entries = match read_dir(path) {
+            Err(error) => {
+                self.report_error(ReadDirectory, path, error);
+                return None;
+            }
+            Ok(entries) => entries,
+        };
+
+        let mut directory = CachedDirectory {
+            key,
+            files: Vec::new(),
+            subdirectories: Vec::new(),
+        };
+        for entry in entries {
+            let (entry, file_type) = match entry.and_then(|entry| {
+                let file_type = entry.file_type()?;
+                Ok((entry, file_type))
+            }) {
+                Err(error) => {
+                    self.report_error(AccessEntry, path, error);
+                    continue;
+                }
+                Ok(entry) => entry,
+            };
+            let file_name = OsStringDisplay::from(entry.file_name());
+            if file_type.is_dir() {
+                directory.subdirectories.push(file_name);
+                continue;
+            }
+            let entry_path = entry.path();
+            match symlink_metadata(&entry_path) {
+                Err(error) => self.report_error(SymlinkMetadata, &entry_path, error),
+                Ok(stats) => {
+                    let data = (self.get_data)(&stats);
+                    self.reporter.report(Event::ReceiveData(data));
+                    directory.files.push((file_name, data.into()));
+                }
+            }
+        }
+        Some(directory)
+    }
+
+    /// Report a filesystem error.
+    fn report_error(&self, operation: Operation, path: &Path, error: Error) {
+        self.reporter.report(Event::EncounterError(ErrorReport {
+            operation,
+            path,
+            error,
+        }));
+    }
+}
@@ -0,0 +1,94 @@
+use crate::{data_tree::DataTree, os_string_display::OsStringDisplay, size::Size};
+use pipe_trait::Pipe;
+use std::{ffi::OsString, path::PathBuf};
+
+/// Remaining components of the path of a root, along with the tree of the root.
+type Entry<Data> = (Vec<OsString>, DataTree<OsStringDisplay, Data>);
+
+/// Nest the trees of the roots under the directories of their paths that follow the longest
+/// common prefix of the paths, and name the top node after the prefix (or `fallback_name` if
+/// the paths have nothing in common).
+///
+/// The intermediate directories are not measured, so they only hold the totals of their
+/// descendants.
+pub fn nest_by_shared_prefix<Data, PostProcessChildren>(
+    roots: Vec<(PathBuf, DataTree<OsStringDisplay, Data>)>,
+    fallback_name: OsStringDisplay,
+    post_process_children: PostProcessChildren,
+) -> DataTree<OsStringDisplay, Data>
+where
+    Data: Size,
+    PostProcessChildren: Fn(&mut Vec<DataTree<OsStringDisplay, Data>>) + Copy,
+{
+    let entries: Vec<Entry<Data>> = roots
+        .into_iter()
+        .map(|(path, tree)| {
+            let components = path
+                .components()
+                .map(|component| component.as_os_str().to_os_string())
+                .collect();
+            (components, tree)
+        })
+        .collect();
+
+    // Every root keeps at least one component of its own.
+    let shortest = entries.iter().map(|(components, _)| components.len()).min();
+    let first = entries.first().map(|(components, _)| components.as_slice());
+    let prefix_len = match (shortest, first) {
+        (Some(shortest), Some(first)) => (0..shortest.saturating_sub(1))
+            .take_while(|index| {
+                entries
+                    .iter()
+                    .all(|(components, _)| components[*index] == first[*index])
+            })
+            .count(),
+        _ => 0,
+    };
+
+    let name = match first {
+        Some(first) if prefix_len > 0 => first[..prefix_len]
+            .iter()
+            .collect::<PathBuf>()
+            .pipe(OsStringDisplay::os_string_from),
+        _ => fallback_name,
+    };
+
+    let entries = entries
+        .into_iter()
+        .map(|(components, tree)| (components[prefix_len..].to_vec(), tree))
+        .collect();
+    let mut children = nest(entries, post_process_children);
+    post_process_children(&mut children);
+    DataTree::dir(name, Data::default(), children)
+}
+
+/// Group the entries by the first of their remaining components.
+fn nest<Data, PostProcessChildren>(
+    entries: Vec<Entry<Data>>,
+    post_process_children: PostProcessChildren,
+) -> Vec<DataTree<OsStringDisplay, Data>>
+where
+    Data: Size,
+    PostProcessChildren: Fn(&mut Vec<DataTree<OsStringDisplay, Data>>) + Copy,
+{
+    let mut children = Vec::new();
+    let mut groups: Vec<(OsString, Vec<Entry<Data>>)> = Vec::new();
+    for (mut components, tree) in entries {
+        if components.len() <= 1 {
+            children.push(tree);
+            continue;
+        }
+        let head = components.remove(0);
+        match groups.iter_mut().find(|(name, _)| *name == head) {
+            Some((_, group)) => group.push((components, tree)),
+            None => groups.push((head, vec![(components, tree)])),
+        }
+    }
+    for (name, group) in groups {
+        let mut grandchildren = nest(group, post_process_children);
+        post_process_children(&mut grandchildren);
+        let name = OsStringDisplay::from(name);
+        children.push(DataTree::dir(name, Data::default(), grandchildren));
+    }
+    children
+}
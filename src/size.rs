@@ -1,3 +1,11 @@
+pub mod changed;
+pub mod owned;
+pub mod timed;
+
+pub use changed::Changed;
+pub use owned::{Owned, Owner};
+pub use timed::{Timed, TimedFormat};
+
 use super::bytes_format::{self, BytesFormat};
 use derive_more::{Add, AddAssign, From, Into, Sum};
 use std::{
@@ -28,6 +36,11 @@ pub trait Size:
     type DisplayOutput: Display;
     /// Display the disk usage in a measurement system.
     fn display(self, input: Self::DisplayFormat) -> Self::DisplayOutput;
+    /// Extra columns to show after the size in the [`Visualizer`](crate::visualizer::Visualizer),
+    /// each of which is preceded by a space (none by default).
+    fn display_columns(self, _input: Self::DisplayFormat) -> String {
+        String::new()
+    }
 }
 
 macro_rules! newtype {
@@ -96,3 +109,11 @@ newtype!(
     Blocks = u64;
     display: (()) -> u64 = |blocks, ()| blocks.inner();
 );
+
+newtype!(
+    #[doc = "Number of bytes multiplied by the number of days since their last modification."]
+    ByteDays = u64;
+    display: (BytesFormat) -> bytes_format::ByteDaysOutput = |byte_days, format| {
+        format.format_byte_days(byte_days.into())
+    };
+);
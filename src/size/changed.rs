@@ -0,0 +1,105 @@
+use super::Size;
+use std::{
+    cmp::Ordering,
+    iter::Sum,
+    ops::{Add, AddAssign, Mul},
+};
+
+/// Width of the column that displays the change of a [`Changed`] value.
+pub const CHANGE_COLUMN_WIDTH: usize = "+999K".len();
+
+/// Disk usage statistic paired with the statistic of the same item in a previous
+/// measurement.
+///
+/// **NOTE:** When only one operand of an addition has a previous statistic, the
+/// missing one is treated as zero.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Changed<Data> {
+    /// The current disk usage statistic.
+    pub size: Data,
+    /// The previous disk usage statistic, if there was a previous measurement.
+    pub previous: Option<Data>,
+}
+
+impl<Data> Changed<Data> {
+    /// Pair a disk usage statistic with its previous value.
+    pub const fn new(size: Data, previous: Option<Data>) -> Self {
+        Changed { size, previous }
+    }
+}
+
+impl<Data: Add<Output = Data> + Default> Add for Changed<Data> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        let previous = match (self.previous, rhs.previous) {
+            (None, None) => None,
+            (left, right) => Some(left.unwrap_or_default() + right.unwrap_or_default()),
+        };
+        Changed {
+            size: self.size + rhs.size,
+            previous,
+        }
+    }
+}
+
+impl<Data: Add<Output = Data> + Default + Copy> AddAssign for Changed<Data> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<Data: Add<Output = Data> + Default> Sum for Changed<Data> {
+    fn sum<Iter: Iterator<Item = Self>>(iter: Iter) -> Self {
+        iter.fold(Changed::default(), Add::add)
+    }
+}
+
+impl<Data: Size + From<u64> + Into<u64>> Size for Changed<Data> {
+    type Inner = u64;
+    type DisplayFormat = Data::DisplayFormat;
+    type DisplayOutput = String;
+    fn display(self, format: Self::DisplayFormat) -> Self::DisplayOutput {
+        let size = self.size.display(format);
+        let previous = match self.previous {
+            None => return size.to_string(),
+            Some(previous) => previous.into(),
+        };
+        let current: u64 = self.size.into();
+        let display_change = |sign, change: u64| {
+            let change: Data = change.into();
+            format!("{}{}", sign, change.display(format))
+        };
+        let change = match current.cmp(&previous) {
+            Ordering::Greater => display_change('+', current - previous),
+            Ordering::Less => display_change('-', previous - current),
+            Ordering::Equal => String::new(),
+        };
+        format!("{:>width$} {}", change, size, width = CHANGE_COLUMN_WIDTH)
+    }
+    fn display_columns(self, format: Self::DisplayFormat) -> String {
+        self.size.display_columns(format)
+    }
+}
+
+impl<Data: Into<u64>> From<Changed<Data>> for u64 {
+    fn from(changed: Changed<Data>) -> Self {
+        changed.size.into()
+    }
+}
+
+impl<Data: From<u64>> From<u64> for Changed<Data> {
+    fn from(inner: u64) -> Self {
+        Changed::new(inner.into(), None)
+    }
+}
+
+impl<Data: From<u64> + Into<u64>> Mul<Changed<Data>> for u64 {
+    type Output = Changed<Data>;
+    fn mul(self, rhs: Changed<Data>) -> Self::Output {
+        let scale = |data: Data| (self * data.into()).into();
+        Changed {
+            size: scale(rhs.size),
+            previous: rhs.previous.map(scale),
+        }
+    }
+}
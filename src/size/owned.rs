@@ -0,0 +1,112 @@
+use super::{Blocks, ByteDays, Bytes, Size};
+use std::{
+    iter::Sum,
+    ops::{Add, AddAssign, Mul},
+};
+
+/// Numeric identifiers of the user and the group that own an item.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Owner {
+    /// User identifier.
+    pub uid: u32,
+    /// Group identifier.
+    pub gid: u32,
+}
+
+/// Disk usage statistic of an item and its descendants, paired with the part that
+/// belongs to the item itself and the owner of the item.
+///
+/// **NOTE:** Addition sums the total sizes but keeps the attribution of the left operand.
+/// Since [`DataTree::dir`](crate::data_tree::DataTree::dir) adds the sizes of the children
+/// to the size of the directory itself, the attribution of a directory stays its own, and
+/// nodes that are not items (such as the node that holds multiple roots) have no owner.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Owned<Data> {
+    /// Total size of the item and its descendants.
+    pub size: Data,
+    /// Size of the item itself.
+    pub own_size: Data,
+    /// Owner of the item itself, `None` if the node is not an item.
+    pub owner: Option<Owner>,
+}
+
+impl<Data: Copy> Owned<Data> {
+    /// Attribute a disk usage statistic of a single item to its owner.
+    pub fn new(size: Data, owner: Owner) -> Self {
+        Owned {
+            size,
+            own_size: size,
+            owner: Some(owner),
+        }
+    }
+}
+
+impl<Data: Add<Output = Data>> Add for Owned<Data> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Owned {
+            size: self.size + rhs.size,
+            ..self
+        }
+    }
+}
+
+impl<Data: Add<Output = Data> + Copy> AddAssign for Owned<Data> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<Data: Add<Output = Data> + Default> Sum for Owned<Data> {
+    fn sum<Iter: Iterator<Item = Self>>(iter: Iter) -> Self {
+        iter.fold(Owned::default(), Add::add)
+    }
+}
+
+macro_rules! owned_size {
+    ($data:ident = $inner:ty) => {
+        impl Size for Owned<$data> {
+            type Inner = $inner;
+            type DisplayFormat = <$data as Size>::DisplayFormat;
+            type DisplayOutput = <$data as Size>::DisplayOutput;
+            fn display(self, format: Self::DisplayFormat) -> Self::DisplayOutput {
+                self.size.display(format)
+            }
+            fn display_columns(self, format: Self::DisplayFormat) -> String {
+                self.size.display_columns(format)
+            }
+        }
+
+        impl From<Owned<$data>> for $inner {
+            fn from(owned: Owned<$data>) -> Self {
+                owned.size.into()
+            }
+        }
+
+        impl From<$inner> for Owned<$data> {
+            fn from(inner: $inner) -> Self {
+                let size = inner.into();
+                Owned {
+                    size,
+                    own_size: size,
+                    owner: None,
+                }
+            }
+        }
+
+        impl Mul<Owned<$data>> for $inner {
+            type Output = Owned<$data>;
+            fn mul(self, rhs: Owned<$data>) -> Self::Output {
+                Owned {
+                    size: self * rhs.size,
+                    own_size: self * rhs.own_size,
+                    owner: rhs.owner,
+                }
+            }
+        }
+    };
+}
+
+owned_size!(Bytes = u64);
+owned_size!(Blocks = u64);
+owned_size!(ByteDays = u64);
@@ -0,0 +1,132 @@
+use super::{Blocks, ByteDays, Bytes, Size};
+use crate::age_format::AgeFormat;
+use std::{
+    cmp::{max, min},
+    fs::Metadata,
+    iter::Sum,
+    ops::{Add, AddAssign, Mul},
+    time::SystemTime,
+};
+
+/// Disk usage statistic paired with the latest and the earliest modification times
+/// and the latest access time amongst the item and its descendants.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Timed<Data> {
+    /// The disk usage statistic.
+    pub size: Data,
+    /// The latest modification time, if it is available.
+    pub modified: Option<SystemTime>,
+    /// The earliest modification time, if it is available.
+    pub oldest_modified: Option<SystemTime>,
+    /// The latest access time, if it is available.
+    pub accessed: Option<SystemTime>,
+}
+
+impl<Data> Timed<Data> {
+    /// Pair a disk usage statistic with a modification time.
+    pub const fn new(size: Data, modified: Option<SystemTime>) -> Self {
+        Timed {
+            size,
+            modified,
+            oldest_modified: modified,
+            accessed: None,
+        }
+    }
+
+    /// Pair a disk usage statistic with the modification and access times from a [`Metadata`].
+    pub fn from_metadata(size: Data, metadata: &Metadata) -> Self {
+        Timed::new(size, metadata.modified().ok()).with_accessed(metadata.accessed().ok())
+    }
+
+    /// Set the access time.
+    pub fn with_accessed(self, accessed: Option<SystemTime>) -> Self {
+        Timed { accessed, ..self }
+    }
+}
+
+/// Format to [`display`](Size::display) a [`Timed`] value, whose ages are shown as
+/// [extra columns](Size::display_columns).
+#[derive(Debug, Clone, Copy)]
+pub struct TimedFormat<SizeFormat> {
+    /// Format of the size.
+    pub size: SizeFormat,
+    /// Which ages to display in the extra columns.
+    pub age: AgeFormat,
+}
+
+/// The earlier of two optional times, where an unknown time is ignored.
+fn earliest(left: Option<SystemTime>, right: Option<SystemTime>) -> Option<SystemTime> {
+    match (left, right) {
+        (Some(left), Some(right)) => Some(min(left, right)),
+        (left, None) => left,
+        (None, right) => right,
+    }
+}
+
+impl<Data: Add<Output = Data>> Add for Timed<Data> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Timed {
+            size: self.size + rhs.size,
+            modified: max(self.modified, rhs.modified),
+            oldest_modified: earliest(self.oldest_modified, rhs.oldest_modified),
+            accessed: max(self.accessed, rhs.accessed),
+        }
+    }
+}
+
+impl<Data: Add<Output = Data> + Copy> AddAssign for Timed<Data> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<Data: Add<Output = Data> + Default> Sum for Timed<Data> {
+    fn sum<Iter: Iterator<Item = Self>>(iter: Iter) -> Self {
+        iter.fold(Timed::default(), Add::add)
+    }
+}
+
+macro_rules! timed_size {
+    ($data:ident = $inner:ty) => {
+        impl Size for Timed<$data> {
+            type Inner = $inner;
+            type DisplayFormat = TimedFormat<<$data as Size>::DisplayFormat>;
+            type DisplayOutput = <$data as Size>::DisplayOutput;
+            fn display(self, format: Self::DisplayFormat) -> Self::DisplayOutput {
+                self.size.display(format.size)
+            }
+            fn display_columns(self, format: Self::DisplayFormat) -> String {
+                format
+                    .age
+                    .columns(self.modified, self.oldest_modified, self.accessed)
+            }
+        }
+
+        impl From<Timed<$data>> for $inner {
+            fn from(timed: Timed<$data>) -> Self {
+                timed.size.into()
+            }
+        }
+
+        impl From<$inner> for Timed<$data> {
+            fn from(inner: $inner) -> Self {
+                Timed::new(inner.into(), None)
+            }
+        }
+
+        impl Mul<Timed<$data>> for $inner {
+            type Output = Timed<$data>;
+            fn mul(self, rhs: Timed<$data>) -> Self::Output {
+                Timed {
+                    size: self * rhs.size,
+                    ..rhs
+                }
+            }
+        }
+    };
+}
+
+timed_size!(Bytes = u64);
+timed_size!(Blocks = u64);
+timed_size!(ByteDays = u64);
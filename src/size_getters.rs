@@ -1,8 +1,8 @@
-use super::size::Bytes;
-use std::fs::Metadata;
+use super::size::{ByteDays, Bytes, Timed};
+use std::{convert::TryFrom, fs::Metadata, time::SystemTime};
 
 #[cfg(unix)]
-use super::size::Blocks;
+use super::size::{Blocks, Owned, Owner};
 #[cfg(unix)]
 use std::os::unix::prelude::MetadataExt;
 
@@ -16,3 +16,85 @@ pub const GET_BLOCK_SIZE: SizeGetter<Bytes> = |metadata| metadata.blksize().into
 /// Returns [`metadata.blocks()`](Metadata::blocks) (POSIX only).
 #[cfg(unix)]
 pub const GET_BLOCK_COUNT: SizeGetter<Blocks> = |metadata| metadata.blocks().into();
+/// Returns [`metadata.len()`](Metadata::len) alongside [`metadata.modified()`](Metadata::modified).
+pub const GET_TIMED_APPARENT_SIZE: SizeGetter<Timed<Bytes>> =
+    |metadata| Timed::from_metadata(GET_APPARENT_SIZE(metadata), metadata);
+/// Returns [`metadata.blksize()`](Metadata::blksize) alongside [`metadata.modified()`](Metadata::modified) (POSIX only).
+#[cfg(unix)]
+pub const GET_TIMED_BLOCK_SIZE: SizeGetter<Timed<Bytes>> =
+    |metadata| Timed::from_metadata(GET_BLOCK_SIZE(metadata), metadata);
+/// Returns [`metadata.blocks()`](Metadata::blocks) alongside [`metadata.modified()`](Metadata::modified) (POSIX only).
+#[cfg(unix)]
+pub const GET_TIMED_BLOCK_COUNT: SizeGetter<Timed<Blocks>> =
+    |metadata| Timed::from_metadata(GET_BLOCK_COUNT(metadata), metadata);
+/// Returns [`metadata.len()`](Metadata::len) attributed to the owner of the item (POSIX only).
+#[cfg(unix)]
+pub const GET_OWNED_APPARENT_SIZE: SizeGetter<Owned<Bytes>> =
+    |metadata| Owned::new(GET_APPARENT_SIZE(metadata), get_owner(metadata));
+/// Returns [`metadata.blksize()`](Metadata::blksize) attributed to the owner of the item (POSIX only).
+#[cfg(unix)]
+pub const GET_OWNED_BLOCK_SIZE: SizeGetter<Owned<Bytes>> =
+    |metadata| Owned::new(GET_BLOCK_SIZE(metadata), get_owner(metadata));
+/// Returns [`metadata.blocks()`](Metadata::blocks) attributed to the owner of the item (POSIX only).
+#[cfg(unix)]
+pub const GET_OWNED_BLOCK_COUNT: SizeGetter<Owned<Blocks>> =
+    |metadata| Owned::new(GET_BLOCK_COUNT(metadata), get_owner(metadata));
+
+/// Returns [`metadata.len()`](Metadata::len) multiplied by the number of days since
+/// [`metadata.modified()`](Metadata::modified) as of `now`.
+pub fn get_cold_bytes(now: SystemTime) -> impl Fn(&Metadata) -> ByteDays + Copy + Sync {
+    move |metadata| get_byte_days(now, metadata)
+}
+/// Returns the result of [`get_cold_bytes`] alongside [`metadata.modified()`](Metadata::modified).
+pub fn get_timed_cold_bytes(
+    now: SystemTime,
+) -> impl Fn(&Metadata) -> Timed<ByteDays> + Copy + Sync {
+    move |metadata| Timed::from_metadata(get_byte_days(now, metadata), metadata)
+}
+/// Returns the result of [`get_cold_bytes`] attributed to the owner of the item (POSIX only).
+#[cfg(unix)]
+pub fn get_owned_cold_bytes(
+    now: SystemTime,
+) -> impl Fn(&Metadata) -> Owned<ByteDays> + Copy + Sync {
+    move |metadata| Owned::new(get_byte_days(now, metadata), get_owner(metadata))
+}
+
+/// Wrap a size getter to measure items other than directories that were modified
+/// after `cutoff` as empty, so that only the untouched files are counted.
+pub fn untouched_since<Data: Default>(
+    get_data: impl Fn(&Metadata) -> Data + Copy + Sync,
+    cutoff: Option<SystemTime>,
+) -> impl Fn(&Metadata) -> Data + Copy + Sync {
+    move |metadata| match cutoff {
+        Some(cutoff)
+            if !metadata.is_dir()
+                && metadata
+                    .modified()
+                    .map_or(false, |modified| modified > cutoff) =>
+        {
+            Data::default()
+        }
+        _ => get_data(metadata),
+    }
+}
+
+/// Multiply the apparent size by the number of days since the last modification.
+fn get_byte_days(now: SystemTime, metadata: &Metadata) -> ByteDays {
+    const SECONDS_PER_DAY: u128 = 24 * 60 * 60;
+    let seconds = metadata
+        .modified()
+        .ok()
+        .and_then(|modified| now.duration_since(modified).ok())
+        .map_or(0, |age| age.as_secs());
+    let byte_days = u128::from(metadata.len()) * u128::from(seconds) / SECONDS_PER_DAY;
+    u64::try_from(byte_days).unwrap_or(u64::MAX).into()
+}
+
+/// Extract the owner of the item from a [`Metadata`] (POSIX only).
+#[cfg(unix)]
+fn get_owner(metadata: &Metadata) -> Owner {
+    Owner {
+        uid: metadata.uid(),
+        gid: metadata.gid(),
+    }
+}
@@ -0,0 +1,146 @@
+pub mod natural_order;
+
+pub use natural_order::natural_cmp;
+
+use crate::{
+    data_tree::DataTree,
+    size::{Size, Timed},
+};
+use std::{cmp::Ordering, ffi::OsStr};
+use strum::{AsRefStr, EnumString, EnumVariantNames};
+
+/// Order of the entries amongst their siblings.
+///
+/// Entries that are equal according to the key are ordered by their names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AsRefStr, EnumString, EnumVariantNames)]
+pub enum SortKey {
+    /// Sort by size, biggest first.
+    #[strum(serialize = "size")]
+    SizeDescending,
+    /// Sort by size, smallest first.
+    #[strum(serialize = "size-asc")]
+    SizeAscending,
+    /// Sort by name in alphabetical order.
+    #[strum(serialize = "name")]
+    Name,
+    /// Sort by name in [natural order](natural_cmp), e.g. `file2` before `file10`.
+    #[strum(serialize = "natural")]
+    NaturalName,
+    /// Sort by number of items, most first.
+    ///
+    /// Applies to the whole tree, see [`sort_tree`](SortKey::sort_tree).
+    #[strum(serialize = "count")]
+    ItemCount,
+    /// Sort by modification time, newest first.
+    ///
+    /// Requires [`Timed`] data, see [`sort_timed`](SortKey::sort_timed).
+    #[strum(serialize = "mtime")]
+    ModificationTime,
+}
+
+impl SortKey {
+    pub(crate) fn default_value() -> &'static str {
+        SortKey::SizeDescending.as_ref()
+    }
+
+    /// Whether the key requires the data to be [`Timed`].
+    pub const fn requires_modification_time(self) -> bool {
+        matches!(self, SortKey::ModificationTime)
+    }
+
+    /// Whether the key should be applied to the whole tree by [`sort_tree`](SortKey::sort_tree)
+    /// instead of to every list of children as it is formed.
+    pub const fn requires_whole_tree(self) -> bool {
+        matches!(self, SortKey::ItemCount)
+    }
+
+    /// Sort a list of sibling trees.
+    ///
+    /// Since the data carry no modification times, [`SortKey::ModificationTime`]
+    /// only sorts by names.
+    ///
+    /// [`SortKey::ItemCount`] counts the items of every sibling anew, which costs as much as
+    /// traversing them, use [`sort_tree`](SortKey::sort_tree) to sort a whole tree instead.
+    pub fn sort<Name, Data>(self, children: &mut Vec<DataTree<Name, Data>>)
+    where
+        Name: AsRef<OsStr>,
+        Data: Size,
+    {
+        match self {
+            SortKey::SizeDescending => children.sort_by(|left, right| {
+                right
+                    .data()
+                    .cmp(&left.data())
+                    .then_with(|| compare_names(left, right))
+            }),
+            SortKey::SizeAscending => children.sort_by(|left, right| {
+                left.data()
+                    .cmp(&right.data())
+                    .then_with(|| compare_names(left, right))
+            }),
+            SortKey::Name | SortKey::ModificationTime => children.sort_by(compare_names),
+            SortKey::NaturalName => children.sort_by(|left, right| {
+                natural_cmp(
+                    &left.name().as_ref().to_string_lossy(),
+                    &right.name().as_ref().to_string_lossy(),
+                )
+                .then_with(|| compare_names(left, right))
+            }),
+            SortKey::ItemCount => {
+                let mut counted: Vec<_> = children
+                    .drain(..)
+                    .map(|child| (child.item_count(), child))
+                    .collect();
+                counted.sort_by(|(left_count, left), (right_count, right)| {
+                    right_count
+                        .cmp(left_count)
+                        .then_with(|| compare_names(left, right))
+                });
+                children.extend(counted.into_iter().map(|(_, child)| child));
+            }
+        }
+    }
+
+    /// Sort all descendants of a tree, counting the items bottom-up only once.
+    ///
+    /// Keys other than [`SortKey::ItemCount`] are applied to every list of children as the
+    /// tree is formed, so they leave the tree as it is.
+    pub fn sort_tree<Name, Data>(self, tree: &mut DataTree<Name, Data>)
+    where
+        Name: AsRef<OsStr>,
+        Data: Size,
+        DataTree<Name, Data>: Send,
+    {
+        if self.requires_whole_tree() {
+            tree.par_sort_by_item_count(compare_names);
+        }
+    }
+
+    /// Sort a list of sibling trees whose data carry modification times.
+    pub fn sort_timed<Name, Data>(self, children: &mut Vec<DataTree<Name, Timed<Data>>>)
+    where
+        Name: AsRef<OsStr>,
+        Timed<Data>: Size,
+    {
+        if self == SortKey::ModificationTime {
+            children.sort_by(|left, right| {
+                right
+                    .data()
+                    .modified
+                    .cmp(&left.data().modified)
+                    .then_with(|| compare_names(left, right))
+            });
+        } else {
+            self.sort(children);
+        }
+    }
+}
+
+/// Compare two trees by their names.
+fn compare_names<Name, Data>(left: &DataTree<Name, Data>, right: &DataTree<Name, Data>) -> Ordering
+where
+    Name: AsRef<OsStr>,
+    Data: Size,
+{
+    left.name().as_ref().cmp(right.name().as_ref())
+}
@@ -0,0 +1,54 @@
+use std::cmp::Ordering;
+
+/// Compare two strings in natural order.
+///
+/// Sequences of ASCII digits are compared by their numeric values (e.g. `"file2"` comes
+/// before `"file10"`), everything else is compared character by character.
+pub fn natural_cmp(left: &str, right: &str) -> Ordering {
+    let (left, right) = (left.as_bytes(), right.as_bytes());
+    let (mut left_index, mut right_index) = (0, 0);
+
+    while left_index < left.len() && right_index < right.len() {
+        let ordering = if left[left_index].is_ascii_digit() && right[right_index].is_ascii_digit() {
+            let left_end = digits_end(left, left_index);
+            let right_end = digits_end(right, right_index);
+            let ordering =
+                compare_digits(&left[left_index..left_end], &right[right_index..right_end]);
+            left_index = left_end;
+            right_index = right_end;
+            ordering
+        } else {
+            let ordering = left[left_index].cmp(&right[right_index]);
+            left_index += 1;
+            right_index += 1;
+            ordering
+        };
+
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+
+    (left.len() - left_index).cmp(&(right.len() - right_index))
+}
+
+/// Find the end of the sequence of digits that starts at `start`.
+fn digits_end(text: &[u8], start: usize) -> usize {
+    text[start..]
+        .iter()
+        .position(|byte| !byte.is_ascii_digit())
+        .map_or(text.len(), |length| start + length)
+}
+
+/// Compare two sequences of digits by their numeric values.
+fn compare_digits(left: &[u8], right: &[u8]) -> Ordering {
+    let trim_zeros = |digits: &[u8]| -> usize {
+        digits
+            .iter()
+            .position(|digit| *digit != b'0')
+            .unwrap_or(digits.len())
+    };
+    let left = &left[trim_zeros(left)..];
+    let right = &right[trim_zeros(right)..];
+    left.len().cmp(&right.len()).then_with(|| left.cmp(right))
+}
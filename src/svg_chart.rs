@@ -0,0 +1,376 @@
+use crate::{data_tree::DataTree, size::Size};
+use std::{
+    cmp::{Ordering, Reverse},
+    fmt::{Display, Error, Formatter},
+    num::NonZeroUsize,
+};
+use strum::{AsRefStr, EnumString, EnumVariantNames};
+
+/// Width of the charts that the CLI renders.
+pub const DEFAULT_SVG_WIDTH: f64 = 1200.0;
+
+/// Height of the charts that the CLI renders.
+pub const DEFAULT_SVG_HEIGHT: f64 = 800.0;
+
+/// Colors that the rectangles take turns to use.
+const PALETTE: [&str; 10] = [
+    "#8cb4d9", "#f6b26b", "#ea9999", "#9fd4d0", "#93c47d", "#ffe599", "#c9a3c5", "#ffc2c9",
+    "#c4a484", "#d5d0cc",
+];
+
+/// Color of the directories when coloring by extension.
+const DIRECTORY_COLOR: &str = "#eeeeee";
+
+/// Color of the files without extensions when coloring by extension.
+const NO_EXTENSION_COLOR: &str = "#bbbbbb";
+
+/// Height of the label above the children of a treemap rectangle.
+const LABEL_HEIGHT: f64 = 16.0;
+
+/// Approximate width of a character of a label.
+const CHAR_WIDTH: f64 = 7.0;
+
+/// Space between a treemap rectangle and its children.
+const PADDING: f64 = 2.0;
+
+/// Shape of a [`SvgChart`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChartKind {
+    /// Nested rectangles whose areas are proportional to the data, laid out by the squarified
+    /// algorithm.
+    Treemap,
+    /// Rows of levels from the root at the top, whose widths are proportional to the data.
+    Icicle,
+}
+
+/// How the rectangles of a [`SvgChart`] are colored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AsRefStr, EnumString, EnumVariantNames)]
+pub enum ColorScheme {
+    /// Every level of the tree has its own color.
+    #[strum(serialize = "depth")]
+    Depth,
+    /// Files are colored by their extensions, directories are gray.
+    #[strum(serialize = "extension")]
+    Extension,
+}
+
+impl ColorScheme {
+    pub(crate) fn default_value() -> &'static str {
+        ColorScheme::Depth.as_ref()
+    }
+
+    /// Color of a node whose name is `name` at `depth` levels below the root.
+    fn color(self, name: &str, depth: usize, is_leaf: bool) -> &'static str {
+        match self {
+            ColorScheme::Depth => PALETTE[depth % PALETTE.len()],
+            ColorScheme::Extension if !is_leaf => DIRECTORY_COLOR,
+            ColorScheme::Extension => match name.rfind('.') {
+                Some(dot) if dot > 0 && dot + 1 < name.len() => {
+                    // FNV-1a, so that the colors are the same on every run.
+                    let hash = name[dot + 1..]
+                        .to_lowercase()
+                        .bytes()
+                        .fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+                            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+                        });
+                    PALETTE[(hash % PALETTE.len() as u64) as usize]
+                }
+                _ => NO_EXTENSION_COLOR,
+            },
+        }
+    }
+}
+
+/// Static SVG image of a [`DataTree`], which can be embedded without scripts.
+#[derive(Debug)]
+pub struct SvgChart<'a, Name, Data>
+where
+    Name: Display,
+    Data: Size,
+{
+    /// The tree to draw.
+    pub data_tree: &'a DataTree<Name, Data>,
+    /// Format to be used to [`display`](Size::display) the data.
+    pub bytes_format: Data::DisplayFormat,
+    /// Maximum number of levels that should be drawn.
+    pub max_depth: NonZeroUsize,
+    /// Shape of the chart.
+    pub kind: ChartKind,
+    /// How the rectangles are colored.
+    pub color_scheme: ColorScheme,
+    /// Width of the image.
+    pub width: f64,
+    /// Height of the image.
+    pub height: f64,
+}
+
+/// Area of the image.
+#[derive(Debug, Clone, Copy)]
+struct Rectangle {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+impl<'a, Name, Data> SvgChart<'a, Name, Data>
+where
+    Name: Display,
+    Data: Size + Into<u64>,
+{
+    /// Draw a node and return its path.
+    fn draw(
+        &self,
+        output: &mut String,
+        node: &DataTree<Name, Data>,
+        path: &str,
+        depth: usize,
+        area: Rectangle,
+    ) -> String {
+        let name = node.name().to_string();
+        let path = if depth == 0 {
+            name.clone()
+        } else if path.ends_with('/') {
+            format!("{}{}", path, name)
+        } else {
+            format!("{}/{}", path, name)
+        };
+        let size = size_of(node);
+        let total = size_of(self.data_tree);
+        let percentage = if total == 0 {
+            0.0
+        } else {
+            size as f64 * 100.0 / total as f64
+        };
+        let formatted_size = node.data().display(self.bytes_format).to_string();
+        let is_leaf = node.children().is_empty();
+        let color = self.color_scheme.color(&name, depth, is_leaf);
+
+        output.push_str(&format!(
+            "<g><title>{} {} ({:.1}%)</title>",
+            escape(&path),
+            escape(&formatted_size),
+            percentage,
+        ));
+        output.push_str(&format!(
+            r##"<rect x="{:.2}" y="{:.2}" width="{:.2}" height="{:.2}" fill="{}" stroke="#ffffff"/>"##,
+            area.x, area.y, area.width, area.height, color,
+        ));
+        let label = format!("{} {}", name, formatted_size);
+        let labels = [label.as_str(), name.as_str()];
+        let label = labels
+            .iter()
+            .find(|label| label.chars().count() as f64 * CHAR_WIDTH + 2.0 * PADDING <= area.width)
+            .filter(|_| area.height >= LABEL_HEIGHT - PADDING);
+        if let Some(label) = label {
+            output.push_str(&format!(
+                r#"<text x="{:.2}" y="{:.2}">{}</text>"#,
+                area.x + PADDING * 2.0,
+                area.y + LABEL_HEIGHT - PADDING * 2.0,
+                escape(label),
+            ));
+        }
+        output.push_str("</g>\n");
+        path
+    }
+
+    fn draw_treemap(
+        &self,
+        output: &mut String,
+        node: &DataTree<Name, Data>,
+        path: &str,
+        depth: usize,
+        area: Rectangle,
+    ) {
+        let path = self.draw(output, node, path, depth, area);
+        if depth + 1 >= self.max_depth.get() {
+            return;
+        }
+        let inner = Rectangle {
+            x: area.x + PADDING,
+            y: area.y + LABEL_HEIGHT,
+            width: area.width - 2.0 * PADDING,
+            height: area.height - LABEL_HEIGHT - PADDING,
+        };
+        if inner.width < 1.0 || inner.height < 1.0 {
+            return;
+        }
+        let mut children: Vec<_> = node
+            .children()
+            .iter()
+            .filter(|child| size_of(child) > 0)
+            .collect();
+        children.sort_by_key(|child| Reverse(child.data()));
+        let mut items: Vec<_> = children
+            .iter()
+            .map(|child| (Some(*child), size_of(child) as f64))
+            .collect();
+        let children_size: u64 = children.iter().map(|child| size_of(child)).sum();
+        let own_size = size_of(node).saturating_sub(children_size);
+        if own_size > 0 {
+            items.push((None, own_size as f64)); // the node itself.
+        }
+        for (child, area) in squarify(&items, inner) {
+            if let Some(child) = child {
+                self.draw_treemap(output, child, &path, depth + 1, area);
+            }
+        }
+    }
+
+    fn draw_icicle(
+        &self,
+        output: &mut String,
+        node: &DataTree<Name, Data>,
+        path: &str,
+        depth: usize,
+        area: Rectangle,
+    ) {
+        let path = self.draw(output, node, path, depth, area);
+        if depth + 1 >= self.max_depth.get() {
+            return;
+        }
+        let size = size_of(node);
+        if size == 0 {
+            return;
+        }
+        let mut x = area.x;
+        for child in node.children() {
+            let width = area.width * size_of(child) as f64 / size as f64;
+            let child_area = Rectangle {
+                x,
+                y: area.y + area.height,
+                width,
+                height: area.height,
+            };
+            self.draw_icicle(output, child, &path, depth + 1, child_area);
+            x += width;
+        }
+    }
+}
+
+impl<'a, Name, Data> Display for SvgChart<'a, Name, Data>
+where
+    Name: Display,
+    Data: Size + Into<u64>,
+{
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> Result<(), Error> {
+        let mut body = String::new();
+        match self.kind {
+            ChartKind::Treemap => {
+                let area = Rectangle {
+                    x: 0.0,
+                    y: 0.0,
+                    width: self.width,
+                    height: self.height,
+                };
+                self.draw_treemap(&mut body, self.data_tree, "", 0, area);
+            }
+            ChartKind::Icicle => {
+                let levels = self.max_depth.get().min(height(self.data_tree));
+                let area = Rectangle {
+                    x: 0.0,
+                    y: 0.0,
+                    width: self.width,
+                    height: self.height / levels as f64,
+                };
+                self.draw_icicle(&mut body, self.data_tree, "", 0, area);
+            }
+        }
+        writeln!(
+            formatter,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="sans-serif" font-size="12">"#,
+            width = self.width,
+            height = self.height,
+        )?;
+        write!(formatter, "{}", body)?;
+        writeln!(formatter, "</svg>")
+    }
+}
+
+fn size_of<Name, Data: Size + Into<u64>>(tree: &DataTree<Name, Data>) -> u64 {
+    tree.data().into()
+}
+
+/// Number of levels of a tree.
+fn height<Name, Data: Size>(tree: &DataTree<Name, Data>) -> usize {
+    1 + tree.children().iter().map(height).max().unwrap_or(0)
+}
+
+/// Lay out items that are sorted by their values, biggest first, into rectangles whose
+/// aspect ratios are close to 1, by the squarified treemap algorithm.
+fn squarify<Item: Copy>(items: &[(Item, f64)], area: Rectangle) -> Vec<(Item, Rectangle)> {
+    // Highest aspect ratio amongst the rectangles of a row.
+    fn worst(areas: &[f64], side: f64) -> f64 {
+        let sum: f64 = areas.iter().sum();
+        let max = areas.iter().copied().fold(f64::MIN, f64::max);
+        let min = areas.iter().copied().fold(f64::MAX, f64::min);
+        f64::max(
+            side * side * max / (sum * sum),
+            sum * sum / (side * side * min),
+        )
+    }
+
+    let Rectangle {
+        mut x,
+        mut y,
+        mut width,
+        mut height,
+    } = area;
+    let total: f64 = items.iter().map(|(_, value)| value).sum();
+    let mut rectangles = Vec::with_capacity(items.len());
+    if total <= 0.0 || width <= 0.0 || height <= 0.0 {
+        return rectangles;
+    }
+    let scale = width * height / total;
+    let areas: Vec<_> = items.iter().map(|(_, value)| value * scale).collect();
+    let mut start = 0;
+    while start < items.len() {
+        let side = f64::min(width, height);
+        let mut end = start + 1;
+        while end < items.len()
+            && worst(&areas[start..=end], side).partial_cmp(&worst(&areas[start..end], side))
+                != Some(Ordering::Greater)
+        {
+            end += 1;
+        }
+        let thickness = areas[start..end].iter().sum::<f64>() / side;
+        let mut offset = 0.0;
+        for ((item, _), area) in items[start..end].iter().zip(&areas[start..end]) {
+            let length = area / thickness;
+            let rectangle = if width >= height {
+                Rectangle {
+                    x,
+                    y: y + offset,
+                    width: thickness,
+                    height: length,
+                }
+            } else {
+                Rectangle {
+                    x: x + offset,
+                    y,
+                    width: length,
+                    height: thickness,
+                }
+            };
+            rectangles.push((*item, rectangle));
+            offset += length;
+        }
+        if width >= height {
+            x += thickness;
+            width -= thickness;
+        } else {
+            y += thickness;
+            height -= thickness;
+        }
+        start = end;
+    }
+    rectangles
+}
+
+/// Escape the special characters of XML.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
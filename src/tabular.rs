@@ -0,0 +1,151 @@
+use crate::{
+    data_tree::DataTree,
+    size::Size,
+    visualizer::traversal::{traverse, Visit},
+};
+use std::{
+    fmt::{Display, Error, Formatter},
+    num::NonZeroUsize,
+};
+
+/// Character that separates the fields of a [`Tabular`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delimiter {
+    /// Comma-separated values, whose fields are quoted when needed.
+    Comma,
+    /// Tab-separated values, whose tabs, line breaks and backslashes are escaped by backslashes.
+    Tab,
+}
+
+/// Flat table of the nodes of a [`DataTree`], with the same rows as a top-down
+/// [`Visualizer`](crate::visualizer::Visualizer) and a header row.
+#[derive(Debug)]
+pub struct Tabular<'a, Name, Data>
+where
+    Name: Display,
+    Data: Size,
+{
+    /// The tree to tabulate.
+    pub data_tree: &'a DataTree<Name, Data>,
+    /// Format to be used to [`display`](Size::display) the data.
+    pub bytes_format: Data::DisplayFormat,
+    /// Maximum number of levels that should be tabulated.
+    pub max_depth: NonZeroUsize,
+    /// Separator of the fields.
+    pub delimiter: Delimiter,
+}
+
+/// Names of the columns of a [`Tabular`].
+pub const TABULAR_HEADER: [&str; 7] = [
+    "depth",
+    "path",
+    "size",
+    "formatted_size",
+    "percent_of_root",
+    "percent_of_parent",
+    "children",
+];
+
+impl<'a, Name, Data> Tabular<'a, Name, Data>
+where
+    Name: Display,
+    Data: Size + Into<u64>,
+{
+    /// Create the rows of the table, excluding the header.
+    pub fn rows(&self) -> Vec<[String; 7]> {
+        struct Row {
+            path: String,
+            size: u64,
+        }
+
+        let root_size = self.data_tree.data().into();
+        let mut rows = Vec::new();
+        let mut fields = Vec::new();
+        traverse(self.data_tree, self.max_depth, |node, visit| {
+            let Visit { ancestors, .. } = visit;
+            let size: u64 = node.data().into();
+            let name = node.name().to_string();
+            let (path, parent_size) = match ancestors.last() {
+                None => (name, size),
+                Some(&parent) => {
+                    let Row { path, size } = &rows[parent];
+                    let path = if path.ends_with('/') {
+                        format!("{}{}", path, name)
+                    } else {
+                        format!("{}/{}", path, name)
+                    };
+                    (path, *size)
+                }
+            };
+            fields.push([
+                ancestors.len().to_string(),
+                path.clone(),
+                size.to_string(),
+                node.data().display(self.bytes_format).to_string(),
+                percentage(size, root_size),
+                percentage(size, parent_size),
+                node.children().len().to_string(),
+            ]);
+            rows.push(Row { path, size });
+            rows.len() - 1
+        });
+        fields
+    }
+}
+
+impl<'a, Name, Data> Display for Tabular<'a, Name, Data>
+where
+    Name: Display,
+    Data: Size + Into<u64>,
+{
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> Result<(), Error> {
+        write_row(formatter, &TABULAR_HEADER, self.delimiter)?;
+        for row in self.rows() {
+            write_row(formatter, &row, self.delimiter)?;
+        }
+        Ok(())
+    }
+}
+
+fn write_row<Field: AsRef<str>>(
+    formatter: &mut Formatter<'_>,
+    fields: &[Field],
+    delimiter: Delimiter,
+) -> Result<(), Error> {
+    let separator = match delimiter {
+        Delimiter::Comma => ",",
+        Delimiter::Tab => "\t",
+    };
+    let fields: Vec<_> = fields
+        .iter()
+        .map(|field| escape(field.as_ref(), delimiter))
+        .collect();
+    writeln!(formatter, "{}", fields.join(separator))
+}
+
+/// Percentage of `part` in `whole` with two decimal places.
+fn percentage(part: u64, whole: u64) -> String {
+    if whole == 0 {
+        "0.00".to_string()
+    } else {
+        format!("{:.2}", part as f64 * 100.0 / whole as f64)
+    }
+}
+
+/// Escape a field according to the conventions of the delimiter.
+fn escape(field: &str, delimiter: Delimiter) -> String {
+    match delimiter {
+        Delimiter::Comma => {
+            if field.contains(|char: char| matches!(char, ',' | '"' | '\n' | '\r')) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.to_string()
+            }
+        }
+        Delimiter::Tab => field
+            .replace('\\', "\\\\")
+            .replace('\t', "\\t")
+            .replace('\n', "\\n")
+            .replace('\r', "\\r"),
+    }
+}
@@ -50,7 +50,15 @@ where
             post_process_children,
         } = builder;
 
-        let Info { data, children } = get_info(&path);
+        let Info {
+            data,
+            children,
+            is_dir,
+        } = get_info(&path);
+
+        if !is_dir {
+            return DataTree::file(name, data);
+        }
 
         let mut children: Vec<_> = children
             .into_par_iter()
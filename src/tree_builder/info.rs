@@ -9,4 +9,6 @@ pub struct Info<Name, Data: Size> {
     pub data: Data,
     /// Direct descendants of given `path`.
     pub children: Vec<Name>,
+    /// Whether given `path` is a directory, the `children` of a file are ignored.
+    pub is_dir: bool,
 }
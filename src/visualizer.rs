@@ -3,6 +3,7 @@ pub mod column_width_distribution;
 pub mod direction;
 pub mod parenthood;
 pub mod proportion_bar;
+pub mod traversal;
 pub mod tree;
 
 pub use child_position::ChildPosition;
@@ -32,6 +33,9 @@ where
     pub column_width_distribution: ColumnWidthDistribution,
     /// Maximum number of levels that should be visualized.
     pub max_depth: NonZeroUsize,
+    /// Quantity that the bars and the percentages are relative to, such as the capacity of
+    /// the filesystem (defaults to the data of the root, which is also the minimum).
+    pub proportion_total: Option<u64>,
 }
 
 mod copy;
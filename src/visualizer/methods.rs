@@ -14,7 +14,10 @@ use tree_table::*;
 
 use super::{ColumnWidthDistribution, Visualizer};
 use crate::size::Size;
-use std::{cmp::min, fmt::Display};
+use std::{
+    cmp::{max, min},
+    fmt::Display,
+};
 use zero_copy_pads::{align_left, align_right};
 
 impl<'a, Name, Data> Visualizer<'a, Name, Data>
@@ -75,16 +78,18 @@ where
         };
 
         let size_width = tree_table.column_width.size_column_width;
+        let extra_columns_width = tree_table.column_width.extra_columns_width;
         let tree_width = tree_table.column_width.tree_column_width;
 
-        let bar_table = render_bars(tree_table, self.data_tree.data().into(), bar_width);
+        let bar_table = render_bars(tree_table, self.proportion_total(), bar_width);
 
         bar_table
             .into_iter()
             .map(|row| {
                 format!(
-                    "{size} {tree}│{bar}│{ratio}",
+                    "{size}{extra_columns} {tree}│{bar}│{ratio}",
                     size = align_right(&row.size, size_width),
+                    extra_columns = align_right(&row.extra_columns, extra_columns_width),
                     tree = align_left(&row.tree_horizontal_slice, tree_width),
                     bar = &row.proportion_bar,
                     ratio = align_right(&row.percentage, PERCENTAGE_COLUMN_MAX_WIDTH),
@@ -92,4 +97,10 @@ where
             })
             .collect()
     }
+
+    /// Quantity that the bars and the percentages are relative to.
+    fn proportion_total(&self) -> u64 {
+        let root = self.data_tree.data().into();
+        self.proportion_total.map_or(root, |total| max(total, root))
+    }
 }
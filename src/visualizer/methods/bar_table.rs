@@ -64,9 +64,10 @@ where
 
             #[cfg(debug_assertions)]
             {
+                // The root may fall short of the width when the total is not the data of the root.
                 let actual_lv4_value = ancestor_value!(0, lv3_value);
                 if actual_lv4_value != 0 {
-                    debug_assert_op!(actual_lv4_value == width);
+                    debug_assert_op!(actual_lv4_value <= width);
                     assert_cmp::debug_assert_op_expr!(
                         lv0_visible + lv1_visible + lv2_visible + lv3_visible + lv4_visible,
                         ==,
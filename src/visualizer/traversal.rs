@@ -0,0 +1,75 @@
+use crate::{data_tree::DataTree, size::Size};
+use std::num::NonZeroUsize;
+
+/// Position of a node that is visited by [`traverse`].
+#[derive(Debug, Clone)]
+pub struct Visit<Info> {
+    /// Number of levels from the node down to the deepest visualized level, including the node.
+    pub remaining_depth: usize,
+    /// Index of the node amongst its siblings.
+    pub index_as_child: usize,
+    /// Information of the ancestors of the node, starting from the root.
+    pub ancestors: Vec<Info>,
+    /// Information of the sibling that was visited right before the node.
+    pub preceding_sibling: Option<Info>,
+}
+
+/// Visit the nodes of a tree that are visualized within `max_depth` levels, in the order of
+/// the rows of a top-down visualization.
+///
+/// The information that `act` returns for a node is given to its descendants and to its
+/// following sibling.
+pub fn traverse<'a, Name, Data, Info, Act>(
+    tree: &'a DataTree<Name, Data>,
+    max_depth: NonZeroUsize,
+    mut act: Act,
+) where
+    Data: Size,
+    Info: Clone,
+    Act: FnMut(&'a DataTree<Name, Data>, Visit<Info>) -> Info,
+{
+    fn visit<'a, Name, Data, Info, Act>(
+        tree: &'a DataTree<Name, Data>,
+        act: &mut Act,
+        param: Visit<Info>,
+    ) -> Option<Info>
+    where
+        Data: Size,
+        Info: Clone,
+        Act: FnMut(&'a DataTree<Name, Data>, Visit<Info>) -> Info,
+    {
+        if param.remaining_depth == 0 {
+            return None;
+        }
+        let info = act(tree, param.clone());
+        let remaining_depth = param.remaining_depth - 1;
+        let mut preceding_sibling = None;
+        for (index_as_child, child) in tree.children().iter().enumerate() {
+            let mut ancestors = Vec::with_capacity(param.ancestors.len() + 1);
+            ancestors.clone_from(&param.ancestors);
+            ancestors.push(info.clone());
+            preceding_sibling = visit(
+                child,
+                act,
+                Visit {
+                    remaining_depth,
+                    index_as_child,
+                    ancestors,
+                    preceding_sibling,
+                },
+            );
+        }
+        Some(info)
+    }
+
+    visit(
+        tree,
+        &mut act,
+        Visit {
+            remaining_depth: max_depth.get(),
+            index_as_child: 0,
+            ancestors: Vec::with_capacity(0),
+            preceding_sibling: None,
+        },
+    );
+}
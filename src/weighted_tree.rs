@@ -0,0 +1,130 @@
+use super::{
+    data_tree::DataTree,
+    os_string_display::OsStringDisplay,
+    size::Size,
+    tree_builder::{Info, TreeBuilder},
+};
+use derive_more::Display;
+use pipe_trait::Pipe;
+use std::collections::HashMap;
+
+/// Line of a weighted hierarchy, e.g. `a;b;c 42` whose key is `a;b;c` and whose weight is `42`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WeightedEntry {
+    /// Components of the key, from the top of the hierarchy.
+    pub key: Vec<String>,
+    /// Weight of the item that the key refers to, excluding its descendants.
+    pub weight: u64,
+}
+
+/// Error that occurs when parsing a weighted hierarchy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum ParseError {
+    /// A line does not end with a weight.
+    #[display(fmt = "Invalid line {}", _0)]
+    InvalidLine(usize),
+}
+
+/// Parse lines of keys followed by whitespaces and weights, splitting the keys by `separator`.
+///
+/// Empty components of the keys are dropped and blank lines are skipped.
+pub fn parse_weighted_text(text: &str, separator: &str) -> Result<Vec<WeightedEntry>, ParseError> {
+    let mut entries = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let error = ParseError::InvalidLine(index + 1);
+        let (key, weight) = line
+            .rfind(char::is_whitespace)
+            .ok_or(error)?
+            .pipe(|middle| line.split_at(middle));
+        let weight = weight.trim_start().parse().map_err(|_| error)?;
+        let key = key.trim_end();
+        let key = if separator.is_empty() {
+            vec![key.to_string()]
+        } else {
+            key.split(separator)
+                .filter(|component| !component.is_empty())
+                .map(String::from)
+                .collect()
+        };
+        entries.push(WeightedEntry { key, weight });
+    }
+    Ok(entries)
+}
+
+/// Build a [`DataTree`] from the entries of a weighted hierarchy using [`From`] or [`Into`].
+///
+/// The weights of the entries with the same key are added up. The data of each node is its
+/// own weight plus the weights of its descendants, and the keys that are only implied by
+/// longer keys weigh nothing by themselves.
+#[derive(Debug)]
+pub struct WeightedTreeBuilder<PostProcessChildren> {
+    /// Name of the root, which holds the entries with empty keys.
+    pub name: OsStringDisplay,
+    /// The entries.
+    pub entries: Vec<WeightedEntry>,
+    /// Processes lists of children after forming.
+    pub post_process_children: PostProcessChildren,
+}
+
+impl<Data, PostProcessChildren> From<WeightedTreeBuilder<PostProcessChildren>>
+    for DataTree<OsStringDisplay, Data>
+where
+    Data: Size + From<u64> + Send + Sync,
+    PostProcessChildren: Fn(&mut Vec<DataTree<OsStringDisplay, Data>>) + Copy + Send + Sync,
+{
+    fn from(builder: WeightedTreeBuilder<PostProcessChildren>) -> Self {
+        let WeightedTreeBuilder {
+            name,
+            entries,
+            post_process_children,
+        } = builder;
+
+        // Own weight and names of the children of every key.
+        let mut nodes: HashMap<Vec<String>, (u64, Vec<String>)> = HashMap::new();
+        nodes.insert(Vec::new(), Default::default());
+        for WeightedEntry { key, weight } in entries {
+            for length in 1..=key.len() {
+                if !nodes.contains_key(&key[..length]) {
+                    nodes.insert(key[..length].to_vec(), Default::default());
+                    let (_, children) = nodes
+                        .get_mut(&key[..length - 1])
+                        .expect("parents are inserted before their children");
+                    children.push(key[length - 1].clone());
+                }
+            }
+            let (own_weight, _) = nodes.get_mut(&key).expect("the key was just inserted");
+            *own_weight = own_weight.saturating_add(weight);
+        }
+
+        TreeBuilder::<Vec<String>, OsStringDisplay, Data, _, _, PostProcessChildren> {
+            name,
+
+            path: Vec::new(),
+
+            get_info: |key| {
+                let (weight, children) = &nodes[key];
+                Info {
+                    data: (*weight).into(),
+                    children: children
+                        .iter()
+                        .map(OsStringDisplay::os_string_from)
+                        .collect(),
+                    is_dir: !children.is_empty(),
+                }
+            },
+
+            join_path: |prefix, name| {
+                let mut key = prefix.clone();
+                key.push(name.to_string_lossy().into_owned());
+                key
+            },
+
+            post_process_children,
+        }
+        .into()
+    }
+}
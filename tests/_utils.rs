@@ -1,11 +1,15 @@
 use build_fs_tree::{dir, file, Build, MergeableFileSystemTree};
 use derive_more::{AsRef, Deref};
 use dirt::{
+    bytes_format::BytesFormat,
     data_tree::{DataTree, DataTreeReflection},
     fs_tree_builder::FsTreeBuilder,
     os_string_display::OsStringDisplay,
+    output_format::{OutputFormat, RenderContext},
     reporter::ErrorOnlyReporter,
-    size::Size,
+    size::{Bytes, Size},
+    svg_chart::ColorScheme,
+    visualizer::{ColumnWidthDistribution, Direction, Visualizer},
 };
 use pipe_trait::Pipe;
 use pretty_assertions::assert_eq;
@@ -15,6 +19,7 @@ use std::{
     env::temp_dir,
     fs::{create_dir, metadata, remove_dir_all, Metadata},
     io::Error,
+    num::NonZeroUsize,
     path::{Path, PathBuf},
 };
 
@@ -210,3 +215,59 @@ where
         }),
     );
 }
+
+/// Tree of the tests of the output formats.
+pub type FormatSampleTree = DataTree<&'static str, Bytes>;
+
+/// Create a directory of a [`FormatSampleTree`], which measures nothing by itself.
+pub fn sample_dir(name: &'static str, children: Vec<FormatSampleTree>) -> FormatSampleTree {
+    FormatSampleTree::dir(name, 0.into(), children)
+}
+
+/// Create a file of a [`FormatSampleTree`].
+pub fn sample_file(name: &'static str, size: u64) -> FormatSampleTree {
+    FormatSampleTree::file(name, size.into())
+}
+
+/// Create the tree of the tests of the output formats, whose names contain what some of the
+/// formats have to escape: quotes and commas, tabs, line breaks, backslashes, markup,
+/// semicolons, Markdown emphasis and table delimiters, and the marker of an ordered list.
+pub fn format_sample_tree() -> FormatSampleTree {
+    sample_dir(
+        "root",
+        vec![
+            sample_dir(
+                "a, \"b\"",
+                vec![
+                    sample_file("x\t<y>;z", 600),
+                    sample_file("c\\d\ne|*_", 150),
+                    sample_dir("empty", Vec::new()),
+                ],
+            ),
+            sample_file("2021. </script>", 250),
+        ],
+    )
+}
+
+/// Render [`format_sample_tree`] in a format the way the CLI renders a tree that it did not
+/// scan, with plain numbers as sizes.
+pub fn render_format_sample(format: OutputFormat, max_depth: usize) -> String {
+    let tree = format_sample_tree();
+    let visualizer = Visualizer {
+        data_tree: &tree,
+        bytes_format: BytesFormat::PlainNumber,
+        direction: Direction::TopDown,
+        column_width_distribution: ColumnWidthDistribution::total(100),
+        max_depth: NonZeroUsize::new(max_depth).expect("non-zero max_depth"),
+        proportion_total: None,
+    };
+    let context = RenderContext {
+        color_scheme: ColorScheme::Depth,
+        quantity: "len",
+        unit: "bytes",
+        progress: None,
+        scan_duration: None,
+        markdown_bar: None,
+    };
+    format.render(visualizer, context)
+}
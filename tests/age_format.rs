@@ -0,0 +1,142 @@
+use dirt::{
+    age_format::{relative_age, AgeFormat},
+    bytes_format::BytesFormat,
+    data_tree::DataTree,
+    size::{Bytes, Size, Timed, TimedFormat},
+    visualizer::{ColumnWidthDistribution, Direction, Visualizer},
+};
+use pretty_assertions::assert_eq;
+use std::{
+    num::NonZeroUsize,
+    time::{Duration, SystemTime},
+};
+use zero_copy_pads::Width;
+
+const DAY: u64 = 24 * 60 * 60;
+
+fn now() -> SystemTime {
+    SystemTime::UNIX_EPOCH + Duration::from_secs(100 * 365 * DAY)
+}
+
+fn ago(seconds: u64) -> SystemTime {
+    now() - Duration::from_secs(seconds)
+}
+
+#[test]
+fn relative_ages() {
+    let actual: Vec<_> = [
+        Some(ago(0)),
+        Some(ago(59)),
+        Some(ago(60)),
+        Some(ago(3 * 60 * 60 + 59)),
+        Some(ago(12 * DAY)),
+        Some(ago(364 * DAY)),
+        Some(ago(3 * 365 * DAY + 7 * DAY)),
+        Some(now() + Duration::from_secs(DAY)),
+        None,
+    ]
+    .iter()
+    .map(|time| relative_age(now(), *time))
+    .collect();
+    let expected = ["0s", "59s", "1m", "3h", "12d", "364d", "3y", "0s", "-"];
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn aggregate_times() {
+    let file = |name, size: u64, modified, accessed| {
+        DataTree::file(
+            name,
+            Timed::new(Bytes::new(size), modified).with_accessed(accessed),
+        )
+    };
+    let tree = DataTree::dir(
+        "root",
+        Timed::new(Bytes::new(4096), Some(ago(DAY))),
+        vec![
+            file("old", 100, Some(ago(400 * DAY)), Some(ago(2 * DAY))),
+            file("new", 20, Some(ago(60)), Some(ago(400 * DAY))),
+            file("unknown", 3, None, None),
+        ],
+    );
+    let actual = tree.data();
+    let expected = Timed {
+        size: Bytes::new(4096 + 100 + 20 + 3),
+        modified: Some(ago(60)),
+        oldest_modified: Some(ago(400 * DAY)),
+        accessed: Some(ago(2 * DAY)),
+    };
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn display_ages() {
+    let timed = Timed {
+        size: Bytes::new(1234),
+        modified: Some(ago(12 * DAY)),
+        oldest_modified: Some(ago(3 * 365 * DAY)),
+        accessed: Some(ago(5 * 60)),
+    };
+    let display = |modified, accessed| {
+        let age = AgeFormat {
+            now: now(),
+            modified,
+            accessed,
+        };
+        let format = TimedFormat {
+            size: BytesFormat::PlainNumber,
+            age,
+        };
+        (
+            timed.display(format).to_string(),
+            timed.display_columns(format),
+        )
+    };
+    let display = |modified, accessed| {
+        let (size, columns) = display(modified, accessed);
+        assert_eq!(size, "1234");
+        columns
+    };
+    assert_eq!(display(false, false), "");
+    assert_eq!(display(true, false), "  12d   3y");
+    assert_eq!(display(false, true), "   5m");
+    assert_eq!(display(true, true), "  12d   3y   5m");
+}
+
+#[test]
+fn visualize_ages_in_their_own_column() {
+    let age = AgeFormat {
+        now: now(),
+        modified: true,
+        accessed: false,
+    };
+    let tree = DataTree::dir(
+        "root",
+        Timed::new(Bytes::new(0), None),
+        vec![
+            DataTree::file(
+                "old",
+                Timed::new(Bytes::new(1000), Some(ago(3 * 365 * DAY))),
+            ),
+            DataTree::file("new", Timed::new(Bytes::new(24), Some(ago(12 * DAY)))),
+        ],
+    );
+    let actual = Visualizer {
+        data_tree: &tree,
+        bytes_format: TimedFormat {
+            size: BytesFormat::PlainNumber,
+            age,
+        },
+        direction: Direction::TopDown,
+        column_width_distribution: ColumnWidthDistribution::total(60),
+        max_depth: NonZeroUsize::new(10).expect("non-zero max_depth"),
+        proportion_total: None,
+    }
+    .to_string();
+    let prefixes: Vec<_> = actual.lines().map(|line| &line[..15]).collect();
+    assert_eq!(
+        prefixes,
+        ["1024  12d   3y ", "1000   3y   3y ", "  24  12d  12d "]
+    );
+    assert!(actual.lines().all(|line| line.width() == 60));
+}
@@ -0,0 +1,267 @@
+use dirt::archive::{read_archive, ArchiveEntry, ArchiveError};
+use pretty_assertions::assert_eq;
+use std::io::Cursor;
+
+/// `release/`, `release/app` (5000 bytes) and `release/README` (300 bytes) in a tar archive
+/// compressed by gzip.
+const TAR_GZ: &[u8] = &[
+    0x1F, 0x8B, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x03, 0xED, 0xD8, 0x3B, 0x0A, 0x02, 0x31,
+    0x14, 0x46, 0xE1, 0x2C, 0x25, 0x3B, 0x30, 0x37, 0x93, 0x87, 0x96, 0x82, 0x53, 0xDA, 0xB8, 0x83,
+    0x80, 0xE9, 0x14, 0x86, 0x8C, 0xEE, 0xDF, 0x41, 0xA6, 0x0A, 0x82, 0x55, 0x26, 0x88, 0xE7, 0x34,
+    0xF7, 0xEB, 0xFF, 0xEE, 0x96, 0x7C, 0xCB, 0x69, 0xCE, 0x3B, 0xD5, 0x30, 0xB3, 0x14, 0x9C, 0x7B,
+    0xDF, 0xA5, 0xFA, 0x7E, 0x70, 0x1C, 0xAC, 0x55, 0xDA, 0xAB, 0x0D, 0x7A, 0xCE, 0x8F, 0x54, 0xB4,
+    0x56, 0x7F, 0x5A, 0x59, 0xF7, 0x4F, 0xD3, 0xD4, 0x69, 0x7F, 0x91, 0x20, 0xD5, 0xFE, 0x62, 0x8C,
+    0x8D, 0x4A, 0x1B, 0xF6, 0x6F, 0x9E, 0x11, 0x3B, 0x38, 0x1F, 0xE2, 0xFE, 0x80, 0x10, 0x42, 0x08,
+    0x21, 0x84, 0x10, 0x42, 0x08, 0x21, 0x84, 0x10, 0x6A, 0xA5, 0xDE, 0xFF, 0xDF, 0xCB, 0x78, 0x3C,
+    0x9D, 0xC7, 0x3E, 0xFF, 0x7F, 0xE7, 0x5D, 0xFD, 0xFF, 0xB5, 0x62, 0xF8, 0xFF, 0x6E, 0xB3, 0x7F,
+    0xBA, 0xEA, 0x7B, 0xD6, 0xDC, 0xEF, 0x57, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0xFD, 0x70, 0x2F,
+    0x53, 0x1E, 0x46, 0x8A, 0x00, 0x28, 0x00, 0x00,
+];
+
+/// The same items in a tar archive compressed by Zstandard.
+const TAR_ZST: &[u8] = &[
+    0x28, 0xB5, 0x2F, 0xFD, 0x64, 0x00, 0x27, 0xAD, 0x03, 0x00, 0xD2, 0x84, 0x0F, 0x10, 0xA0, 0x6F,
+    0x58, 0x8E, 0x9F, 0xEE, 0x35, 0x32, 0x92, 0x4F, 0xF6, 0x97, 0xAB, 0xFF, 0x0A, 0xA6, 0xDC, 0x50,
+    0xF6, 0x73, 0x90, 0xFA, 0x87, 0x41, 0xC6, 0x62, 0x2A, 0xAD, 0x86, 0x8E, 0x29, 0x64, 0x88, 0xF0,
+    0xEE, 0xCD, 0x26, 0xB8, 0xFF, 0x97, 0xFF, 0x39, 0x1D, 0x77, 0x53, 0x67, 0xEB, 0x65, 0xD2, 0x1C,
+    0x82, 0x30, 0xCE, 0x63, 0x14, 0x67, 0x00, 0xAB, 0x83, 0x11, 0xD2, 0x13, 0x20, 0x10, 0x6D, 0xAC,
+    0x5B, 0x0D, 0xD0, 0x64, 0x5F, 0x84, 0x8C, 0xCA, 0xC7, 0xAE, 0x0E, 0xD0, 0x1C, 0x40, 0xD3, 0xB4,
+    0xFA, 0x80, 0x25, 0x05, 0x7B, 0x53, 0x3D, 0x02, 0xA3, 0xF2, 0x1D, 0xA1, 0x92, 0x66, 0x80, 0x17,
+    0xD0, 0xA5, 0x38, 0xD8, 0x94, 0x5F, 0x25, 0x3C, 0xE0, 0xA0, 0x00, 0x86, 0x53, 0x90, 0x03, 0x24,
+    0xC0, 0xB4, 0x52,
+];
+
+/// The same items in a zip archive, where `release/app` is compressed into 38 bytes and
+/// `release/README` into 14 bytes.
+const ZIP: &[u8] = &[
+    0x50, 0x4B, 0x03, 0x04, 0x14, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x21, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x08, 0x00, 0x00, 0x00, 0x72, 0x65,
+    0x6C, 0x65, 0x61, 0x73, 0x65, 0x2F, 0x50, 0x4B, 0x03, 0x04, 0x14, 0x00, 0x00, 0x00, 0x08, 0x00,
+    0x00, 0x00, 0x21, 0x00, 0x9D, 0x1D, 0x9E, 0xA3, 0x26, 0x00, 0x00, 0x00, 0x88, 0x13, 0x00, 0x00,
+    0x0B, 0x00, 0x00, 0x00, 0x72, 0x65, 0x6C, 0x65, 0x61, 0x73, 0x65, 0x2F, 0x61, 0x70, 0x70, 0xED,
+    0xC6, 0x49, 0x01, 0x00, 0x20, 0x08, 0x00, 0xB0, 0x4A, 0x78, 0x20, 0xDA, 0xBF, 0x98, 0x29, 0xF8,
+    0x6D, 0xAF, 0xC5, 0x98, 0x6B, 0xE7, 0xA9, 0xFB, 0xC2, 0xCC, 0xCC, 0xCC, 0xCC, 0xCC, 0xCC, 0xCC,
+    0xCC, 0xCC, 0x9A, 0xF6, 0x01, 0x50, 0x4B, 0x03, 0x04, 0x14, 0x00, 0x00, 0x00, 0x08, 0x00, 0x00,
+    0x00, 0x21, 0x00, 0x50, 0x2C, 0x1D, 0x9C, 0x0E, 0x00, 0x00, 0x00, 0x2C, 0x01, 0x00, 0x00, 0x0E,
+    0x00, 0x00, 0x00, 0x72, 0x65, 0x6C, 0x65, 0x61, 0x73, 0x65, 0x2F, 0x52, 0x45, 0x41, 0x44, 0x4D,
+    0x45, 0x2B, 0x4A, 0x4D, 0x4C, 0x51, 0xC8, 0x4D, 0x55, 0x28, 0x1A, 0xA5, 0x09, 0xD2, 0x00, 0x50,
+    0x4B, 0x01, 0x02, 0x14, 0x03, 0x14, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x21, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x08, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x80, 0x01, 0x00, 0x00, 0x00, 0x00, 0x72, 0x65, 0x6C,
+    0x65, 0x61, 0x73, 0x65, 0x2F, 0x50, 0x4B, 0x01, 0x02, 0x14, 0x03, 0x14, 0x00, 0x00, 0x00, 0x08,
+    0x00, 0x00, 0x00, 0x21, 0x00, 0x9D, 0x1D, 0x9E, 0xA3, 0x26, 0x00, 0x00, 0x00, 0x88, 0x13, 0x00,
+    0x00, 0x0B, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x80, 0x01, 0x26,
+    0x00, 0x00, 0x00, 0x72, 0x65, 0x6C, 0x65, 0x61, 0x73, 0x65, 0x2F, 0x61, 0x70, 0x70, 0x50, 0x4B,
+    0x01, 0x02, 0x14, 0x03, 0x14, 0x00, 0x00, 0x00, 0x08, 0x00, 0x00, 0x00, 0x21, 0x00, 0x50, 0x2C,
+    0x1D, 0x9C, 0x0E, 0x00, 0x00, 0x00, 0x2C, 0x01, 0x00, 0x00, 0x0E, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x80, 0x01, 0x75, 0x00, 0x00, 0x00, 0x72, 0x65, 0x6C, 0x65,
+    0x61, 0x73, 0x65, 0x2F, 0x52, 0x45, 0x41, 0x44, 0x4D, 0x45, 0x50, 0x4B, 0x05, 0x06, 0x00, 0x00,
+    0x00, 0x00, 0x03, 0x00, 0x03, 0x00, 0xAB, 0x00, 0x00, 0x00, 0xAF, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+
+fn entry(path: &str, size: u64, compressed_size: Option<u64>) -> ArchiveEntry {
+    ArchiveEntry {
+        path: path.to_string(),
+        size,
+        compressed_size,
+    }
+}
+
+/// Create a header block of a tar archive.
+fn tar_header(name: &str, size: u64, type_flag: u8) -> Vec<u8> {
+    let mut header = vec![0; 512];
+    header[..name.len()].copy_from_slice(name.as_bytes());
+    header[124..135].copy_from_slice(format!("{:011o}", size).as_bytes());
+    header[156] = type_flag;
+    header[257..263].copy_from_slice(b"ustar\0");
+    set_checksum(&mut header);
+    header
+}
+
+/// Fill the checksum field of a header block.
+fn set_checksum(header: &mut [u8]) {
+    header[148..156].copy_from_slice(b"        ");
+    let checksum: u32 = header.iter().map(|byte| u32::from(*byte)).sum();
+    header[148..155].copy_from_slice(format!("{:06o}\0", checksum).as_bytes());
+}
+
+/// Create a record of a PAX extended header, whose length counts its own digits.
+fn pax_record(key: &str, value: &str) -> String {
+    let record = format!(" {}={}\n", key, value);
+    let mut length = record.len();
+    while length != record.len() + length.to_string().len() {
+        length = record.len() + length.to_string().len();
+    }
+    format!("{}{}", length, record)
+}
+
+/// Append an item with its contents padded to a whole block.
+fn tar_item(archive: &mut Vec<u8>, name: &str, type_flag: u8, contents: &[u8]) {
+    archive.extend(tar_header(name, contents.len() as u64, type_flag));
+    archive.extend(contents);
+    archive.extend(vec![0; (512 - contents.len() % 512) % 512]);
+}
+
+#[test]
+fn tar() {
+    let long_name = format!("release/{}", "long-name-".repeat(12));
+    let mut archive = Vec::new();
+    tar_item(&mut archive, "release/", b'5', &[]);
+    tar_item(&mut archive, "release/app", b'0', &[b'x'; 5000]);
+    tar_item(
+        &mut archive,
+        "././@LongLink",
+        b'L',
+        format!("{}\0", long_name).as_bytes(),
+    );
+    tar_item(&mut archive, "truncated", b'0', &[b'x'; 700]);
+    tar_item(&mut archive, "release/link", b'2', &[]);
+    archive.extend(vec![0; 1024]);
+    let actual = read_archive(Cursor::new(archive)).expect("read archive");
+    let expected = vec![
+        entry("release/", 0, None),
+        entry("release/app", 5000, None),
+        entry(&long_name, 700, None),
+        entry("release/link", 0, None),
+    ];
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn old_gnu_sparse_tar() {
+    let mut archive = tar_header("disk.img", 1024, b'S');
+    archive[257..265].copy_from_slice(b"ustar  \0");
+    archive[345..357].copy_from_slice(b"00000000000\0"); // an access time, not a prefix.
+    archive[482] = 1; // the map continues in the next block.
+    archive[483..495].copy_from_slice(b"00000200000\0");
+    set_checksum(&mut archive);
+    let mut map_extension = vec![0; 512];
+    map_extension[504] = 0; // the map ends in this block.
+    archive.extend(map_extension);
+    archive.extend(vec![b'x'; 1024]);
+    tar_item(&mut archive, "after", b'0', &[b'x'; 10]);
+    archive.extend(vec![0; 1024]);
+    let actual = read_archive(Cursor::new(archive)).expect("read archive");
+    let expected = vec![entry("disk.img", 65536, None), entry("after", 10, None)];
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn pax_sparse_tar() {
+    let records =
+        pax_record("GNU.sparse.name", "disk.img") + &pax_record("GNU.sparse.realsize", "65536");
+    let mut archive = Vec::new();
+    tar_item(
+        &mut archive,
+        "./PaxHeaders/disk.img",
+        b'x',
+        records.as_bytes(),
+    );
+    tar_item(
+        &mut archive,
+        "./GNUSparseFile.0/disk.img",
+        b'0',
+        &[b'x'; 1536],
+    );
+    tar_item(&mut archive, "after", b'0', &[b'x'; 10]);
+    archive.extend(vec![0; 1024]);
+    let actual = read_archive(Cursor::new(archive)).expect("read archive");
+    let expected = vec![entry("disk.img", 65536, None), entry("after", 10, None)];
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn truncated_tar() {
+    let mut archive = Vec::new();
+    tar_item(&mut archive, "release/app", b'0', &[b'x'; 5000]);
+    archive.truncate(3000);
+    let actual = read_archive(Cursor::new(archive)).map_err(|error| error.to_string());
+    assert_eq!(actual, Err("Invalid tar archive".to_string()));
+}
+
+#[test]
+fn oversized_tar_extension() {
+    let mut archive = tar_header("././@LongLink", 0o77_777_777_777, b'L');
+    archive.extend(vec![b'x'; 512]);
+    let actual = read_archive(Cursor::new(archive)).map_err(|error| error.to_string());
+    assert_eq!(actual, Err("Invalid tar archive".to_string()));
+}
+
+#[test]
+fn tar_gz() {
+    let actual = read_archive(Cursor::new(TAR_GZ)).expect("read archive");
+    let expected = vec![
+        entry("release/", 0, None),
+        entry("release/app", 5000, None),
+        entry("release/README", 300, None),
+    ];
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn corrupted_tar_gz() {
+    let mut archive = TAR_GZ.to_vec();
+    let checksum = archive.len() - 8;
+    archive[checksum] ^= 0xFF;
+    let actual = read_archive(Cursor::new(archive)).map_err(|error| error.to_string());
+    assert_eq!(actual, Err("Invalid gzip stream".to_string()));
+}
+
+#[test]
+fn tar_zst() {
+    let actual = read_archive(Cursor::new(TAR_ZST)).expect("read archive");
+    let expected = vec![
+        entry("release/", 0, None),
+        entry("release/app", 5000, None),
+        entry("release/README", 300, None),
+    ];
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn zip() {
+    let actual = read_archive(Cursor::new(ZIP)).expect("read archive");
+    let expected = vec![
+        entry("release/", 0, Some(0)),
+        entry("release/app", 5000, Some(38)),
+        entry("release/README", 300, Some(14)),
+    ];
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn zip_directory_beyond_archive() {
+    let mut archive = ZIP.to_vec();
+    let directory_size = archive.len() - 10;
+    archive[directory_size..directory_size + 4].copy_from_slice(&[0xF0, 0xFF, 0xFF, 0xFF]);
+    let actual = read_archive(Cursor::new(archive)).map_err(|error| error.to_string());
+    assert_eq!(actual, Err("Invalid zip archive".to_string()));
+}
+
+#[test]
+fn unsupported_compression() {
+    let archive = Cursor::new(b"BZh91AY&SY");
+    let actual = read_archive(archive).map_err(|error| error.to_string());
+    assert_eq!(
+        actual,
+        Err("bzip2 compression is not supported".to_string())
+    );
+}
+
+#[test]
+fn weighted_by_size() {
+    let actual = entry("./release//app", 5000, None)
+        .into_weighted(false)
+        .expect("convert entry");
+    assert_eq!(actual.key, vec!["release".to_string(), "app".to_string()]);
+    assert_eq!(actual.weight, 5000);
+}
+
+#[test]
+fn weighted_by_unrecorded_compressed_size() {
+    let actual = entry("release/app", 5000, None).into_weighted(true);
+    assert!(matches!(
+        actual,
+        Err(ArchiveError::CompressedSizeUnrecorded)
+    ));
+}
@@ -0,0 +1,49 @@
+use dirt::args::interval::{FromStrError::*, Interval};
+use pretty_assertions::assert_eq;
+use std::time::Duration;
+
+fn parse(text: &str) -> Duration {
+    text.parse::<Interval>().expect("create interval").into()
+}
+
+#[test]
+fn seconds_without_unit() {
+    assert_eq!(parse("2"), Duration::from_secs(2));
+    assert_eq!(parse("0.5"), Duration::from_millis(500));
+}
+
+#[test]
+fn with_unit() {
+    assert_eq!(parse("250ms"), Duration::from_millis(250));
+    assert_eq!(parse("3s"), Duration::from_secs(3));
+    assert_eq!(parse("1.5m"), Duration::from_secs(90));
+    assert_eq!(parse("1h"), Duration::from_secs(3600));
+}
+
+#[test]
+fn zero() {
+    let actual_error = "0".parse::<Interval>().expect_err("cause range error");
+    let actual_message = actual_error.to_string();
+    let expected_error = OutOfRange;
+    let expected_message = "out of range".to_string();
+    assert_eq!(
+        (actual_error, actual_message),
+        (expected_error, expected_message),
+    );
+}
+
+#[test]
+fn negative() {
+    let actual = "-1s".parse::<Interval>().expect_err("cause range error");
+    assert_eq!(actual, OutOfRange);
+}
+
+#[test]
+fn invalid_float_literal() {
+    let actual = "1d"
+        .parse::<Interval>()
+        .expect_err("cause syntax error")
+        .to_string();
+    let expected = "invalid float literal";
+    assert_eq!(actual, expected);
+}
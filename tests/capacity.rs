@@ -0,0 +1,70 @@
+#![cfg(target_os = "linux")]
+pub mod _utils;
+pub use _utils::*;
+
+use dirt::{
+    bytes_format::BytesFormat,
+    capacity::{Capacity, CapacitySettings},
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn query_capacity() {
+    let workspace = SampleWorkspace::default();
+    let capacity = Capacity::of(&workspace).expect("query capacity");
+    assert!(capacity.total > 0);
+    assert!(capacity.used <= capacity.total);
+    assert!(capacity.available <= capacity.total);
+    assert!(capacity.used_percentage() <= 100);
+}
+
+#[test]
+fn one_capacity_per_filesystem() {
+    let workspace = SampleWorkspace::default();
+    let roots = vec![
+        workspace.join("flat"),
+        workspace.join("nested"),
+        workspace.join("nonexistent"),
+    ];
+    let actual: Vec<_> = Capacity::of_roots(&roots)
+        .into_iter()
+        .map(|(root, capacity)| (root, capacity.is_ok()))
+        .collect();
+    let expected = vec![
+        (workspace.join("flat"), true),
+        (workspace.join("nonexistent"), false),
+    ];
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn header_of_current_capacities() {
+    let workspace = SampleWorkspace::default();
+    let header = CapacitySettings {
+        roots: vec![workspace.join("flat"), workspace.join("nonexistent")],
+        bytes_format: BytesFormat::PlainNumber,
+    }
+    .header();
+    let capacity = Capacity::of(&workspace).expect("query capacity");
+    assert_eq!(header.total, capacity.total);
+    let prefix = format!(
+        "{}: {} total, ",
+        workspace.join("flat").display(),
+        capacity.total
+    );
+    assert!(header.text.starts_with(&prefix), "{:?}", header.text);
+    assert_eq!(header.text.lines().count(), 1);
+    let failed_roots: Vec<_> = header.errors.into_iter().map(|(root, _)| root).collect();
+    assert_eq!(failed_roots, [workspace.join("nonexistent")]);
+}
+
+#[test]
+fn used_percentage() {
+    let capacity = Capacity {
+        total: 400,
+        used: 300,
+        available: 50,
+    };
+    assert_eq!(capacity.used_percentage(), 75);
+    assert_eq!(Capacity::default().used_percentage(), 0);
+}
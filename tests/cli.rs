@@ -0,0 +1,234 @@
+pub mod _utils;
+pub use _utils::*;
+
+use pretty_assertions::assert_eq;
+use std::{
+    fs::{read_to_string, write},
+    process::{Command, Output, Stdio},
+};
+
+/// Run the program in `workspace` with the standard output piped, i.e. not connected to a
+/// terminal.
+fn dirt_in(workspace: &SampleWorkspace, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_dirt"))
+        .current_dir(&***workspace)
+        .args(args)
+        .stdin(Stdio::null())
+        .output()
+        .expect("run the program")
+}
+
+/// Run the program in a new sample workspace with the standard output piped.
+fn dirt(args: &[&str]) -> Output {
+    dirt_in(&SampleWorkspace::default(), args)
+}
+
+#[test]
+fn watch_requires_text_output() {
+    let output = dirt(&["--watch=1", "--output-format=csv", "."]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(
+        stderr.trim_end(),
+        "[error] Watching is only available with --output-format=text",
+    );
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn trend_requires_text_output() {
+    let output = dirt(&[
+        "--history=history",
+        "--history-trend=2",
+        "--output-format=csv",
+        ".",
+    ]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(
+        stderr.trim_end(),
+        "[error] The trend is only available with --output-format=text",
+    );
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn csv_without_terminal() {
+    let output = dirt(&[
+        "--output-format=csv",
+        "--quantity=len",
+        "--minimal-ratio=0",
+        "flat",
+    ]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {}", stderr);
+    let stdout = String::from_utf8(output.stdout).expect("decode stdout");
+    let lines: Vec<_> = stdout.lines().collect();
+    assert_eq!(
+        lines[0],
+        "depth,path,size,formatted_size,percent_of_root,percent_of_parent,children",
+    );
+    assert!(lines[1].starts_with("0,flat,"));
+    let files: Vec<_> = lines[2..]
+        .iter()
+        .map(|line| line.splitn(4, ',').take(3).collect::<Vec<_>>().join(","))
+        .collect();
+    assert_eq!(
+        files,
+        ["1,flat/3,3", "1,flat/2,2", "1,flat/1,1", "1,flat/0,0"]
+    );
+}
+
+#[test]
+fn html_without_terminal() {
+    let output = dirt(&[
+        "--output-format=html",
+        "--quantity=len",
+        "--minimal-ratio=0",
+        "flat",
+    ]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {}", stderr);
+    let stdout = String::from_utf8(output.stdout).expect("decode stdout");
+    assert!(stdout.starts_with("<!DOCTYPE html>"));
+    assert!(stdout.contains("<title>flat</title>"));
+    assert!(stdout.contains(r#"{"n":"3","s":3,"#));
+}
+
+#[test]
+fn svg_treemap_without_terminal() {
+    let output = dirt(&[
+        "--output-format=svg-treemap",
+        "--quantity=len",
+        "--minimal-ratio=0",
+        "flat",
+    ]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {}", stderr);
+    let stdout = String::from_utf8(output.stdout).expect("decode stdout");
+    assert!(stdout.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg""#));
+    assert!(stdout.contains("<g><title>flat/3 3B "));
+    assert!(stdout.ends_with("</svg>\n"));
+}
+
+#[test]
+fn folded_without_terminal() {
+    let output = dirt(&[
+        "--output-format=folded",
+        "--quantity=len",
+        "--minimal-ratio=0",
+        "flat",
+    ]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {}", stderr);
+    let stdout = String::from_utf8(output.stdout).expect("decode stdout");
+    let files: Vec<_> = stdout
+        .lines()
+        .filter(|line| line.starts_with("flat;"))
+        .collect();
+    assert_eq!(files, ["flat;3 3", "flat;2 2", "flat;1 1"]);
+}
+
+#[test]
+fn prometheus_without_terminal() {
+    let workspace = SampleWorkspace::default();
+    let output = dirt_in(
+        &workspace,
+        &[
+            "--output-format=prometheus",
+            "--quantity=len",
+            "--minimal-ratio=0",
+            "--output-file=metrics.prom",
+            "flat",
+        ],
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {}", stderr);
+    assert!(output.stdout.is_empty());
+    let metrics = read_to_string(workspace.join("metrics.prom")).expect("read the metrics");
+    assert!(metrics.starts_with("# HELP dirt_bytes Measured quantity of a path.\n"));
+    assert!(metrics.contains("dirt_bytes{path=\"flat/3\",quantity=\"len\"} 3\n"));
+    assert!(metrics.contains("\ndirt_scanned_items 5\n"));
+    assert!(metrics.contains("\ndirt_scan_errors 0\n"));
+    assert!(!workspace.join("metrics.prom.tmp").exists());
+}
+
+#[test]
+fn prometheus_of_imported_hierarchy() {
+    let workspace = SampleWorkspace::default();
+    write(workspace.join("du.txt"), "3\tflat/3\n6\tflat\n").expect("write the output of du");
+    let output = dirt_in(
+        &workspace,
+        &[
+            "--output-format=prometheus",
+            "--minimal-ratio=0",
+            "--du-import=du.txt",
+        ],
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {}", stderr);
+    let stdout = String::from_utf8(output.stdout).expect("decode stdout");
+    assert!(stdout.contains("dirt_bytes{path=\"flat/3\",quantity=\"len\"} 3072\n"));
+    assert!(!stdout.contains("dirt_scan_duration_seconds"));
+    assert!(!stdout.contains("dirt_scanned_items"));
+    assert!(!stdout.contains("dirt_scan_errors"));
+}
+
+#[test]
+fn trend_into_output_file() {
+    let workspace = SampleWorkspace::default();
+    for _ in 0..2 {
+        let output = dirt_in(
+            &workspace,
+            &[
+                "--history=history",
+                "--quantity=len",
+                "--total-width=100",
+                "flat",
+            ],
+        );
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(output.status.success(), "stderr: {}", stderr);
+    }
+    let output = dirt_in(
+        &workspace,
+        &[
+            "--history=history",
+            "--history-trend=2",
+            "--quantity=len",
+            "--output-file=trend.txt",
+            "flat",
+        ],
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {}", stderr);
+    assert!(output.stdout.is_empty());
+    let trend = read_to_string(workspace.join("trend.txt")).expect("read the trend");
+    assert!(trend.starts_with("2 snapshots from "));
+    assert!(trend.contains("PATH\n"));
+}
+
+#[cfg(unix)]
+#[test]
+fn prometheus_metric_of_blocks() {
+    let output = dirt(&["--output-format=prometheus", "--quantity=blocks", "flat"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {}", stderr);
+    let stdout = String::from_utf8(output.stdout).expect("decode stdout");
+    assert!(stdout.starts_with("# HELP dirt_blocks Measured quantity of a path.\n"));
+    assert!(stdout.contains("\ndirt_blocks{path=\"flat\",quantity=\"blocks\"} "));
+    assert!(!stdout.contains("dirt_bytes"));
+}
+
+#[test]
+fn markdown_without_terminal() {
+    let output = dirt(&[
+        "--output-format=markdown-list",
+        "--quantity=len",
+        "--minimal-ratio=0",
+        "flat",
+    ]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {}", stderr);
+    let stdout = String::from_utf8(output.stdout).expect("decode stdout");
+    assert!(stdout.starts_with("- flat: "));
+    assert!(stdout.contains("\n  - 3: 3B ("));
+}
@@ -0,0 +1,86 @@
+use dirt::{data_tree::DataTree, size::Bytes};
+use pretty_assertions::assert_eq;
+
+type SampleName = String;
+type SampleData = Bytes;
+type SampleTree = DataTree<SampleName, SampleData>;
+
+fn dir(name: &'static str, children: Vec<SampleTree>) -> SampleTree {
+    SampleTree::dir(name.to_string(), 4096.into(), children)
+}
+
+fn file(name: &'static str, size: u64) -> SampleTree {
+    SampleTree::file(name.to_string(), size.into())
+}
+
+#[allow(clippy::ptr_arg)] // the signature is dictated by `into_par_collapsed_chains`.
+fn join_names(parent: &SampleName, child: &SampleName) -> SampleName {
+    format!("{}/{}", parent, child)
+}
+
+#[test]
+fn chain_of_directories() {
+    let chain = dir(
+        "main",
+        vec![dir(
+            "java",
+            vec![dir(
+                "com",
+                vec![dir(
+                    "example",
+                    vec![dir(
+                        "app",
+                        vec![file("App.java", 123), file("Util.java", 45)],
+                    )],
+                )],
+            )],
+        )],
+    );
+    let actual = dir("src", vec![chain, file("build.gradle", 67)])
+        .into_par_collapsed_chains(join_names)
+        .into_reflection();
+    let expected = SampleTree::dir(
+        "src".to_string(),
+        4096.into(),
+        vec![
+            SampleTree::dir(
+                "main/java/com/example/app".to_string(),
+                (4096 * 5).into(),
+                vec![file("App.java", 123), file("Util.java", 45)],
+            ),
+            file("build.gradle", 67),
+        ],
+    )
+    .into_reflection();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn directory_of_one_file() {
+    let actual = dir("root", vec![dir("dir", vec![file("file", 123)])])
+        .into_par_collapsed_chains(join_names)
+        .into_reflection();
+    let expected = dir("root", vec![dir("dir", vec![file("file", 123)])]).into_reflection();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn root_is_not_collapsed() {
+    let actual = dir(
+        "root",
+        vec![dir("a", vec![dir("b", vec![file("c", 1), file("d", 2)])])],
+    )
+    .into_par_collapsed_chains(join_names)
+    .into_reflection();
+    let expected = SampleTree::dir(
+        "root".to_string(),
+        4096.into(),
+        vec![SampleTree::dir(
+            "a/b".to_string(),
+            (4096 * 2).into(),
+            vec![file("c", 1), file("d", 2)],
+        )],
+    )
+    .into_reflection();
+    assert_eq!(actual, expected);
+}
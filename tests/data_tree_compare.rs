@@ -0,0 +1,83 @@
+use dirt::{
+    bytes_format::BytesFormat,
+    data_tree::DataTree,
+    size::{Bytes, Changed, Size},
+};
+use pretty_assertions::assert_eq;
+
+type SampleTree = DataTree<&'static str, Bytes>;
+
+fn dir(name: &'static str, children: Vec<SampleTree>) -> SampleTree {
+    SampleTree::dir(name, 4096.into(), children)
+}
+
+fn file(name: &'static str, size: u64) -> SampleTree {
+    SampleTree::file(name, size.into())
+}
+
+fn changes(tree: &DataTree<&'static str, Changed<Bytes>>) -> Vec<(&'static str, Changed<Bytes>)> {
+    let mut result = vec![(*tree.name(), tree.data())];
+    for child in tree.children() {
+        result.extend(changes(child));
+    }
+    result
+}
+
+fn changed(size: u64, previous: Option<u64>) -> Changed<Bytes> {
+    Changed::new(size.into(), previous.map(Bytes::new))
+}
+
+#[test]
+fn without_previous_tree() {
+    let tree = dir("root", vec![file("a", 1), file("b", 2)]);
+    let actual = changes(&tree.par_compare(None));
+    let expected = vec![
+        ("root", changed(4096 + 1 + 2, None)),
+        ("a", changed(1, None)),
+        ("b", changed(2, None)),
+    ];
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn with_previous_tree() {
+    let previous = dir(
+        "root",
+        vec![
+            file("grown", 100),
+            file("removed", 50),
+            dir("dir", vec![file("same", 7)]),
+        ],
+    );
+    let current = dir(
+        "root",
+        vec![
+            file("grown", 300),
+            dir("dir", vec![file("same", 7), file("new", 5)]),
+            dir("new dir", vec![file("file", 9)]),
+        ],
+    );
+    let actual = changes(&current.par_compare(Some(&previous)));
+    let expected = vec![
+        (
+            "root",
+            changed(4096 * 3 + 300 + 7 + 5 + 9, Some(4096 * 2 + 100 + 50 + 7)),
+        ),
+        ("grown", changed(300, Some(100))),
+        ("dir", changed(4096 + 7 + 5, Some(4096 + 7))),
+        ("same", changed(7, Some(7))),
+        ("new", changed(5, Some(0))),
+        ("new dir", changed(4096 + 9, Some(0))),
+        ("file", changed(9, Some(0))),
+    ];
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn display_changes() {
+    let display = |size, previous| changed(size, previous).display(BytesFormat::PlainNumber);
+    assert_eq!(display(123, None), "123");
+    assert_eq!(display(123, Some(100)), "  +23 123");
+    assert_eq!(display(123, Some(200)), "  -77 123");
+    assert_eq!(display(123, Some(123)), "      123");
+}
@@ -0,0 +1,98 @@
+use dirt::{data_tree::DataTree, size::Bytes};
+use pretty_assertions::assert_eq;
+
+type SampleTree = DataTree<&'static str, Bytes>;
+
+fn dir(name: &'static str, children: Vec<SampleTree>) -> SampleTree {
+    SampleTree::dir(name, 4096.into(), children)
+}
+
+fn file(name: &'static str, size: u64) -> SampleTree {
+    SampleTree::file(name, size.into())
+}
+
+fn sample_tree() -> SampleTree {
+    dir(
+        "root",
+        vec![dir("a", vec![file("x", 10), file("y", 20)]), file("b", 30)],
+    )
+}
+
+fn sort_by_name(children: &mut Vec<SampleTree>) {
+    children.sort_by_key(|child| *child.name());
+}
+
+fn update(path: &[&'static str], replacement: Option<SampleTree>) -> (bool, SampleTree) {
+    let mut tree = sample_tree();
+    let is_updated = tree.update_descendant(path, replacement, |_| Bytes::new(4096), sort_by_name);
+    (is_updated, tree)
+}
+
+#[test]
+fn replace() {
+    let actual = update(&["a", "x"], Some(file("x", 1000)));
+    let expected = (
+        true,
+        dir(
+            "root",
+            vec![
+                dir("a", vec![file("x", 1000), file("y", 20)]),
+                file("b", 30),
+            ],
+        ),
+    );
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn remove() {
+    let actual = update(&["a", "y"], None);
+    let expected = (
+        true,
+        dir("root", vec![dir("a", vec![file("x", 10)]), file("b", 30)]),
+    );
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn add() {
+    let actual = update(&["a", "w"], Some(file("w", 5)));
+    let expected = (
+        true,
+        dir(
+            "root",
+            vec![
+                dir("a", vec![file("w", 5), file("x", 10), file("y", 20)]),
+                file("b", 30),
+            ],
+        ),
+    );
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn own_data_is_refreshed() {
+    let mut tree = sample_tree();
+    let is_updated = tree.update_descendant(
+        &["b"],
+        None,
+        |prefix| Bytes::new(1 + prefix.len() as u64),
+        sort_by_name,
+    );
+    assert!(is_updated);
+    assert_eq!(u64::from(tree.data()), 1 + 4096 + 10 + 20);
+}
+
+#[test]
+fn missing_parent() {
+    let actual = update(&["c", "x"], Some(file("x", 1)));
+    let expected = (false, sample_tree());
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn empty_path() {
+    let actual = update(&[], Some(file("x", 1)));
+    let expected = (false, sample_tree());
+    assert_eq!(actual, expected);
+}
@@ -0,0 +1,145 @@
+use dirt::{
+    data_tree::{DataTree, DataTreeReflection},
+    du::{parse_du_output, DuEntry, DuHierarchy, ParseError},
+    os_string_display::OsStringDisplay,
+    size::Bytes,
+    weighted_tree::{WeightedEntry, WeightedTreeBuilder},
+};
+use pretty_assertions::assert_eq;
+use text_block_macros::text_block_fnl;
+
+type SampleReflection = DataTreeReflection<OsStringDisplay, Bytes>;
+
+fn du_entry(path: &str, size: u64) -> DuEntry {
+    DuEntry {
+        path: path.to_string(),
+        size,
+    }
+}
+
+fn weighted(key: &[&str], weight: u64) -> WeightedEntry {
+    WeightedEntry {
+        key: key.iter().map(|component| component.to_string()).collect(),
+        weight,
+    }
+}
+
+fn node(name: &str, data: u64, children: Vec<SampleReflection>) -> SampleReflection {
+    DataTreeReflection {
+        name: OsStringDisplay::os_string_from(name),
+        data: data.into(),
+        children,
+    }
+}
+
+fn sort_by_name(children: &mut Vec<DataTree<OsStringDisplay, Bytes>>) {
+    children.sort_by(|left, right| left.name().cmp(right.name()));
+}
+
+#[test]
+fn parse_bytes() {
+    let text = text_block_fnl! {
+        "3\t./src/main.rs"
+        "4096\t./src"
+        ""
+        "10\t./with\ttab"
+        "4109\t."
+    };
+    let actual = parse_du_output(text, 1);
+    let expected = Ok(vec![
+        du_entry("./src/main.rs", 3),
+        du_entry("./src", 4096),
+        du_entry("./with\ttab", 10),
+        du_entry(".", 4109),
+    ]);
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn parse_kibibytes() {
+    let actual = parse_du_output("8\t/var/log\n", 1024);
+    let expected = Ok(vec![du_entry("/var/log", 8 * 1024)]);
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn reject_invalid_lines() {
+    assert_eq!(
+        parse_du_output("4\ta\n4K\tb\n", 1),
+        Err(ParseError::InvalidLine(2)),
+    );
+    assert_eq!(parse_du_output("4 a\n", 1), Err(ParseError::InvalidLine(1)));
+    assert_eq!(parse_du_output("4\t\n", 1), Err(ParseError::InvalidLine(1)));
+}
+
+#[test]
+fn subtract_listed_descendants() {
+    let actual = DuHierarchy::new(vec![
+        du_entry("./a/x", 100),
+        du_entry("./a/y/z", 50),
+        du_entry("./a", 4096 + 100 + 50),
+        du_entry("./b", 20),
+        du_entry(".", 4096 + 4096 + 150 + 20),
+    ]);
+    let expected = DuHierarchy {
+        root: Some(".".to_string()),
+        entries: vec![
+            weighted(&["a", "x"], 100),
+            weighted(&["a", "y", "z"], 50),
+            weighted(&["a"], 4096),
+            weighted(&["b"], 20),
+            weighted(&[], 4096),
+        ],
+    };
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn directories_only() {
+    let entries = parse_du_output(
+        text_block_fnl! {
+            "8\t/srv/db/tables"
+            "12\t/srv/db"
+            "4\t/srv/www"
+            "20\t/srv"
+        },
+        1024,
+    )
+    .expect("parse du output");
+    let DuHierarchy { root, entries } = DuHierarchy::new(entries);
+    let tree: DataTree<OsStringDisplay, Bytes> = WeightedTreeBuilder {
+        name: OsStringDisplay::os_string_from(root.expect("single root")),
+        entries,
+        post_process_children: sort_by_name,
+    }
+    .into();
+    let actual = tree.into_reflection();
+    let expected = node(
+        "/srv",
+        20 * 1024,
+        vec![
+            node("db", 12 * 1024, vec![node("tables", 8 * 1024, Vec::new())]),
+            node("www", 4 * 1024, Vec::new()),
+        ],
+    );
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn several_roots() {
+    let actual = DuHierarchy::new(vec![
+        du_entry("a/x", 1),
+        du_entry("a", 3),
+        du_entry("b", 5),
+        du_entry("b", 7),
+    ]);
+    let expected = DuHierarchy {
+        root: None,
+        entries: vec![
+            weighted(&["a", "x"], 1),
+            weighted(&["a"], 2),
+            weighted(&["b"], 7),
+        ],
+    };
+    assert_eq!(actual, expected);
+}
@@ -0,0 +1,136 @@
+use dirt::{
+    bytes_format::BytesFormat,
+    data_tree::DataTree,
+    extension_breakdown::{extension_of, Bucket, ExtensionBreakdown},
+    os_string_display::OsStringDisplay,
+    size::Bytes,
+    visualizer::{ColumnWidthDistribution, Direction, Visualizer},
+};
+use maplit::btreemap;
+use pretty_assertions::assert_eq;
+use std::{ffi::OsStr, num::NonZeroUsize};
+use text_block_macros::text_block_fnl;
+
+fn multi_part_extensions() -> Vec<String> {
+    vec!["tar.gz".to_string(), ".tar.zst".to_string()]
+}
+
+fn extension(name: &str) -> Option<String> {
+    extension_of(OsStr::new(name), &multi_part_extensions())
+}
+
+#[test]
+fn extensions() {
+    assert_eq!(extension("video.mp4"), Some("mp4".to_string()));
+    assert_eq!(extension("VIDEO.MP4"), Some("mp4".to_string()));
+    assert_eq!(extension("release.tar.gz"), Some("tar.gz".to_string()));
+    assert_eq!(extension("release.tar.zst"), Some("tar.zst".to_string()));
+    assert_eq!(extension("release.tar.xz"), Some("xz".to_string()));
+    assert_eq!(extension("Makefile"), None);
+    assert_eq!(extension(".bashrc"), None);
+    assert_eq!(extension("trailing."), None);
+}
+
+fn sample_tree() -> DataTree<&'static str, Bytes> {
+    let dir = DataTree::<&'static str, Bytes>::fixed_size_dir_constructor(4096.into());
+    let file = |name, size: u64| DataTree::file(name, Bytes::new(size));
+    dir(
+        "root",
+        vec![
+            file("a.mp4", 1000),
+            file("Makefile", 10),
+            dir(
+                "nested",
+                vec![
+                    file("b.MP4", 2000),
+                    file("c.tar.gz", 300),
+                    dir("empty", vec![]),
+                ],
+            ),
+        ],
+    )
+}
+
+#[test]
+fn buckets() {
+    let actual = ExtensionBreakdown::from_tree(&sample_tree(), &multi_part_extensions());
+    let expected = ExtensionBreakdown {
+        buckets: btreemap! {
+            None => Bucket { data: Bytes::new(10), count: 1 },
+            Some("mp4".to_string()) => Bucket { data: Bytes::new(3000), count: 2 },
+            Some("tar.gz".to_string()) => Bucket { data: Bytes::new(300), count: 1 },
+        },
+        directories: Bucket {
+            data: Bytes::new(3 * 4096),
+            count: 3,
+        },
+    };
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn data_tree() {
+    let actual = ExtensionBreakdown::from_tree(&sample_tree(), &multi_part_extensions())
+        .into_data_tree(OsStringDisplay::os_string_from("(total)"), |children| {
+            children.sort_by(|left, right| left.data().cmp(&right.data()).reverse())
+        })
+        .into_reflection();
+    let expected = DataTree::dir(
+        OsStringDisplay::os_string_from("(total)"),
+        Bytes::new(0),
+        vec![
+            DataTree::file(
+                OsStringDisplay::os_string_from("(3 directories)"),
+                Bytes::new(12288),
+            ),
+            DataTree::file(
+                OsStringDisplay::os_string_from("*.mp4 (2 files)"),
+                Bytes::new(3000),
+            ),
+            DataTree::file(
+                OsStringDisplay::os_string_from("*.tar.gz (1 file)"),
+                Bytes::new(300),
+            ),
+            DataTree::file(
+                OsStringDisplay::os_string_from("(no extension) (1 file)"),
+                Bytes::new(10),
+            ),
+        ],
+    )
+    .into_reflection();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn total_is_preserved() {
+    let tree = sample_tree();
+    let total = ExtensionBreakdown::from_tree(&tree, &multi_part_extensions())
+        .into_data_tree(OsStringDisplay::os_string_from("(total)"), |_| {})
+        .data();
+    assert_eq!(total, tree.data());
+}
+
+#[test]
+fn rendered_counts() {
+    let tree = ExtensionBreakdown::from_tree(&sample_tree(), &multi_part_extensions())
+        .into_data_tree(OsStringDisplay::os_string_from("(total)"), |children| {
+            children.sort_by(|left, right| left.data().cmp(&right.data()).reverse())
+        });
+    let actual = Visualizer {
+        data_tree: &tree,
+        bytes_format: BytesFormat::PlainNumber,
+        direction: Direction::TopDown,
+        column_width_distribution: ColumnWidthDistribution::total(60),
+        max_depth: NonZeroUsize::new(10).expect("non-zero max_depth"),
+        proportion_total: None,
+    }
+    .to_string();
+    let expected = text_block_fnl! {
+        "15598 └─┬(total)                  │████████████████████│100%"
+        "12288   ├──(3 directories)        │    ████████████████│ 79%"
+        " 3000   ├──*.mp4 (2 files)        │                ████│ 19%"
+        "  300   ├──*.tar.gz (1 file)      │                    │  2%"
+        "   10   └──(no extension) (1 file)│                    │  0%"
+    };
+    assert_eq!(actual, expected);
+}
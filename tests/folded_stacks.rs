@@ -0,0 +1,75 @@
+pub mod _utils;
+pub use _utils::*;
+
+use dirt::{folded_stacks::FoldedStacks, output_format::OutputFormat};
+use pretty_assertions::assert_eq;
+use std::num::NonZeroUsize;
+use text_block_macros::text_block_fnl;
+
+#[test]
+fn every_leaf() {
+    let actual = render_format_sample(OutputFormat::Folded, 10);
+    let expected = text_block_fnl! {
+        "root;a, \"b\";x\t<y>\\x3Bz 600"
+        "root;a, \"b\";c\\d\\ne|*_ 150"
+        "root;2021. </script> 250"
+    };
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn within_max_depth() {
+    let actual = render_format_sample(OutputFormat::Folded, 2);
+    let expected = text_block_fnl! {
+        "root;a, \"b\" 750"
+        "root;2021. </script> 250"
+    };
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn own_size_of_directories() {
+    let tree = FormatSampleTree::dir(
+        "root",
+        4096.into(),
+        vec![FormatSampleTree::dir(
+            "a",
+            4096.into(),
+            vec![sample_file("b", 600)],
+        )],
+    );
+    let actual = FoldedStacks {
+        data_tree: &tree,
+        max_depth: NonZeroUsize::new(10).expect("non-zero max_depth"),
+    }
+    .to_string();
+    let expected = text_block_fnl! {
+        "root 4096"
+        "root;a 4096"
+        "root;a;b 600"
+    };
+    assert_eq!(actual, expected);
+}
+
+#[cfg(unix)]
+#[test]
+fn non_utf8_name() {
+    use dirt::{data_tree::DataTree, os_string_display::OsStringDisplay, size::Bytes};
+    use std::{ffi::OsString, os::unix::ffi::OsStringExt};
+    type SampleTree = DataTree<OsStringDisplay, Bytes>;
+    let tree = SampleTree::dir(
+        OsStringDisplay::os_string_from("root"),
+        0.into(),
+        vec![SampleTree::file(
+            OsString::from_vec(b"x;\xFF".to_vec()).into(),
+            123.into(),
+        )],
+    );
+    let actual = FoldedStacks {
+        data_tree: &tree,
+        max_depth: NonZeroUsize::new(10).expect("non-zero max_depth"),
+    }
+    .to_string();
+    let expected = "root;\"x\\x3B\\xFF\" 123\n";
+    assert_eq!(actual, expected);
+}
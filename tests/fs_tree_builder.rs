@@ -1,7 +1,16 @@
 pub mod _utils;
 pub use _utils::*;
 
-use dirt::size::Bytes;
+use dirt::{
+    bytes_format::BytesFormat,
+    size::{ByteDays, Bytes, Size},
+    size_getters::get_cold_bytes,
+};
+use pretty_assertions::assert_eq;
+use std::{
+    fs::metadata,
+    time::{Duration, SystemTime},
+};
 
 #[cfg(unix)]
 use dirt::size::Blocks;
@@ -27,3 +36,57 @@ fn blocks_as_blocks() {
     let workspace = SampleWorkspace::default();
     test_sample_tree::<Blocks, _>(&workspace, |metadata| metadata.blocks());
 }
+
+#[test]
+fn cold_bytes_as_byte_days() {
+    let workspace = SampleWorkspace::default();
+    let now = SystemTime::now() + Duration::from_secs(10 * 24 * 60 * 60);
+    let get_data = get_cold_bytes(now);
+    test_sample_tree::<ByteDays, _>(&workspace, |metadata| get_data(metadata).inner());
+}
+
+#[test]
+fn cold_bytes_of_sample_files() {
+    let workspace = SampleWorkspace::default();
+    let paths = ["flat/0", "flat/1", "flat/2", "flat/3", "nested/0/1"];
+    let latest_modification = paths
+        .iter()
+        .map(|path| metadata(workspace.join(path)).expect("get metadata"))
+        .map(|metadata| metadata.modified().expect("get modification time"))
+        .max()
+        .expect("there are files");
+    // The files were written within seconds of each other, which is negligible in byte-days.
+    let get_data = get_cold_bytes(latest_modification + Duration::from_secs(10 * 24 * 60 * 60));
+    let actual: Vec<_> = paths
+        .iter()
+        .map(|path| metadata(workspace.join(path)).expect("get metadata"))
+        .map(|metadata| get_data(&metadata))
+        .collect();
+    let expected: Vec<_> = [0, 10, 20, 30, 60]
+        .iter()
+        .copied()
+        .map(ByteDays::new)
+        .collect();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn display_byte_days() {
+    let display = |value, format| ByteDays::new(value).display(format).to_string();
+    assert_eq!(display(1_234_567, BytesFormat::PlainNumber), "1234567");
+    assert_eq!(display(1_234_567, BytesFormat::MetricUnits), "1MBd");
+    assert_eq!(display(3_000, BytesFormat::BinaryUnits), "3KBd");
+    assert_eq!(display(750, BytesFormat::MetricUnits), "750Bd");
+}
+
+#[test]
+fn cold_bytes_of_file() {
+    let workspace = SampleWorkspace::default();
+    let metadata = metadata(workspace.join("nested/0/1")).expect("get metadata");
+    let modified = metadata.modified().expect("get modification time");
+    let days = |days: u64| Duration::from_secs(days * 24 * 60 * 60);
+    let cold_bytes = |now| get_cold_bytes(now)(&metadata);
+    assert_eq!(cold_bytes(modified + days(10)), ByteDays::new(6 * 10));
+    assert_eq!(cold_bytes(modified), ByteDays::new(0));
+    assert_eq!(cold_bytes(modified - days(1)), ByteDays::new(0));
+}
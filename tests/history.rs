@@ -0,0 +1,225 @@
+pub mod _utils;
+pub use _utils::*;
+
+use dirt::{
+    bytes_format::BytesFormat,
+    capacity::Capacity,
+    data_tree::DataTree,
+    history::{format_date, HistoryStore, Snapshot, Trend},
+    os_string_display::OsStringDisplay,
+    size::Bytes,
+};
+use pretty_assertions::assert_eq;
+use std::{fs::write, path::PathBuf};
+use text_block_macros::text_block_fnl;
+
+type SampleTree = DataTree<OsStringDisplay, Bytes>;
+
+const DAY: u64 = 24 * 60 * 60;
+
+fn name(name: &str) -> OsStringDisplay {
+    OsStringDisplay::os_string_from(name)
+}
+
+fn dir(dir_name: &str, children: Vec<SampleTree>) -> SampleTree {
+    SampleTree::dir(name(dir_name), 4096.into(), children)
+}
+
+fn file(file_name: &str, size: u64) -> SampleTree {
+    SampleTree::file(name(file_name), size.into())
+}
+
+fn sample_tree(log_size: u64) -> SampleTree {
+    dir(
+        "root",
+        vec![
+            dir(
+                "var",
+                vec![dir("log", vec![file("syslog", log_size)]), file("lock", 0)],
+            ),
+            file("hello world.txt", 100),
+        ],
+    )
+}
+
+#[test]
+fn serialize_top_levels() {
+    let tree = sample_tree(1000);
+    let text = tree.to_text(2);
+    assert_eq!(
+        text,
+        text_block_fnl! {
+            "0 13388 root/"
+            "1 9192 var/"
+            r"1 100 hello\x20world.txt"
+        },
+    );
+    let expected = dir(
+        "root",
+        vec![
+            SampleTree::dir(name("var"), 9192.into(), Vec::new()),
+            file("hello world.txt", 100),
+        ],
+    );
+    assert_eq!(SampleTree::from_text(&text), Some(expected));
+    assert_eq!(SampleTree::from_text(&tree.to_text(usize::MAX)), Some(tree));
+}
+
+#[test]
+fn reject_invalid_trees() {
+    assert_eq!(SampleTree::from_text(""), None);
+    assert_eq!(SampleTree::from_text("1 10 orphan\n"), None);
+    assert_eq!(SampleTree::from_text("0 10 a/\n0 10 b/\n"), None);
+    assert_eq!(SampleTree::from_text("0 10 a\n1 10 b\n"), None);
+    assert_eq!(SampleTree::from_text("0 ten a\n"), None);
+}
+
+#[test]
+fn dates() {
+    assert_eq!(format_date(0), "1970-01-01");
+    assert_eq!(format_date(951_782_400), "2000-02-29");
+    assert_eq!(format_date(1_616_457_600 + DAY - 1), "2021-03-23");
+}
+
+#[test]
+fn record_and_load() {
+    let temp = Temp::new_dir().expect("create temporary directory");
+    let store = HistoryStore {
+        directory: temp.join("history"),
+    };
+    let roots = [PathBuf::from("/root")];
+    for (index, log_size) in [1000, 3000, 5000].iter().enumerate() {
+        let time = (index as u64 + 1) * DAY;
+        store
+            .record(time, "len", &roots, &sample_tree(*log_size), 3)
+            .expect("record a snapshot");
+    }
+    store
+        .record(DAY, "blocks", &roots, &sample_tree(1), 3)
+        .expect("record a snapshot of another label");
+    store
+        .record(
+            4 * DAY,
+            "len",
+            &[PathBuf::from("/other root")],
+            &sample_tree(1),
+            3,
+        )
+        .expect("record a snapshot of other roots");
+    write(store.directory.join("junk.snapshot"), "junk").expect("write an invalid snapshot");
+
+    let snapshots = store
+        .load::<Bytes>("len", &roots, 2)
+        .expect("load snapshots");
+    let actual: Vec<_> = snapshots
+        .iter()
+        .map(|snapshot| (snapshot.time, snapshot.tree.data()))
+        .collect();
+    let expected = vec![
+        (2 * DAY, Bytes::new(4096 * 3 + 3000 + 100)),
+        (3 * DAY, Bytes::new(4096 * 3 + 5000 + 100)),
+    ];
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn keep_snapshots_of_the_same_second() {
+    let temp = Temp::new_dir().expect("create temporary directory");
+    let store = HistoryStore {
+        directory: temp.join("history"),
+    };
+    let roots = [PathBuf::from("/root"), PathBuf::from("/with space")];
+    for log_size in &[1000, 3000] {
+        store
+            .record(DAY, "len", &roots, &sample_tree(*log_size), 3)
+            .expect("record a snapshot");
+    }
+    let snapshots = store
+        .load::<Bytes>("len", &roots, 3)
+        .expect("load snapshots");
+    let mut actual: Vec<_> = snapshots
+        .iter()
+        .map(|snapshot| (snapshot.roots.as_slice(), snapshot.tree.data()))
+        .collect();
+    actual.sort_by_key(|(_, data)| *data);
+    let expected = vec![
+        (&roots[..], Bytes::new(4096 * 3 + 1000 + 100)),
+        (&roots[..], Bytes::new(4096 * 3 + 3000 + 100)),
+    ];
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn trend() {
+    let snapshot = |time, log_size| Snapshot {
+        time,
+        label: "len".to_string(),
+        roots: vec![PathBuf::from("/root")],
+        tree: SampleTree::from_text(&sample_tree(log_size).to_text(3)).expect("parse tree"),
+    };
+    let snapshots = [
+        snapshot(1_616_457_600, 1000),
+        snapshot(1_616_457_600 + 4 * DAY, 9000),
+    ];
+    let trend = Trend {
+        snapshots: &snapshots,
+        capacity: Some(Capacity {
+            total: 1_000_000,
+            used: 800_000,
+            available: 200_000,
+        }),
+        now: 1_616_457_600 + 4 * DAY,
+        bytes_format: BytesFormat::PlainNumber,
+    };
+    let actual = trend.to_string();
+    let expected = text_block_fnl! {
+        "2 snapshots from 2021-03-23 to 2021-03-27 (4.0 days)"
+        "      SIZE     CHANGE    PER DAY  PATH"
+        "     21388      +8000      +2000  root"
+        "     17192      +8000      +2000  root/var"
+        "     13096      +8000      +2000  root/var/log"
+        "         0         +0         +0  root/var/lock"
+        "       100         +0         +0  root/hello world.txt"
+        "Filesystem: 1000000 total, 800000 used, 200000 available"
+        "At +2000 per day, the filesystem is full by 2021-07-05"
+    };
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn trend_over_every_snapshot() {
+    let snapshot = |time, log_size| Snapshot {
+        time,
+        label: "len".to_string(),
+        roots: vec![PathBuf::from("/root")],
+        tree: SampleTree::from_text(&sample_tree(log_size).to_text(3)).expect("parse tree"),
+    };
+    let snapshots = [
+        snapshot(1_616_457_600, 1000),
+        snapshot(1_616_457_600 + DAY, 9000),
+        snapshot(1_616_457_600 + 4 * DAY, 9000),
+    ];
+    let trend = Trend {
+        snapshots: &snapshots,
+        capacity: Some(Capacity {
+            total: 1_000_000,
+            used: 800_000,
+            available: 200_000,
+        }),
+        now: 1_616_457_600 + 4 * DAY,
+        bytes_format: BytesFormat::PlainNumber,
+    };
+    let actual = trend.to_string();
+    let expected = text_block_fnl! {
+        "3 snapshots from 2021-03-23 to 2021-03-27 (4.0 days)"
+        "      SIZE     CHANGE    PER DAY  PATH"
+        "     21388      +8000      +1538  root"
+        "     17192      +8000      +1538  root/var"
+        "     13096      +8000      +1538  root/var/log"
+        "         0         +0         +0  root/var/lock"
+        "       100         +0         +0  root/hello world.txt"
+        "Filesystem: 1000000 total, 800000 used, 200000 available"
+        "At +1538 per day, the filesystem is full by 2021-08-04"
+    };
+    assert_eq!(actual, expected);
+}
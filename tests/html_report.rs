@@ -0,0 +1,72 @@
+pub mod _utils;
+pub use _utils::*;
+
+use dirt::{
+    bytes_format::BytesFormat, html_report::HtmlReport, output_format::OutputFormat, size::Bytes,
+};
+use pretty_assertions::assert_eq;
+use std::num::NonZeroUsize;
+
+fn report(tree: &FormatSampleTree, max_depth: usize) -> HtmlReport<'_, &'static str, Bytes> {
+    HtmlReport {
+        data_tree: tree,
+        bytes_format: BytesFormat::MetricUnits,
+        max_depth: NonZeroUsize::new(max_depth).expect("non-zero max_depth"),
+    }
+}
+
+#[test]
+fn json() {
+    let tree = format_sample_tree();
+    let actual = report(&tree, 10).json();
+    let expected = concat!(
+        r#"{"n":"root","s":1000,"f":"1K","c":["#,
+        r#"{"n":"a, \"b\"","s":750,"f":"750B","c":["#,
+        r#"{"n":"x\u0009\u003cy>;z","s":600,"f":"600B"},"#,
+        r#"{"n":"c\\d\u000ae|*_","s":150,"f":"150B"},"#,
+        r#"{"n":"empty","s":0,"f":"0B"}]},"#,
+        r#"{"n":"2021. \u003c/script>","s":250,"f":"250B"}]}"#,
+    );
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn json_within_max_depth() {
+    let tree = format_sample_tree();
+    let actual = report(&tree, 2).json();
+    let expected = concat!(
+        r#"{"n":"root","s":1000,"f":"1K","c":["#,
+        r#"{"n":"a, \"b\"","s":750,"f":"750B"},"#,
+        r#"{"n":"2021. \u003c/script>","s":250,"f":"250B"}]}"#,
+    );
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn self_contained_page() {
+    let page = render_format_sample(OutputFormat::Html, 10);
+    let tree = format_sample_tree();
+    let json = HtmlReport {
+        bytes_format: BytesFormat::PlainNumber,
+        ..report(&tree, 10)
+    }
+    .json();
+    assert!(page.starts_with("<!DOCTYPE html>"));
+    assert!(page.contains("<title>root</title>"));
+    assert!(page.contains(&format!(
+        r#"<script type="application/json" id="data">{}</script>"#,
+        json,
+    )));
+    assert_eq!(page.matches("</script>").count(), 2);
+    assert!(!page.contains("__TITLE__"));
+    assert!(!page.contains("__DATA__"));
+    assert!(!page.contains("http://"));
+    assert!(!page.contains("https://"));
+}
+
+#[test]
+fn escape_title() {
+    let tree = sample_dir("root <&>", Vec::new());
+    let page = report(&tree, 10).to_string();
+    assert!(page.contains("<title>root &lt;&amp;&gt;</title>"));
+}
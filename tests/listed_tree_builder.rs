@@ -0,0 +1,86 @@
+pub mod _utils;
+pub use _utils::*;
+
+use dirt::{
+    data_tree::{DataTree, DataTreeReflection},
+    listed_tree_builder::{parse_file_list, ListedTreeBuilder},
+    os_string_display::OsStringDisplay,
+    reporter::ErrorOnlyReporter,
+    size::Bytes,
+};
+use pretty_assertions::assert_eq;
+use std::{fs::symlink_metadata, path::PathBuf};
+
+type SampleReflection = DataTreeReflection<OsStringDisplay, Bytes>;
+
+fn node(name: &str, data: u64, children: Vec<SampleReflection>) -> SampleReflection {
+    DataTreeReflection {
+        name: OsStringDisplay::os_string_from(name),
+        data: data.into(),
+        children,
+    }
+}
+
+#[test]
+fn measure_only_listed_items() {
+    let workspace = SampleWorkspace::default();
+    let len = |suffix: &str| {
+        symlink_metadata(workspace.join(suffix))
+            .expect("get metadata")
+            .len()
+    };
+    let paths = vec![
+        workspace.join("flat/1"),
+        workspace.join("./flat/3"),
+        workspace.join("nested"),
+        workspace.join("flat/1"),
+    ];
+    let tree: DataTree<OsStringDisplay, Bytes> = ListedTreeBuilder {
+        paths,
+        get_data: |metadata| metadata.len().into(),
+        reporter: ErrorOnlyReporter::new(|error| {
+            panic!("Unexpected call to report_error: {:?}", error)
+        }),
+        post_process_children,
+    }
+    .into();
+
+    // Descend from `.` through the directories that lead to the workspace.
+    let mut tree = tree.into_reflection();
+    for component in workspace.components() {
+        assert_eq!(tree.children.len(), 1, "{:?}", tree.name);
+        tree = tree.children.pop().expect("there is one child");
+        assert_eq!(tree.name.as_os_str(), component.as_os_str());
+    }
+
+    let expected = node(
+        &workspace.file_name().expect("get name").to_string_lossy(),
+        len("flat/1") + len("flat/3") + len("nested"),
+        vec![
+            node(
+                "flat",
+                len("flat/1") + len("flat/3"),
+                vec![
+                    node("1", len("flat/1"), Vec::new()),
+                    node("3", len("flat/3"), Vec::new()),
+                ],
+            ),
+            node("nested", len("nested"), Vec::new()),
+        ],
+    );
+    assert_eq!(tree, expected);
+}
+
+#[test]
+fn split_by_newlines() {
+    let actual = parse_file_list(b"a\nb c\r\n\n./d/e\n");
+    let expected: Vec<PathBuf> = vec!["a".into(), "b c".into(), "./d/e".into()];
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn split_by_nul_characters() {
+    let actual = parse_file_list(b"a\0b\nc\0\0d\0");
+    let expected: Vec<PathBuf> = vec!["a".into(), "b\nc".into(), "d".into()];
+    assert_eq!(actual, expected);
+}
@@ -0,0 +1,83 @@
+#![cfg(target_os = "linux")]
+pub mod _utils;
+pub use _utils::*;
+
+use dirt::{
+    data_tree::{DataTree, DataTreeReflection},
+    fs_tree_builder::FsTreeBuilder,
+    live::LiveTree,
+    os_string_display::OsStringDisplay,
+    reporter::{ErrorOnlyReporter, ErrorReport},
+    size::Bytes,
+    size_getters::GET_APPARENT_SIZE,
+};
+use pipe_trait::Pipe;
+use pretty_assertions::assert_eq;
+use std::{
+    fs::{create_dir, remove_file, rename, write},
+    path::Path,
+};
+
+fn scan(root: &Path) -> DataTreeReflection<OsStringDisplay, Bytes> {
+    FsTreeBuilder {
+        root: root.to_path_buf(),
+        get_data: GET_APPARENT_SIZE,
+        reporter: ErrorOnlyReporter::new(ErrorReport::SILENT),
+        post_process_children,
+    }
+    .pipe(DataTree::<OsStringDisplay, Bytes>::from)
+    .into_reflection()
+}
+
+#[test]
+fn apply_changes() {
+    let workspace = SampleWorkspace::default();
+    let mut live_tree = LiveTree::new(
+        vec![workspace.to_path_buf()],
+        GET_APPARENT_SIZE,
+        ErrorOnlyReporter::new(ErrorReport::SILENT),
+        post_process_children,
+    )
+    .expect("start live tree");
+
+    let current = |live_tree: &LiveTree<_, _, _, _>| live_tree.trees()[0].clone().into_reflection();
+    assert_eq!(current(&live_tree), scan(&workspace));
+
+    write(workspace.join("flat/3"), "abcdefghijklmnopqrstuvwxyz").expect("grow a file");
+    remove_file(workspace.join("flat/0")).expect("remove a file");
+    create_dir(workspace.join("new-dir")).expect("create a directory");
+    write(workspace.join("new-dir/file"), "content").expect("create a file");
+    rename(workspace.join("nested/0"), workspace.join("empty-dir/0")).expect("move a directory");
+
+    let summary = live_tree.update().expect("update live tree");
+    assert!(summary.events > 0);
+    assert_eq!(current(&live_tree), scan(&workspace));
+
+    write(workspace.join("empty-dir/0/1"), "").expect("truncate a moved file");
+    live_tree.update().expect("update live tree");
+    assert_eq!(current(&live_tree), scan(&workspace));
+}
+
+#[test]
+fn forget_directories_moved_out() {
+    let workspace = SampleWorkspace::default();
+    let outside = Temp::new_dir().expect("create an outside directory");
+    let mut live_tree = LiveTree::new(
+        vec![workspace.join("nested")],
+        GET_APPARENT_SIZE,
+        ErrorOnlyReporter::new(ErrorReport::SILENT),
+        post_process_children,
+    )
+    .expect("start live tree");
+
+    let current = |live_tree: &LiveTree<_, _, _, _>| live_tree.trees()[0].clone().into_reflection();
+
+    rename(workspace.join("nested/0"), outside.join("0")).expect("move a directory out");
+    live_tree.update().expect("update live tree");
+    assert_eq!(current(&live_tree), scan(&workspace.join("nested")));
+
+    write(outside.join("0/1"), "changed").expect("change a file outside");
+    let summary = live_tree.update().expect("update live tree");
+    assert_eq!(summary.rescanned, 0);
+    assert_eq!(current(&live_tree), scan(&workspace.join("nested")));
+}
@@ -0,0 +1,99 @@
+pub mod _utils;
+pub use _utils::*;
+
+use dirt::{
+    bytes_format::BytesFormat,
+    markdown::{Markdown, MarkdownStyle},
+    output_format::OutputFormat,
+};
+use pretty_assertions::assert_eq;
+use std::num::NonZeroUsize;
+use text_block_macros::text_block_fnl;
+
+fn markdown(
+    style: MarkdownStyle,
+    bar_width: Option<usize>,
+    max_depth: usize,
+    proportion_total: Option<u64>,
+) -> String {
+    Markdown {
+        data_tree: &format_sample_tree(),
+        bytes_format: BytesFormat::PlainNumber,
+        max_depth: NonZeroUsize::new(max_depth).expect("non-zero max_depth"),
+        proportion_total,
+        style,
+        bar_width: bar_width.map(|width| NonZeroUsize::new(width).expect("non-zero width")),
+    }
+    .to_string()
+}
+
+#[test]
+fn table() {
+    let actual = render_format_sample(OutputFormat::MarkdownTable, 10);
+    let expected = text_block_fnl! {
+        "| Name | Size | Percentage |"
+        "| :--- | ---: | ---: |"
+        "| root | 1000 | 100% |"
+        "| &emsp;a, \"b\" | 750 | 75% |"
+        "| &emsp;&emsp;x\t\\<y\\>;z | 600 | 60% |"
+        r"| &emsp;&emsp;c\\d\\ne\|\*\_ | 150 | 15% |"
+        "| &emsp;&emsp;empty | 0 | 0% |"
+        r"| &emsp;2021\. \</script\> | 250 | 25% |"
+    };
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn table_with_bars() {
+    let actual = markdown(MarkdownStyle::Table, Some(8), 10, None);
+    let expected = text_block_fnl! {
+        "| Name | Size | Percentage | Bar |"
+        "| :--- | ---: | ---: | :--- |"
+        "| root | 1000 | 100% | `████████` |"
+        "| &emsp;a, \"b\" | 750 | 75% | `██████  ` |"
+        "| &emsp;&emsp;x\t\\<y\\>;z | 600 | 60% | `████▊   ` |"
+        r"| &emsp;&emsp;c\\d\\ne\|\*\_ | 150 | 15% | `█▎      ` |"
+        "| &emsp;&emsp;empty | 0 | 0% | `        ` |"
+        r"| &emsp;2021\. \</script\> | 250 | 25% | `██      ` |"
+    };
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn list() {
+    let actual = render_format_sample(OutputFormat::MarkdownList, 10);
+    let expected = text_block_fnl! {
+        "- root: 1000 (100%)"
+        "  - a, \"b\": 750 (75%)"
+        "    - x\t\\<y\\>;z: 600 (60%)"
+        r"    - c\\d\\ne\|\*\_: 150 (15%)"
+        "    - empty: 0 (0%)"
+        r"  - 2021\. \</script\>: 250 (25%)"
+    };
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn list_with_bars() {
+    let actual = markdown(MarkdownStyle::List, Some(8), 10, None);
+    let expected = text_block_fnl! {
+        "- `████████` root: 1000 (100%)"
+        "  - `██████  ` a, \"b\": 750 (75%)"
+        "    - `████▊   ` x\t\\<y\\>;z: 600 (60%)"
+        r"    - `█▎      ` c\\d\\ne\|\*\_: 150 (15%)"
+        "    - `        ` empty: 0 (0%)"
+        r"  - `██      ` 2021\. \</script\>: 250 (25%)"
+    };
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn list_within_max_depth_relative_to_total() {
+    let actual = markdown(MarkdownStyle::List, None, 2, Some(4000));
+    let expected = text_block_fnl! {
+        "- root: 1000 (25%)"
+        "  - a, \"b\": 750 (19%)"
+        r"  - 2021\. \</script\>: 250 (6%)"
+    };
+    assert_eq!(actual, expected);
+}
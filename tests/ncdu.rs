@@ -0,0 +1,184 @@
+pub mod _utils;
+pub use _utils::*;
+
+use dirt::{
+    data_tree::{DataTree, DataTreeReflection},
+    ncdu::{
+        json::{Value, MAX_DEPTH},
+        scan, NcduEntry, NcduSize, ParseError,
+    },
+    os_string_display::OsStringDisplay,
+    reporter::{ErrorOnlyReporter, ErrorReport},
+    size::{Blocks, Bytes},
+    size_getters::GET_APPARENT_SIZE,
+};
+use pretty_assertions::assert_eq;
+use std::fs::symlink_metadata;
+
+type SampleReflection = DataTreeReflection<OsStringDisplay, Bytes>;
+
+fn node(name: &str, data: u64, children: Vec<SampleReflection>) -> SampleReflection {
+    DataTreeReflection {
+        name: OsStringDisplay::os_string_from(name),
+        data: data.into(),
+        children,
+    }
+}
+
+fn sort_by_name(children: &mut Vec<DataTree<OsStringDisplay, Bytes>>) {
+    children.sort_by(|left, right| left.name().cmp(right.name()));
+}
+
+const SAMPLE_DUMP: &str = r#"[1,2,{"progname":"ncdu","progver":"1.18","timestamp":1600000000},
+[{"name":"/srv","asize":4096,"dsize":4096,"dev":2049,"ino":2},
+{"name":"data.bin","asize":1000,"dsize":4096,"ino":12},
+[{"name":"b","asize":4096,"dsize":4096,"ino":3},
+{"name":"link-1","asize":300,"dsize":512,"ino":7,"hlnkc":true,"nlink":2}],
+[{"name":"c","asize":4096,"dsize":4096,"ino":4},
+{"name":"link-2","asize":300,"dsize":512,"ino":7,"hlnkc":true,"nlink":2},
+{"name":"café \"quoted\"","asize":5,"dsize":0,"ino":13}],
+[{"name":"mnt","asize":4096,"dsize":4096,"dev":2050,"ino":7},
+{"name":"other-device","asize":20,"dsize":4096,"ino":7,"hlnkc":true,"nlink":2}],
+{"name":"node_modules","excluded":"pattern"}]]
+"#;
+
+#[test]
+fn parse_apparent_sizes() {
+    let actual = NcduEntry::parse_dump(SAMPLE_DUMP.as_bytes())
+        .expect("parse dump")
+        .into_data_tree::<Bytes, _>(NcduSize::Apparent, sort_by_name)
+        .into_reflection();
+    let expected = node(
+        "/srv",
+        4096 + 1000 + (4096 + 300) + (4096 + 5) + (4096 + 20),
+        vec![
+            node("b", 4096 + 300, vec![node("link-1", 300, Vec::new())]),
+            node(
+                "c",
+                4096 + 5,
+                vec![
+                    node("caf\u{e9} \"quoted\"", 5, Vec::new()),
+                    node("link-2", 0, Vec::new()), // already counted as `b/link-1`.
+                ],
+            ),
+            node("data.bin", 1000, Vec::new()),
+            node("mnt", 4096 + 20, vec![node("other-device", 20, Vec::new())]),
+            node("node_modules", 0, Vec::new()),
+        ],
+    );
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn parse_disk_usage() {
+    let actual = NcduEntry::parse_dump(SAMPLE_DUMP.as_bytes())
+        .expect("parse dump")
+        .into_data_tree::<Blocks, _>(NcduSize::Blocks, |_: &mut Vec<_>| {})
+        .into_reflection();
+    let expected: Blocks = ((4 * 4096 + 4096 + 512 + 4096) / 512).into();
+    assert_eq!(actual.data, expected);
+}
+
+#[test]
+fn reject_invalid_dumps() {
+    let parse = |text: &str| NcduEntry::parse_dump(text.as_bytes());
+    assert_eq!(
+        parse(r#"[2,0,{},{"name":"/"}]"#),
+        Err(ParseError::UnsupportedVersion(2)),
+    );
+    assert_eq!(parse(r#"[1,2,{}]"#), Err(ParseError::InvalidDump));
+    assert_eq!(
+        parse(r#"[1,2,{},{"asize":1}]"#),
+        Err(ParseError::InvalidDump)
+    );
+    assert_eq!(parse(r#"{"name":"/"}"#), Err(ParseError::InvalidDump));
+    assert_eq!(
+        parse(r#"[1,2,{},{"name":"/"}"#),
+        Err(ParseError::InvalidDump)
+    );
+}
+
+#[test]
+fn reject_deeply_nested_documents() {
+    let nested = |depth: usize| "[".repeat(depth) + &"]".repeat(depth);
+    assert!(Value::parse(nested(MAX_DEPTH).as_bytes()).is_some());
+    assert_eq!(Value::parse(nested(MAX_DEPTH + 1).as_bytes()), None);
+    assert_eq!(
+        NcduEntry::parse_dump(nested(100 * MAX_DEPTH).as_bytes()),
+        Err(ParseError::InvalidDump),
+    );
+}
+
+#[test]
+fn write_and_read_dump() {
+    let original = NcduEntry::parse_dump(SAMPLE_DUMP.as_bytes()).expect("parse dump");
+    let mut dump = Vec::new();
+    original.write_dump(&mut dump, 42).expect("write dump");
+    let text = String::from_utf8(dump.clone()).expect("dump is valid UTF-8");
+    assert!(text.starts_with(r#"[1,2,{"progname":"dirt","#));
+    assert!(text.contains(r#"{"name":"café \"quoted\"","#));
+    let actual = NcduEntry::parse_dump(&dump).expect("parse written dump");
+    assert_eq!(actual, original);
+}
+
+#[test]
+fn scan_sample_workspace() {
+    let workspace = SampleWorkspace::default();
+    let root = workspace.join("nested");
+    let len = |suffix: &str| {
+        symlink_metadata(root.join(suffix))
+            .expect("get metadata")
+            .len()
+    };
+    let reporter = ErrorOnlyReporter::new(ErrorReport::SILENT);
+    let entry = scan(&root, "/nested", GET_APPARENT_SIZE, reporter);
+    let mut dump = Vec::new();
+    entry.write_dump(&mut dump, 0).expect("write dump");
+    let actual = NcduEntry::parse_dump(&dump)
+        .expect("parse written dump")
+        .into_data_tree::<Bytes, _>(NcduSize::Apparent, sort_by_name)
+        .into_reflection();
+    let expected = node(
+        "/nested",
+        len("") + len("0") + len("0/1"),
+        vec![node(
+            "0",
+            len("0") + len("0/1"),
+            vec![node("1", len("0/1"), Vec::new())],
+        )],
+    );
+    assert_eq!(actual, expected);
+}
+
+#[cfg(unix)]
+#[test]
+fn scan_progress_in_blocks() {
+    use dirt::{
+        reporter::{ParallelReporter, ProgressAndErrorReporter, ProgressReport},
+        size_getters::GET_BLOCK_COUNT,
+    };
+    use std::{os::unix::prelude::MetadataExt, time::Duration};
+
+    let workspace = SampleWorkspace::default();
+    let root = workspace.join("nested");
+    let blocks = |suffix: &str| {
+        symlink_metadata(root.join(suffix))
+            .expect("get metadata")
+            .blocks()
+    };
+    let reporter = ProgressAndErrorReporter::new(
+        ProgressReport::SILENT,
+        Duration::from_secs(1),
+        ErrorReport::SILENT,
+    );
+    let entry = scan(&root, "/nested", GET_BLOCK_COUNT, &reporter);
+    let progress = reporter.progress().expect("get progress");
+    reporter.destroy().expect("stop reporter");
+    let total = blocks("") + blocks("0") + blocks("0/1");
+    let progress_total: u64 = progress.total.into();
+    assert_eq!((progress.items, progress_total), (3, total));
+    let actual = entry
+        .into_data_tree::<Blocks, _>(NcduSize::Blocks, |_: &mut Vec<_>| {})
+        .data();
+    assert_eq!(actual, Blocks::new(total));
+}
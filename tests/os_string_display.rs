@@ -107,3 +107,25 @@ fn non_utf8_os_string_display() {
     let expected = r#""\xFF\xDD""#;
     assert_eq!(actual, expected);
 }
+
+#[test]
+fn partially_non_utf8_os_string() {
+    let actual = b"tab\tquote\"\xFF\xDDend\xE2\x82"
+        .to_vec()
+        .pipe(OsString::from_vec)
+        .pipe(OsStringDisplay)
+        .to_string();
+    let expected = r#""tab\tquote\"\xFF\xDDend\xE2\x82""#;
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn control_and_non_ascii_characters_of_non_utf8_os_string() {
+    let actual = b"esc\x1B\x7F\xC2\x85\xC3\xA9\xFF"
+        .to_vec()
+        .pipe(OsString::from_vec)
+        .pipe(OsStringDisplay)
+        .to_string();
+    let expected = r#""esc\u{1B}\u{7F}\u{85}é\xFF""#;
+    assert_eq!(actual, expected);
+}
@@ -0,0 +1,82 @@
+pub mod _utils;
+pub use _utils::*;
+
+use dirt::overlapping_roots::{remove_overlapping_roots, OverlapKind, OverlappingRoot};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn remove_descendants_and_duplicates() {
+    let workspace = SampleWorkspace::default();
+    let roots = vec![
+        workspace.join("nested/0"),
+        workspace.join("flat"),
+        workspace.join("nested"),
+        workspace.join("nested/../flat"),
+        workspace.join("empty-dir"),
+    ];
+    let (kept, dropped) = remove_overlapping_roots(&roots);
+    assert_eq!(
+        kept,
+        vec![
+            workspace.join("flat"),
+            workspace.join("nested"),
+            workspace.join("empty-dir"),
+        ],
+    );
+    assert_eq!(
+        dropped,
+        vec![
+            OverlappingRoot {
+                root: workspace.join("nested/0"),
+                kind: OverlapKind::Descendant,
+                covering_root: workspace.join("nested"),
+            },
+            OverlappingRoot {
+                root: workspace.join("nested/../flat"),
+                kind: OverlapKind::Identical,
+                covering_root: workspace.join("flat"),
+            },
+        ],
+    );
+}
+
+#[test]
+fn keep_unresolvable_roots() {
+    let workspace = SampleWorkspace::default();
+    let roots = vec![workspace.join("nonexistent"), workspace.join("flat")];
+    let (kept, dropped) = remove_overlapping_roots(&roots);
+    assert_eq!(kept, roots);
+    assert_eq!(dropped, Vec::new());
+}
+
+#[cfg(unix)]
+#[test]
+fn remove_hard_links() {
+    use std::fs::hard_link;
+    let workspace = SampleWorkspace::default();
+    hard_link(workspace.join("flat/3"), workspace.join("empty-dir/3")).expect("create hard link");
+    let roots = vec![workspace.join("flat/3"), workspace.join("empty-dir/3")];
+    let (kept, dropped) = remove_overlapping_roots(&roots);
+    assert_eq!(kept, vec![workspace.join("flat/3")]);
+    assert_eq!(
+        dropped,
+        vec![OverlappingRoot {
+            root: workspace.join("empty-dir/3"),
+            kind: OverlapKind::Identical,
+            covering_root: workspace.join("flat/3"),
+        }],
+    );
+}
+
+#[test]
+fn display_warning() {
+    let overlapping_root = OverlappingRoot {
+        root: "a/b".into(),
+        kind: OverlapKind::Descendant,
+        covering_root: "a".into(),
+    };
+    assert_eq!(
+        overlapping_root.to_string(),
+        r#"Ignored "a/b", which is inside "a""#,
+    );
+}
@@ -0,0 +1,190 @@
+use dirt::{
+    data_tree::DataTree,
+    os_string_display::OsStringDisplay,
+    ownership::{group_by_owner, owner_ids, AccountNames, OwnerKind},
+    size::{Bytes, Owned, Owner},
+};
+use maplit::hashmap;
+use pretty_assertions::assert_eq;
+
+type SampleData = Owned<Bytes>;
+type SampleTree = DataTree<OsStringDisplay, SampleData>;
+
+const ROOT: Owner = Owner { uid: 0, gid: 0 };
+const ALICE: Owner = Owner {
+    uid: 1000,
+    gid: 100,
+};
+const BOB: Owner = Owner {
+    uid: 1001,
+    gid: 100,
+};
+
+fn name(name: &str) -> OsStringDisplay {
+    OsStringDisplay::os_string_from(name)
+}
+
+fn dir(dir_name: &str, owner: Owner, children: Vec<SampleTree>) -> SampleTree {
+    SampleTree::dir(name(dir_name), Owned::new(4096.into(), owner), children)
+}
+
+fn file(file_name: &str, owner: Owner, size: u64) -> SampleTree {
+    SampleTree::file(name(file_name), Owned::new(size.into(), owner))
+}
+
+fn sample_tree() -> SampleTree {
+    dir(
+        "home",
+        ROOT,
+        vec![
+            dir(
+                "alice",
+                ALICE,
+                vec![file("notes.txt", ALICE, 300), file("shared.txt", BOB, 50)],
+            ),
+            dir("bob", BOB, vec![file("video.mp4", BOB, 7000)]),
+        ],
+    )
+}
+
+fn sample_names() -> AccountNames {
+    AccountNames {
+        users: hashmap! {
+            0 => "root".to_string(),
+            1000 => "alice".to_string(),
+        },
+        groups: hashmap! {
+            0 => "root".to_string(),
+            100 => "users".to_string(),
+        },
+    }
+}
+
+fn sort_by_name(children: &mut Vec<SampleTree>) {
+    children.sort_by(|left, right| left.name().cmp(right.name()));
+}
+
+fn sizes(tree: &SampleTree) -> Vec<(String, u64)> {
+    let mut result = vec![(tree.name().to_string(), u64::from(tree.data().size))];
+    for child in tree.children() {
+        result.extend(
+            sizes(child)
+                .into_iter()
+                .map(|(child_name, size)| (format!("{}/{}", tree.name(), child_name), size)),
+        );
+    }
+    result
+}
+
+fn owners(tree: &SampleTree) -> Vec<(String, Option<u32>)> {
+    let owner = tree.data().owner.map(|owner| owner.uid);
+    let mut result = vec![(tree.name().to_string(), owner)];
+    for child in tree.children() {
+        result.extend(
+            owners(child)
+                .into_iter()
+                .map(|(child_name, owner)| (format!("{}/{}", tree.name(), child_name), owner)),
+        );
+    }
+    result
+}
+
+#[test]
+fn unknown_names_are_identifiers() {
+    let names = sample_names();
+    assert_eq!(names.name(OwnerKind::User, 1000), "alice");
+    assert_eq!(names.name(OwnerKind::User, 1001), "1001");
+    assert_eq!(names.name(OwnerKind::Group, 100), "users");
+}
+
+#[test]
+fn group_by_user() {
+    let tree = group_by_owner(
+        &sample_tree(),
+        OwnerKind::User,
+        &sample_names(),
+        sort_by_name,
+    );
+    let actual = sizes(&tree);
+    let expected = vec![
+        ("home".to_string(), 4096 * 3 + 300 + 50 + 7000),
+        ("home/1001".to_string(), 4096 + 50 + 7000),
+        ("home/1001/alice".to_string(), 50),
+        ("home/1001/alice/shared.txt".to_string(), 50),
+        ("home/1001/bob".to_string(), 4096 + 7000),
+        ("home/1001/bob/video.mp4".to_string(), 7000),
+        ("home/alice".to_string(), 4096 + 300),
+        ("home/alice/alice".to_string(), 4096 + 300),
+        ("home/alice/alice/notes.txt".to_string(), 300),
+        ("home/root".to_string(), 4096),
+    ];
+    assert_eq!(actual, expected);
+    let actual = owners(&tree);
+    let expected = vec![
+        ("home".to_string(), None),
+        ("home/1001".to_string(), None),
+        ("home/1001/alice".to_string(), None),
+        ("home/1001/alice/shared.txt".to_string(), Some(BOB.uid)),
+        ("home/1001/bob".to_string(), Some(BOB.uid)),
+        ("home/1001/bob/video.mp4".to_string(), Some(BOB.uid)),
+        ("home/alice".to_string(), None),
+        ("home/alice/alice".to_string(), Some(ALICE.uid)),
+        ("home/alice/alice/notes.txt".to_string(), Some(ALICE.uid)),
+        ("home/root".to_string(), None),
+    ];
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn group_by_group() {
+    let tree = group_by_owner(
+        &sample_tree(),
+        OwnerKind::Group,
+        &sample_names(),
+        sort_by_name,
+    );
+    let actual = sizes(&tree);
+    let expected = vec![
+        ("home".to_string(), 4096 * 3 + 300 + 50 + 7000),
+        ("home/root".to_string(), 4096),
+        ("home/users".to_string(), 4096 * 2 + 300 + 50 + 7000),
+        ("home/users/alice".to_string(), 4096 + 300 + 50),
+        ("home/users/alice/notes.txt".to_string(), 300),
+        ("home/users/alice/shared.txt".to_string(), 50),
+        ("home/users/bob".to_string(), 4096 + 7000),
+        ("home/users/bob/video.mp4".to_string(), 7000),
+    ];
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn nodes_without_owners() {
+    let tree = SampleTree::dir(
+        name("(total)"),
+        Owned::default(),
+        vec![dir("alice", ALICE, vec![file("notes.txt", ALICE, 300)])],
+    );
+    assert_eq!(
+        owner_ids(&tree, OwnerKind::User)
+            .into_iter()
+            .collect::<Vec<_>>(),
+        [1000],
+    );
+    let tree = group_by_owner(&tree, OwnerKind::User, &sample_names(), sort_by_name);
+    let actual = sizes(&tree);
+    let expected = vec![
+        ("(total)".to_string(), 4096 + 300),
+        ("(total)/alice".to_string(), 4096 + 300),
+        ("(total)/alice/alice".to_string(), 4096 + 300),
+        ("(total)/alice/alice/notes.txt".to_string(), 300),
+    ];
+    assert_eq!(actual, expected);
+}
+
+#[cfg(unix)]
+#[test]
+fn lookup_superuser() {
+    let names = AccountNames::lookup(OwnerKind::User, vec![0]);
+    assert_eq!(names.name(OwnerKind::User, 0), "root");
+    assert!(names.groups.is_empty());
+}
@@ -0,0 +1,78 @@
+pub mod _utils;
+pub use _utils::*;
+
+use dirt::{output_format::OutputFormat, prometheus::PrometheusMetrics, reporter::ProgressReport};
+use pretty_assertions::assert_eq;
+use std::{num::NonZeroUsize, time::Duration};
+use text_block_macros::text_block_fnl;
+
+#[test]
+fn tree_and_scan() {
+    let tree = format_sample_tree();
+    let actual = PrometheusMetrics {
+        data_tree: &tree,
+        max_depth: NonZeroUsize::new(10).expect("non-zero max_depth"),
+        quantity: "len",
+        unit: "bytes",
+        progress: Some(ProgressReport {
+            items: 6,
+            total: 1000.into(),
+            errors: 2,
+        }),
+        scan_duration: Some(Duration::from_millis(1250)),
+    }
+    .to_string();
+    let expected = text_block_fnl! {
+        "# HELP dirt_bytes Measured quantity of a path."
+        "# TYPE dirt_bytes gauge"
+        r#"dirt_bytes{path="root",quantity="len"} 1000"#
+        r#"dirt_bytes{path="root/a, \"b\"",quantity="len"} 750"#
+        "dirt_bytes{path=\"root/a, \\\"b\\\"/x\t<y>;z\",quantity=\"len\"} 600"
+        r#"dirt_bytes{path="root/a, \"b\"/c\\d\ne|*_",quantity="len"} 150"#
+        r#"dirt_bytes{path="root/a, \"b\"/empty",quantity="len"} 0"#
+        r#"dirt_bytes{path="root/2021. </script>",quantity="len"} 250"#
+        "# HELP dirt_scan_duration_seconds Duration of the scan."
+        "# TYPE dirt_scan_duration_seconds gauge"
+        "dirt_scan_duration_seconds 1.25"
+        "# HELP dirt_scanned_items Number of scanned items."
+        "# TYPE dirt_scanned_items gauge"
+        "dirt_scanned_items 6"
+        "# HELP dirt_scan_errors Number of errors of the scan."
+        "# TYPE dirt_scan_errors gauge"
+        "dirt_scan_errors 2"
+    };
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn within_max_depth_without_scan() {
+    let actual = render_format_sample(OutputFormat::Prometheus, 2);
+    let expected = text_block_fnl! {
+        "# HELP dirt_bytes Measured quantity of a path."
+        "# TYPE dirt_bytes gauge"
+        r#"dirt_bytes{path="root",quantity="len"} 1000"#
+        r#"dirt_bytes{path="root/a, \"b\"",quantity="len"} 750"#
+        r#"dirt_bytes{path="root/2021. </script>",quantity="len"} 250"#
+    };
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn metric_named_after_unit() {
+    let tree = format_sample_tree();
+    let actual = PrometheusMetrics {
+        data_tree: &tree,
+        max_depth: NonZeroUsize::new(1).expect("non-zero max_depth"),
+        quantity: "blocks",
+        unit: "blocks",
+        progress: None,
+        scan_duration: None,
+    }
+    .to_string();
+    let expected = text_block_fnl! {
+        "# HELP dirt_blocks Measured quantity of a path."
+        "# TYPE dirt_blocks gauge"
+        r#"dirt_blocks{path="root",quantity="blocks"} 1000"#
+    };
+    assert_eq!(actual, expected);
+}
@@ -0,0 +1,147 @@
+pub mod _utils;
+pub use _utils::*;
+
+use dirt::{
+    args::Fraction,
+    data_tree::{DataTree, DataTreeReflection},
+    fs_tree_builder::FsTreeBuilder,
+    os_string_display::OsStringDisplay,
+    reporter::{ErrorOnlyReporter, ErrorReport},
+    scan_cache::{CachedDirectory, CachedFsTreeBuilder, DirectoryKey, ScanCache},
+    size::Bytes,
+    size_getters::GET_APPARENT_SIZE,
+};
+use maplit::hashmap;
+use pipe_trait::Pipe;
+use pretty_assertions::assert_eq;
+use std::{fs::write, path::Path};
+
+fn scan(root: &Path) -> DataTreeReflection<OsStringDisplay, Bytes> {
+    FsTreeBuilder {
+        root: root.to_path_buf(),
+        get_data: GET_APPARENT_SIZE,
+        reporter: ErrorOnlyReporter::new(ErrorReport::SILENT),
+        post_process_children,
+    }
+    .pipe(DataTree::<OsStringDisplay, Bytes>::from)
+    .into_reflection()
+}
+
+fn cached_scan(
+    root: &Path,
+    cache: &ScanCache,
+    sample_ratio: f32,
+) -> (DataTreeReflection<OsStringDisplay, Bytes>, ScanCache) {
+    let (tree, cache) = CachedFsTreeBuilder {
+        root: root.to_path_buf(),
+        get_data: GET_APPARENT_SIZE,
+        reporter: ErrorOnlyReporter::new(ErrorReport::SILENT),
+        post_process_children,
+        cache,
+        sample_ratio: Fraction::new(sample_ratio).expect("create sample ratio"),
+    }
+    .build();
+    (tree.into_reflection(), cache)
+}
+
+fn name(name: &str) -> OsStringDisplay {
+    OsStringDisplay::os_string_from(name)
+}
+
+#[test]
+fn text_round_trip() {
+    let cache = ScanCache {
+        label: "len".to_string(),
+        directories: hashmap! {
+            "root dir".into() => CachedDirectory {
+                key: DirectoryKey {
+                    device: 2049,
+                    inode: 131,
+                    modified: 1_600_000_000_123_456_789,
+                    changed: -1,
+                },
+                files: vec![(name("a file"), 12), (name("back\\slash"), 0)],
+                subdirectories: vec![name("sub")],
+            },
+            "root dir/sub".into() => CachedDirectory {
+                key: DirectoryKey::default(),
+                files: Vec::new(),
+                subdirectories: Vec::new(),
+            },
+        },
+    };
+    let text = cache.to_text();
+    assert_eq!(
+        text.lines().collect::<Vec<_>>(),
+        [
+            r"dirt-scan-cache-v1 len",
+            r"D 2049 131 1600000000123456789 -1 root\x20dir",
+            r"F 12 a\x20file",
+            r"F 0 back\x5cslash",
+            r"S sub",
+            r"D 0 0 0 0 root\x20dir/sub",
+        ],
+    );
+    assert_eq!(ScanCache::from_text(&text), Ok(cache));
+}
+
+#[test]
+fn invalid_text() {
+    use dirt::scan_cache::ParseError::*;
+    assert_eq!(ScanCache::from_text("not a cache"), Err(InvalidHeader));
+    assert_eq!(
+        ScanCache::from_text("dirt-scan-cache-v1 len\nF 12 orphan\n"),
+        Err(InvalidLine(2)),
+    );
+    assert_eq!(
+        ScanCache::from_text("dirt-scan-cache-v1 len\nD 0 0 x 0 root\n"),
+        Err(InvalidLine(2)),
+    );
+}
+
+#[test]
+fn reuse_unchanged_directories() {
+    let workspace = SampleWorkspace::default();
+    let empty = ScanCache::new("len".to_string());
+
+    let (first, cache) = cached_scan(&workspace, &empty, 0.0);
+    assert_eq!(first, scan(&workspace));
+    assert_eq!(cache.directories.len(), 5);
+
+    let (second, reused) = cached_scan(&workspace, &cache, 0.0);
+    assert_eq!(second, first);
+    assert_eq!(reused, cache);
+
+    let save_path = workspace.join("cache");
+    cache.save(&save_path).expect("save the cache");
+    assert_eq!(ScanCache::load(&save_path, "len".to_string()), cache);
+    assert_eq!(
+        ScanCache::load(&save_path, "blocks".to_string()),
+        ScanCache::new("blocks".to_string()),
+    );
+}
+
+#[test]
+fn detect_changed_entries() {
+    let workspace = SampleWorkspace::default();
+    let empty = ScanCache::new("len".to_string());
+    let (_, cache) = cached_scan(&workspace, &empty, 0.0);
+
+    write(workspace.join("nested/0/2"), "new file").expect("add a file");
+    let (actual, _) = cached_scan(&workspace, &cache, 0.0);
+    assert_eq!(actual, scan(&workspace));
+}
+
+#[test]
+fn detect_changed_contents_by_sampling() {
+    let workspace = SampleWorkspace::default();
+    let empty = ScanCache::new("len".to_string());
+    let (original, cache) = cached_scan(&workspace, &empty, 0.0);
+
+    // Rewriting a file does not change its directory, so only sampling can notice it.
+    write(workspace.join("nested/0/1"), "abcdefghi").expect("grow a file");
+    let (unsampled, _) = cached_scan(&workspace, &cache, 0.0);
+    assert_eq!(unsampled, original);
+    let (sampled, _) = cached_scan(&workspace, &cache, 0.99);
+    assert_eq!(sampled, scan(&workspace));
+}
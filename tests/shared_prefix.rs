@@ -0,0 +1,81 @@
+use dirt::{
+    data_tree::DataTree, os_string_display::OsStringDisplay, shared_prefix::nest_by_shared_prefix,
+    size::Bytes,
+};
+use pretty_assertions::assert_eq;
+use std::path::PathBuf;
+
+type SampleTree = DataTree<OsStringDisplay, Bytes>;
+
+fn dir(name: &'static str, children: Vec<SampleTree>) -> SampleTree {
+    SampleTree::dir(OsStringDisplay::os_string_from(name), 0.into(), children)
+}
+
+fn file(name: &'static str, size: u64) -> SampleTree {
+    SampleTree::file(OsStringDisplay::os_string_from(name), size.into())
+}
+
+fn root(path: &'static str, tree: SampleTree) -> (PathBuf, SampleTree) {
+    (PathBuf::from(path), tree)
+}
+
+fn nest(roots: Vec<(PathBuf, SampleTree)>) -> SampleTree {
+    nest_by_shared_prefix(
+        roots,
+        OsStringDisplay::os_string_from("(total)"),
+        |_: &mut Vec<SampleTree>| {},
+    )
+}
+
+#[test]
+fn nest_under_intermediate_directories() {
+    let actual = nest(vec![
+        root("/srv/a/x", file("x", 100)),
+        root("/srv/a/y", file("y", 20)),
+        root("/srv/b", file("b", 3)),
+    ])
+    .into_reflection();
+    let expected = dir(
+        "/srv",
+        vec![file("b", 3), dir("a", vec![file("x", 100), file("y", 20)])],
+    )
+    .into_reflection();
+    assert_eq!(actual, expected);
+    assert_eq!(expected.data, Bytes::from(123));
+}
+
+#[test]
+fn keep_a_component_for_every_root() {
+    let actual = nest(vec![
+        root("/srv/a", dir("a", vec![file("file", 100)])),
+        root("/srv/b/c", file("c", 20)),
+    ])
+    .into_reflection();
+    let expected = dir(
+        "/srv",
+        vec![
+            dir("a", vec![file("file", 100)]),
+            dir("b", vec![file("c", 20)]),
+        ],
+    )
+    .into_reflection();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn fall_back_to_the_root_label() {
+    let actual = nest(vec![
+        root("relative/x", file("x", 100)),
+        root("/absolute/y", file("y", 20)),
+    ])
+    .into_reflection();
+    let expected = dir(
+        "(total)",
+        vec![
+            dir("relative", vec![file("x", 100)]),
+            dir("/", vec![dir("absolute", vec![file("y", 20)])]),
+        ],
+    )
+    .into_reflection();
+    assert_eq!(actual, expected);
+}
@@ -0,0 +1,145 @@
+use dirt::{
+    data_tree::DataTree,
+    size::{Bytes, Timed},
+    sort_key::{natural_cmp, SortKey},
+};
+use pretty_assertions::assert_eq;
+use std::{
+    cmp::Ordering,
+    time::{Duration, SystemTime},
+};
+
+type SampleTree = DataTree<&'static str, Bytes>;
+
+fn sample_children() -> Vec<SampleTree> {
+    vec![
+        SampleTree::file("file10", 3.into()),
+        SampleTree::dir("dir", 1.into(), vec![SampleTree::file("a", 1.into())]),
+        SampleTree::file("file2", 2.into()),
+        SampleTree::file("file1", 2.into()),
+        SampleTree::dir(
+            "more",
+            0.into(),
+            vec![
+                SampleTree::file("a", 0.into()),
+                SampleTree::file("b", 0.into()),
+            ],
+        ),
+    ]
+}
+
+fn sorted_names(sort_key: SortKey) -> Vec<&'static str> {
+    let mut children = sample_children();
+    sort_key.sort(&mut children);
+    children.iter().map(|child| *child.name()).collect()
+}
+
+#[test]
+fn natural_order() {
+    assert_eq!(natural_cmp("file2", "file10"), Ordering::Less);
+    assert_eq!(natural_cmp("file10", "file2"), Ordering::Greater);
+    assert_eq!(natural_cmp("file02", "file2"), Ordering::Equal);
+    assert_eq!(natural_cmp("v1.9.0", "v1.10.0"), Ordering::Less);
+    assert_eq!(natural_cmp("abc", "abd"), Ordering::Less);
+    assert_eq!(natural_cmp("abc", "abc1"), Ordering::Less);
+    assert_eq!(natural_cmp("", ""), Ordering::Equal);
+}
+
+#[test]
+fn size_descending() {
+    let actual = sorted_names(SortKey::SizeDescending);
+    let expected = ["file10", "dir", "file1", "file2", "more"];
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn size_ascending() {
+    let actual = sorted_names(SortKey::SizeAscending);
+    let expected = ["more", "dir", "file1", "file2", "file10"];
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn name() {
+    let actual = sorted_names(SortKey::Name);
+    let expected = ["dir", "file1", "file10", "file2", "more"];
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn natural_name() {
+    let actual = sorted_names(SortKey::NaturalName);
+    let expected = ["dir", "file1", "file2", "file10", "more"];
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn item_count() {
+    let actual = sorted_names(SortKey::ItemCount);
+    let expected = ["more", "dir", "file1", "file10", "file2"];
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn modification_time() {
+    let time = |secs: u64| Some(SystemTime::UNIX_EPOCH + Duration::from_secs(secs));
+    let file = |name: &'static str, size: u64, modified| {
+        DataTree::file(name, Timed::new(Bytes::new(size), modified))
+    };
+    let mut children = vec![
+        file("old", 3, time(100)),
+        file("unknown", 5, None),
+        file("new", 1, time(300)),
+        DataTree::dir(
+            "dir",
+            Timed::new(Bytes::new(1), time(50)),
+            vec![file("newer", 1, time(200)), file("older", 1, time(10))],
+        ),
+        file("also new", 1, time(300)),
+    ];
+    SortKey::ModificationTime.sort_timed(&mut children);
+    let actual: Vec<_> = children.iter().map(|child| *child.name()).collect();
+    let expected = ["also new", "new", "dir", "old", "unknown"];
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn item_count_of_whole_tree() {
+    let mut tree = SampleTree::dir(
+        "root",
+        0.into(),
+        vec![
+            SampleTree::dir(
+                "one",
+                0.into(),
+                vec![SampleTree::dir(
+                    "nested",
+                    0.into(),
+                    vec![
+                        SampleTree::file("z", 1.into()),
+                        SampleTree::dir("y", 0.into(), vec![SampleTree::file("x", 1.into())]),
+                    ],
+                )],
+            ),
+            SampleTree::file("two", 9.into()),
+            SampleTree::dir("three", 0.into(), vec![SampleTree::file("a", 1.into())]),
+        ],
+    );
+    SortKey::ItemCount.sort_tree(&mut tree);
+    let names = |tree: &SampleTree| -> Vec<&'static str> {
+        tree.children().iter().map(|child| *child.name()).collect()
+    };
+    assert_eq!(names(&tree), ["one", "three", "two"]);
+    assert_eq!(names(&tree.children()[0].children()[0]), ["y", "z"]);
+    assert_eq!(tree.item_count(), 9);
+}
+
+#[test]
+fn whole_tree_keys() {
+    assert!(SortKey::ItemCount.requires_whole_tree());
+    let mut tree = SampleTree::dir("root", 0.into(), sample_children());
+    SortKey::SizeDescending.sort_tree(&mut tree);
+    let actual: Vec<_> = tree.children().iter().map(|child| *child.name()).collect();
+    let expected = ["file10", "dir", "file2", "file1", "more"]; // left as it is.
+    assert_eq!(actual, expected);
+}
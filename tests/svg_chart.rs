@@ -0,0 +1,93 @@
+pub mod _utils;
+pub use _utils::*;
+
+use dirt::{
+    bytes_format::BytesFormat,
+    output_format::OutputFormat,
+    svg_chart::{ChartKind, ColorScheme, SvgChart, DEFAULT_SVG_HEIGHT, DEFAULT_SVG_WIDTH},
+};
+use pretty_assertions::assert_eq;
+use std::num::NonZeroUsize;
+use text_block_macros::text_block_fnl;
+
+fn chart(kind: ChartKind, color_scheme: ColorScheme, max_depth: usize) -> String {
+    SvgChart {
+        data_tree: &format_sample_tree(),
+        bytes_format: BytesFormat::PlainNumber,
+        max_depth: NonZeroUsize::new(max_depth).expect("non-zero max_depth"),
+        kind,
+        color_scheme,
+        width: 400.0,
+        height: 300.0,
+    }
+    .to_string()
+}
+
+/// Paths in the titles of the rectangles, in the order they are drawn.
+fn drawn_paths(svg: &str) -> Vec<&str> {
+    svg.split("<g><title>")
+        .skip(1)
+        .map(|group| group.split("</title>").next().expect("group has a title"))
+        .map(|title| title.rsplitn(3, ' ').nth(2).expect("title has a path"))
+        .collect()
+}
+
+#[test]
+fn icicle_by_depth() {
+    let actual = chart(ChartKind::Icicle, ColorScheme::Depth, 10);
+    let expected = text_block_fnl! {
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="400" height="300" viewBox="0 0 400 300" font-family="sans-serif" font-size="12">"#
+        r##"<g><title>root 1000 (100.0%)</title><rect x="0.00" y="0.00" width="400.00" height="100.00" fill="#8cb4d9" stroke="#ffffff"/><text x="4.00" y="12.00">root 1000</text></g>"##
+        r##"<g><title>root/a, &quot;b&quot; 750 (75.0%)</title><rect x="0.00" y="100.00" width="300.00" height="100.00" fill="#f6b26b" stroke="#ffffff"/><text x="4.00" y="112.00">a, &quot;b&quot; 750</text></g>"##
+        "<g><title>root/a, &quot;b&quot;/x\t&lt;y&gt;;z 600 (60.0%)</title><rect x=\"0.00\" y=\"200.00\" width=\"240.00\" height=\"100.00\" fill=\"#ea9999\" stroke=\"#ffffff\"/><text x=\"4.00\" y=\"212.00\">x\t&lt;y&gt;;z 600</text></g>"
+        "<g><title>root/a, &quot;b&quot;/c\\d\ne|*_ 150 (15.0%)</title><rect x=\"240.00\" y=\"200.00\" width=\"60.00\" height=\"100.00\" fill=\"#ea9999\" stroke=\"#ffffff\"/><text x=\"244.00\" y=\"212.00\">c\\d\ne|*_</text></g>"
+        r##"<g><title>root/a, &quot;b&quot;/empty 0 (0.0%)</title><rect x="300.00" y="200.00" width="0.00" height="100.00" fill="#ea9999" stroke="#ffffff"/></g>"##
+        r##"<g><title>root/2021. &lt;/script&gt; 250 (25.0%)</title><rect x="300.00" y="100.00" width="100.00" height="100.00" fill="#f6b26b" stroke="#ffffff"/></g>"##
+        "</svg>"
+    };
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn icicle_within_max_depth() {
+    let actual = chart(ChartKind::Icicle, ColorScheme::Depth, 2);
+    assert_eq!(
+        drawn_paths(&actual),
+        [
+            "root",
+            "root/a, &quot;b&quot;",
+            "root/2021. &lt;/script&gt;"
+        ],
+    );
+    assert!(actual.contains(r#"<rect x="0.00" y="0.00" width="400.00" height="150.00""#));
+}
+
+#[test]
+fn treemap_by_extension() {
+    let actual = chart(ChartKind::Treemap, ColorScheme::Extension, 10);
+    // Parents are drawn before their children, so that the children are on top, and the
+    // empty directory is not drawn.
+    assert_eq!(
+        drawn_paths(&actual),
+        [
+            "root",
+            "root/a, &quot;b&quot;",
+            "root/a, &quot;b&quot;/x\t&lt;y&gt;;z",
+            "root/a, &quot;b&quot;/c\\d\ne|*_",
+            "root/2021. &lt;/script&gt;",
+        ],
+    );
+    assert!(actual
+        .contains(r##"<rect x="0.00" y="0.00" width="400.00" height="300.00" fill="#eeeeee""##));
+    assert_eq!(actual.matches(r##"fill="#bbbbbb""##).count(), 2); // the names without extensions.
+}
+
+#[test]
+fn default_size_of_the_cli() {
+    let actual = render_format_sample(OutputFormat::SvgTreemap, 10);
+    assert!(actual.starts_with(&format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}""#,
+        w = DEFAULT_SVG_WIDTH,
+        h = DEFAULT_SVG_HEIGHT,
+    )));
+}
@@ -0,0 +1,71 @@
+pub mod _utils;
+pub use _utils::*;
+
+use dirt::{
+    bytes_format::BytesFormat,
+    output_format::OutputFormat,
+    tabular::{Delimiter, Tabular},
+};
+use pretty_assertions::assert_eq;
+use std::num::NonZeroUsize;
+use text_block_macros::text_block_fnl;
+
+#[test]
+fn comma_separated_values() {
+    let actual = render_format_sample(OutputFormat::Csv, 10);
+    let expected = text_block_fnl! {
+        "depth,path,size,formatted_size,percent_of_root,percent_of_parent,children"
+        "0,root,1000,1000,100.00,100.00,2"
+        "1,\"root/a, \"\"b\"\"\",750,750,75.00,75.00,3"
+        "2,\"root/a, \"\"b\"\"/x\t<y>;z\",600,600,60.00,80.00,0"
+        "2,\"root/a, \"\"b\"\"/c\\d\ne|*_\",150,150,15.00,20.00,0"
+        "2,\"root/a, \"\"b\"\"/empty\",0,0,0.00,0.00,0"
+        "1,root/2021. </script>,250,250,25.00,25.00,0"
+    };
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn tab_separated_values() {
+    let actual = render_format_sample(OutputFormat::Tsv, 10);
+    let expected = text_block_fnl! {
+        "depth\tpath\tsize\tformatted_size\tpercent_of_root\tpercent_of_parent\tchildren"
+        "0\troot\t1000\t1000\t100.00\t100.00\t2"
+        "1\troot/a, \"b\"\t750\t750\t75.00\t75.00\t3"
+        "2\troot/a, \"b\"/x\\t<y>;z\t600\t600\t60.00\t80.00\t0"
+        "2\troot/a, \"b\"/c\\\\d\\ne|*_\t150\t150\t15.00\t20.00\t0"
+        "2\troot/a, \"b\"/empty\t0\t0\t0.00\t0.00\t0"
+        "1\troot/2021. </script>\t250\t250\t25.00\t25.00\t0"
+    };
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn honour_max_depth() {
+    let actual = render_format_sample(OutputFormat::Csv, 2);
+    let expected = text_block_fnl! {
+        "depth,path,size,formatted_size,percent_of_root,percent_of_parent,children"
+        "0,root,1000,1000,100.00,100.00,2"
+        "1,\"root/a, \"\"b\"\"\",750,750,75.00,75.00,3"
+        "1,root/2021. </script>,250,250,25.00,25.00,0"
+    };
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn root_path_with_trailing_slash() {
+    let tree = sample_dir("/", vec![sample_file("etc", 0)]);
+    let actual = Tabular {
+        data_tree: &tree,
+        bytes_format: BytesFormat::PlainNumber,
+        max_depth: NonZeroUsize::new(10).expect("non-zero max_depth"),
+        delimiter: Delimiter::Comma,
+    }
+    .to_string();
+    let expected = text_block_fnl! {
+        "depth,path,size,formatted_size,percent_of_root,percent_of_parent,children"
+        "0,/,0,0,0.00,0.00,1"
+        "1,/etc,0,0,0.00,0.00,0"
+    };
+    assert_eq!(actual, expected);
+}
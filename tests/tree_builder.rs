@@ -48,10 +48,13 @@ impl SampleTree {
                     .collect();
                 let mut path = path.iter();
                 match self.0.path(&mut path) {
-                    Some(FileSystemTree::File(content)) => Info::from((len(content), Vec::new())),
+                    Some(FileSystemTree::File(content)) => {
+                        Info::from((len(content), Vec::new(), false))
+                    }
                     Some(FileSystemTree::Directory(content)) => Info::from((
                         SAMPLE_DIR_SIZE,
                         content.keys().map(ToString::to_string).collect(),
+                        true,
                     )),
                     None => panic!("Path does not exist"),
                 }
@@ -25,6 +25,7 @@ macro_rules! test_case {
         max_depth = $max_depth:expr,
         column_width_distribution = $column_width_function:ident $($column_width_arguments:literal)+,
         direction = $direction:ident,
+        $(proportion_total = $proportion_total:expr,)?
         expected = $expected:expr,
     ) => {
         $(#[$attributes])*
@@ -34,12 +35,14 @@ macro_rules! test_case {
             let column_width_distribution =
                 ColumnWidthDistribution::$column_width_function($($column_width_arguments),+);
             let max_depth = NonZeroUsize::new($max_depth).expect("non-zero max_depth");
+            let proportion_total: Option<u64> = None $(.or(Some($proportion_total)))?;
             let actual = Visualizer {
                 max_depth,
                 column_width_distribution,
                 data_tree: &tree,
                 bytes_format: $bytes_format,
                 direction: Direction::$direction,
+                proportion_total,
             }
             .to_string();
             let expected = $expected;
@@ -393,6 +396,36 @@ test_case! {
         },
 }
 
+test_case! {
+    nested_relative_to_larger_total where
+        tree = nested_tree::<Bytes>(&["a", "b"], 4096.into(), "z", 1024.into()),
+        bytes_format = BinaryUnits,
+        max_depth = 10,
+        column_width_distribution = total 90,
+        direction = BottomUp,
+        proportion_total = 4 * (4096 * 2 + 1024),
+        expected = text_block_fnl! {
+            "1K     ┌──z│                                                               ░░░░░░░░██│  3%"
+            "5K   ┌─┴b  │                                                               ██████████│ 14%"
+            "9K ┌─┴a    │                                                       ██████████████████│ 25%"
+        },
+}
+
+test_case! {
+    nested_relative_to_smaller_total where
+        tree = nested_tree::<Bytes>(&["a", "b"], 4096.into(), "z", 1024.into()),
+        bytes_format = BinaryUnits,
+        max_depth = 10,
+        column_width_distribution = total 90,
+        direction = BottomUp,
+        proportion_total = 1,
+        expected = text_block_fnl! {
+            "1K     ┌──z│                                ░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░████████│ 11%"
+            "5K   ┌─┴b  │                                █████████████████████████████████████████│ 56%"
+            "9K ┌─┴a    │█████████████████████████████████████████████████████████████████████████│100%"
+        },
+}
+
 test_case! {
     nested_bottom_up_metric where
         tree = nested_tree::<Bytes>(
@@ -0,0 +1,107 @@
+use dirt::{
+    data_tree::{DataTree, DataTreeReflection},
+    os_string_display::OsStringDisplay,
+    size::Bytes,
+    weighted_tree::{parse_weighted_text, ParseError, WeightedEntry, WeightedTreeBuilder},
+};
+use pipe_trait::Pipe;
+use pretty_assertions::assert_eq;
+use text_block_macros::text_block_fnl;
+
+type SampleReflection = DataTreeReflection<OsStringDisplay, Bytes>;
+
+fn entry(key: &[&str], weight: u64) -> WeightedEntry {
+    WeightedEntry {
+        key: key.iter().map(|component| component.to_string()).collect(),
+        weight,
+    }
+}
+
+fn node(name: &str, data: u64, children: Vec<SampleReflection>) -> SampleReflection {
+    DataTreeReflection {
+        name: OsStringDisplay::os_string_from(name),
+        data: data.into(),
+        children,
+    }
+}
+
+fn sort_by_name(children: &mut Vec<DataTree<OsStringDisplay, Bytes>>) {
+    children.sort_by(|left, right| left.name().cmp(right.name()));
+}
+
+#[test]
+fn parse_folded_stacks() {
+    let text = text_block_fnl! {
+        "main;parse;read 42"
+        ""
+        "main;parse 8"
+        "  main;render frame;draw\t 50  "
+    };
+    let actual = parse_weighted_text(text, ";");
+    let expected = Ok(vec![
+        entry(&["main", "parse", "read"], 42),
+        entry(&["main", "parse"], 8),
+        entry(&["main", "render frame", "draw"], 50),
+    ]);
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn parse_path_like_keys() {
+    let actual = parse_weighted_text("/srv//db/users 100\nlog 1\n", "/");
+    let expected = Ok(vec![
+        entry(&["srv", "db", "users"], 100),
+        entry(&["log"], 1),
+    ]);
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn reject_missing_weight() {
+    let actual = parse_weighted_text("a;b 1\na;b\n", ";");
+    assert_eq!(actual, Err(ParseError::InvalidLine(2)));
+}
+
+#[test]
+fn build_tree() {
+    let entries = vec![
+        entry(&["main", "parse", "read"], 42),
+        entry(&["main", "parse"], 8),
+        entry(&["main", "render", "draw"], 50),
+        entry(&["main", "parse", "read"], 3),
+        entry(&[], 7),
+    ];
+    let actual = WeightedTreeBuilder {
+        name: OsStringDisplay::os_string_from("(total)"),
+        entries,
+        post_process_children: sort_by_name,
+    }
+    .pipe(DataTree::<OsStringDisplay, Bytes>::from)
+    .into_reflection();
+    let expected = node(
+        "(total)",
+        110,
+        vec![node(
+            "main",
+            103,
+            vec![
+                node("parse", 53, vec![node("read", 45, Vec::new())]),
+                node("render", 50, vec![node("draw", 50, Vec::new())]),
+            ],
+        )],
+    );
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn saturate_repeated_weights() {
+    let entries = vec![entry(&[], u64::MAX), entry(&[], 1)];
+    let actual = WeightedTreeBuilder {
+        name: OsStringDisplay::os_string_from("(total)"),
+        entries,
+        post_process_children: sort_by_name,
+    }
+    .pipe(DataTree::<OsStringDisplay, Bytes>::from)
+    .into_reflection();
+    assert_eq!(actual, node("(total)", u64::MAX, Vec::new()));
+}